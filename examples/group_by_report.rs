@@ -0,0 +1,23 @@
+//! Report total units and mean price per region.
+//!
+//! ```text
+//! cargo run --example group_by_report -- examples/data/sales.csv
+//! ```
+use nn::prelude::*;
+
+fn main() -> Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data/sales.csv".to_owned());
+
+    let df = CsvParser::parse(&path)?;
+
+    let report = df
+        .group_by(&["region"])?
+        .agg(&[("units", Agg::Sum), ("price", Agg::Mean)])?
+        .sort_by(&[("region", SortOrder::Asc)])?;
+
+    report.show(report.len());
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Join sales records with the region managers on `region`
+//! (left join: sales without a known region keep an empty manager).
+//!
+//! ```text
+//! cargo run --example join -- examples/data/sales.csv examples/data/regions.csv
+//! ```
+use nn::prelude::*;
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let left = args
+        .next()
+        .unwrap_or_else(|| "examples/data/sales.csv".to_owned());
+    let right = args
+        .next()
+        .unwrap_or_else(|| "examples/data/regions.csv".to_owned());
+
+    let sales = CsvParser::parse(&left)?;
+    let regions = CsvParser::parse(&right)?.select(&["region", "manager"])?;
+
+    let report = sales.join(&regions, &["region"], JoinType::Left)?;
+    report.show(report.len());
+
+    Ok(())
+}
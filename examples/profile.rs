@@ -0,0 +1,29 @@
+//! Profile a CSV file: size, column types and null count per column.
+//!
+//! ```text
+//! cargo run --example profile -- examples/data/sales.csv
+//! ```
+use nn::prelude::*;
+
+fn main() -> Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data/sales.csv".to_owned());
+
+    let df = CsvParser::parse(&path)?;
+    println!("{}: {} rows x {} columns", path, df.len(), df.hlen());
+
+    for (name, dtype) in df.header().iter().zip(df.dtypes()) {
+        let nulls = df.iter_col(name).map_or(0, |col| {
+            col.filter(|cell| matches!(cell, Cell::Null)).count()
+        });
+        println!(
+            "{:<16} {:<8} nulls: {}",
+            name,
+            format!("{:?}", dtype),
+            nulls
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+//! Convert a CSV file to a JSON array of records, or an object of
+//! columns with `--columns`.
+//!
+//! ```text
+//! cargo run --example to_json --features io-json -- examples/data/sales.csv
+//! ```
+use nn::io::json::Orientation;
+use nn::prelude::*;
+
+fn main() -> Result<()> {
+    let mut path = "examples/data/sales.csv".to_owned();
+    let mut orientation = Orientation::Records;
+
+    std::env::args().skip(1).for_each(|arg| match arg.as_str() {
+        "--columns" => orientation = Orientation::Columns,
+        _ => path = arg,
+    });
+
+    let df = CsvParser::parse(&path)?;
+    df.write_json(std::io::stdout(), orientation)?;
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Cancellation of a parse running on other threads.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Flag telling the threads of a parse to stop, shared by its clones.
+///
+/// Given to the parser with [`ParserOptions::cancel_token`], the token
+/// is checked every few thousand cells. Once it is cancelled, the
+/// parse stops and fails with [`Error::Cancelled`], reporting the
+/// rows read so far.
+///
+/// ```
+/// use nn::cancel::CancelToken;
+/// use nn::error::Error;
+/// use nn::options::ParserOptions;
+/// use nn::parser::CsvParser;
+///
+/// let token = CancelToken::new();
+/// let options = ParserOptions::new().cancel_token(token.clone());
+///
+/// // Cancelled from another thread, here before the parse starts
+/// std::thread::spawn(move || token.cancel()).join().unwrap();
+/// let result = CsvParser::parse_buffer_with_options(b"a\n1\n2\n", 1, &options);
+/// assert!(matches!(result, Err(Error::Cancelled { rows: 0 })));
+/// ```
+///
+/// [`ParserOptions::cancel_token`]: crate::options::ParserOptions::cancel_token
+/// [`Error::Cancelled`]: crate::error::Error::Cancelled
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Token not cancelled yet
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tell the parses holding the token to stop
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once [`Self::cancel`] is called on any clone
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -1,4 +1,10 @@
+use crate::datetime::{Date, Duration, Time};
+use crate::decimal::Decimal128;
+use crate::encoding::BytesEncoding;
+use crate::io::float::RoundTripF64;
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cell {
     /// Null
     Null,
@@ -6,47 +12,340 @@ pub enum Cell {
     String(String),
     /// Number
     Number(i64),
+    /// Unsigned number too large for a `Number`
+    UInt(u64),
     /// Decimal value
     Decimal(f64),
+    /// Exact decimal value, boxed so that it does not grow every cell
+    Decimal128(Box<Decimal128>),
+    /// Calendar date
+    Date(Date),
+    /// Time of day
+    Time(Time),
+    /// Length of time
+    Duration(Duration),
+    /// Binary payload
+    Bytes(Vec<u8>),
+    /// Boolean
+    Bool(bool),
+    /// String stored as its code in the dictionary of the column, see
+    /// [`DataFrame::categories`]
+    ///
+    /// [`DataFrame::categories`]: crate::dframe::DataFrame::categories
+    Categorical(u32),
+}
+
+impl Cell {
+    /// Data type of the cell
+    #[inline(always)]
+    pub fn cell_type(&self) -> CellType {
+        match self {
+            Self::Null => CellType::Null,
+            Self::String(_) => CellType::String,
+            Self::Number(_) => CellType::I64,
+            Self::UInt(_) => CellType::U64,
+            Self::Decimal(_) => CellType::F64,
+            Self::Decimal128(_) => CellType::Decimal128,
+            Self::Date(_) => CellType::Date,
+            Self::Time(_) => CellType::Time,
+            Self::Duration(_) => CellType::Duration,
+            Self::Bytes(_) => CellType::Bytes,
+            Self::Bool(_) => CellType::Bool,
+            Self::Categorical(_) => CellType::Categorical,
+        }
+    }
+
+    /// Numeric value of the cell, `None` if it is not a number
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => Some(*number as f64),
+            Self::UInt(number) => Some(*number as f64),
+            Self::Decimal(decimal) => Some(*decimal),
+            Self::Decimal128(decimal) => Some(decimal.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Text of string cells, `None` for other cells. Categories hold
+    /// only their code, their text is in the dictionary of the column.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Text of string cells, and of categories coded in `dictionary`,
+    /// the dictionary of the column of the cell
+    #[cfg(feature = "dataframe")]
+    #[inline]
+    pub(crate) fn text<'a>(
+        &'a self,
+        dictionary: Option<&'a [String]>,
+    ) -> Option<&'a str> {
+        match self {
+            Self::String(string) => Some(string),
+            Self::Categorical(code) => {
+                dictionary?.get(*code as usize).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+
+    /// Hashable key of the cell, with categories keyed by the string
+    /// they stand for in `dictionary`, so that they group with strings
+    #[cfg(feature = "dataframe")]
+    #[inline]
+    pub(crate) fn key_in<'a>(
+        &'a self,
+        dictionary: Option<&'a [String]>,
+    ) -> CellKey<'a> {
+        match self.text(dictionary) {
+            Some(text) => CellKey::String(text),
+            None => self.key(),
+        }
+    }
+
+    /// Copy of the cell, with categories turned into the strings they
+    /// stand for in `dictionary`
+    #[cfg(feature = "dataframe")]
+    #[inline]
+    pub(crate) fn decoded(&self, dictionary: Option<&[String]>) -> Cell {
+        match self.text(dictionary) {
+            Some(text) if matches!(self, Self::Categorical(_)) => {
+                Cell::String(text.to_owned())
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Hashable key of the cell, equal for equal cells
+    #[cfg(feature = "dataframe")]
+    #[inline]
+    pub(crate) fn key(&self) -> CellKey<'_> {
+        match self {
+            Self::Null => CellKey::Null,
+            Self::String(string) => CellKey::String(string),
+            Self::Number(number) => CellKey::Number(*number),
+            // Unsigned numbers group with the equal signed numbers
+            Self::UInt(number) => match i64::try_from(*number) {
+                Ok(number) => CellKey::Number(number),
+                Err(_) => CellKey::UInt(*number),
+            },
+            // Both zeros are equal, and all NaNs are grouped together
+            Self::Decimal(decimal) if *decimal == 0.0 => CellKey::Decimal(0),
+            Self::Decimal(decimal) if decimal.is_nan() => {
+                CellKey::Decimal(f64::NAN.to_bits())
+            }
+            Self::Decimal(decimal) => CellKey::Decimal(decimal.to_bits()),
+            Self::Decimal128(decimal) => CellKey::Decimal128(**decimal),
+            Self::Date(date) => CellKey::Date(*date),
+            Self::Time(time) => CellKey::Time(*time),
+            Self::Duration(duration) => CellKey::Duration(*duration),
+            Self::Bytes(bytes) => CellKey::Bytes(bytes),
+            Self::Bool(value) => CellKey::Bool(*value),
+            // Categories of the same column group by their code
+            Self::Categorical(code) => CellKey::Category(*code),
+        }
+    }
+}
+
+impl From<Decimal128> for Cell {
+    #[inline]
+    fn from(decimal: Decimal128) -> Self {
+        Self::Decimal128(Box::new(decimal))
+    }
+}
+
+impl std::fmt::Display for Cell {
+    /// Text of the cell: `null` for nulls, shortest round trip digits
+    /// for decimals, base64 for bytes and the code of categories
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => f.pad("null"),
+            Self::String(string) => f.pad(string),
+            Self::Number(number) => f.pad(&number.to_string()),
+            Self::UInt(number) => f.pad(&number.to_string()),
+            Self::Decimal(decimal) => {
+                f.pad(&RoundTripF64(*decimal).to_string())
+            }
+            Self::Decimal128(decimal) => decimal.fmt(f),
+            Self::Date(date) => f.pad(&date.to_string()),
+            Self::Time(time) => f.pad(&time.to_string()),
+            Self::Duration(duration) => f.pad(&duration.to_string()),
+            Self::Bytes(bytes) => f.pad(&BytesEncoding::Base64.encode(bytes)),
+            Self::Bool(value) => f.pad(if *value { "true" } else { "false" }),
+            Self::Categorical(code) => f.pad(&code.to_string()),
+        }
+    }
+}
+
+/// Borrowed view of a `Cell` usable as a hash map key, decimals are
+/// compared by their bits
+#[cfg(feature = "dataframe")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum CellKey<'a> {
+    Null,
+    String(&'a str),
+    Number(i64),
+    UInt(u64),
+    Decimal(u64),
+    Decimal128(Decimal128),
+    Date(Date),
+    Time(Time),
+    Duration(Duration),
+    Bytes(&'a [u8]),
+    Bool(bool),
+    Category(u32),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellType {
     /// String data type
     String,
     /// Signed Integer
     I64,
+    /// Unsigned Integer, for columns holding integers above `i64::MAX`
+    U64,
     /// Floating Number
     F64,
+    /// Exact decimal number
+    Decimal128,
     /// Empty or null
     Null,
+    /// Calendar date
+    Date,
+    /// Time of day
+    Time,
+    /// Length of time
+    Duration,
+    /// Binary payload
+    Bytes,
+    /// Boolean
+    Bool,
+    /// Strings stored as codes in a shared dictionary
+    Categorical,
 }
 
 impl CellType {
+    /// Type named `name`, as written by its `Display` implementation
+    /// (`str`, `i64`, `u64`, `f64`, `dec`, `null`, `date`, `time`,
+    /// `duration`, `bytes`, `bool` or `cat`), `None` for other names
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "str" => Self::String,
+            "i64" => Self::I64,
+            "u64" => Self::U64,
+            "f64" => Self::F64,
+            "dec" => Self::Decimal128,
+            "null" => Self::Null,
+            "date" => Self::Date,
+            "time" => Self::Time,
+            "duration" => Self::Duration,
+            "bytes" => Self::Bytes,
+            "bool" => Self::Bool,
+            "cat" => Self::Categorical,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` for the types of numbers: integers, unsigned
+    /// integers, decimals and exact decimals
+    #[inline(always)]
+    pub fn is_numeric(self) -> bool {
+        matches!(self, Self::I64 | Self::U64 | Self::F64 | Self::Decimal128)
+    }
+
+    /// Type of a column holding values of both `self` and `current_type`:
+    /// nulls fit any type, signed integers promote to unsigned ones
+    /// (columns holding negative integers are read as decimals once
+    /// parsed), integers promote to decimals and anything else
+    /// (including exact decimals, dates, times, durations, bytes and
+    /// booleans mixed with other values) falls back to string.
     #[inline(always)]
-    pub(crate) fn infer_type(&self, current_type: Self) -> Self {
+    pub fn infer_type(&self, current_type: Self) -> Self {
         match self {
             Self::Null => current_type,
             Self::F64 => current_type.infer_from_f64(),
             Self::I64 => current_type.infer_from_i64(),
+            Self::U64 => current_type.infer_from_u64(),
+            Self::Decimal128
+            | Self::Date
+            | Self::Time
+            | Self::Duration
+            | Self::Bytes
+            | Self::Bool
+            | Self::Categorical => current_type.infer_exact(*self),
             _ => Self::String,
         }
     }
 
     #[inline(always)]
     fn infer_from_f64(self) -> Self {
-        if self == Self::String {
-            return Self::String;
+        // Integers and nulls fit in a decimal column
+        if let Self::I64 | Self::U64 | Self::F64 | Self::Null = self {
+            Self::F64
+        } else {
+            Self::String
         }
-
-        self
     }
     #[inline(always)]
     pub fn infer_from_i64(self) -> Self {
-        if let Self::I64 | Self::Null = self {
-            Self::I64
+        match self {
+            Self::I64 | Self::Null => Self::I64,
+            Self::U64 => Self::U64,
+            Self::F64 => Self::F64,
+            _ => Self::String,
+        }
+    }
+    #[inline(always)]
+    fn infer_from_u64(self) -> Self {
+        match self {
+            Self::I64 | Self::U64 | Self::Null => Self::U64,
+            Self::F64 => Self::F64,
+            _ => Self::String,
+        }
+    }
+    #[inline(always)]
+    fn infer_exact(self, cell_type: Self) -> Self {
+        if self == cell_type || self == Self::Null {
+            cell_type
         } else {
-            self
+            Self::String
         }
     }
 }
+
+impl std::fmt::Display for CellType {
+    /// Short name of the type: `str`, `i64`, `u64`, `f64`, `dec`,
+    /// `null`, `date`, `time`, `duration`, `bytes`, `bool` or `cat`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::String => "str",
+            Self::I64 => "i64",
+            Self::U64 => "u64",
+            Self::F64 => "f64",
+            Self::Decimal128 => "dec",
+            Self::Null => "null",
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::Duration => "duration",
+            Self::Bytes => "bytes",
+            Self::Bool => "bool",
+            Self::Categorical => "cat",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cell;
+
+    #[test]
+    fn exact_decimals_do_not_grow_cells() {
+        assert_eq!(std::mem::size_of::<Cell>(), 32);
+    }
+}
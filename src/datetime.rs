@@ -0,0 +1,861 @@
+/// Order of day, month and year in dates written with numeric
+/// fields only (e.g. `05/01/2024`), used to resolve ambiguous dates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateOrder {
+    /// Day, month, year (`31/01/2024`)
+    DMY,
+    /// Month, day, year (`01/31/2024`)
+    MDY,
+    /// Year, month, day (`2024/01/31`)
+    YMD,
+}
+
+/// Calendar date, stored as days since `1970-01-01`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date(i32);
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct",
+    "nov", "dec",
+];
+
+/// Returns `true` for leap years of the proleptic Gregorian calendar
+#[inline]
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Total days in `month` of `year`
+#[inline]
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Date from year, month (1-12) and day (1-31), `None` if the date
+    /// does not exist
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return None;
+        }
+
+        // Days from civil (H. Hinnant), with years starting in March
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month_from_march = (month + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4
+            - year_of_era / 100
+            + day_of_year as i32;
+
+        Some(Self(era * 146097 + day_of_era - 719468))
+    }
+
+    /// Date `days` days after `1970-01-01`
+    #[inline]
+    pub fn from_days_since_epoch(days: i32) -> Self {
+        Self(days)
+    }
+
+    /// Total days since `1970-01-01`
+    #[inline]
+    pub fn days_since_epoch(self) -> i32 {
+        self.0
+    }
+
+    /// Year, month (1-12) and day (1-31) of the date
+    pub fn ymd(self) -> (i32, u32, u32) {
+        // Civil from days (H. Hinnant)
+        let days = self.0 + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+            - day_of_era / 146096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i32::from(month <= 2);
+
+        (year, month, day)
+    }
+
+    /// Parse `text` with a `strftime` like `format`.
+    ///
+    /// Supported directives: `%Y` (year), `%y` (two digit year, 1970 to
+    /// 2069), `%m` (month), `%d` (day), `%b` (abbreviated month name) and
+    /// `%%`. Other characters of the format should match as is.
+    pub fn parse_with_format(text: &str, format: &str) -> Option<Self> {
+        let (mut text, mut format) = (text.as_bytes(), format.as_bytes());
+        let (mut year, mut month, mut day) = (None, None, None);
+
+        while let Some((&f, rest)) = format.split_first() {
+            format = rest;
+
+            if f != b'%' {
+                text = text.strip_prefix(&[f])?;
+                continue;
+            }
+
+            let (&directive, rest) = format.split_first()?;
+            format = rest;
+
+            match directive {
+                b'Y' => {
+                    let (sign, digits) = match text.first() {
+                        Some(b'-') => (-1, &text[1..]),
+                        _ => (1, text),
+                    };
+                    let (value, rest) = take_number(digits, 4)?;
+                    year = Some(sign * value as i32);
+                    text = rest;
+                }
+                b'y' => {
+                    let (value, rest) = take_number(text, 2)?;
+                    year = Some(
+                        if value < 70 { 2000 } else { 1900 } + value as i32,
+                    );
+                    text = rest;
+                }
+                b'm' => {
+                    let (value, rest) = take_number(text, 2)?;
+                    month = Some(value);
+                    text = rest;
+                }
+                b'd' => {
+                    let (value, rest) = take_number(text, 2)?;
+                    day = Some(value);
+                    text = rest;
+                }
+                b'b' => {
+                    let name = text.get(..3)?.to_ascii_lowercase();
+                    let index = MONTH_NAMES
+                        .iter()
+                        .position(|month| month.as_bytes() == name)?;
+                    month = Some(index as u32 + 1);
+                    text = &text[3..];
+                }
+                b'%' => text = text.strip_prefix(b"%")?,
+                _ => return None,
+            }
+        }
+
+        if !text.is_empty() {
+            return None;
+        }
+
+        Self::from_ymd(year?, month?, day?)
+    }
+
+    /// Parse a date made of three numeric fields separated by `-`, `/` or
+    /// `.`, with a four digit year.
+    ///
+    /// Dates starting with the year (`2024-01-31`) are always read as
+    /// year, month, day; others are resolved with `order`.
+    pub fn parse_with_order(text: &str, order: DateOrder) -> Option<Self> {
+        let separator =
+            text.bytes().find(|c| matches!(c, b'-' | b'/' | b'.'))?;
+        let mut fields = text.split(separator as char);
+        let (first, second, third) =
+            (fields.next()?, fields.next()?, fields.next()?);
+
+        if fields.next().is_some()
+            || [first, second, third].iter().any(|field| {
+                field.is_empty() || !field.bytes().all(|c| c.is_ascii_digit())
+            })
+        {
+            return None;
+        }
+
+        let (year, month, day) = match (first.len(), order) {
+            (4, _) | (_, DateOrder::YMD) => (first, second, third),
+            (_, DateOrder::DMY) => (third, second, first),
+            (_, DateOrder::MDY) => (third, first, second),
+        };
+
+        if year.len() != 4 || month.len() > 2 || day.len() > 2 {
+            return None;
+        }
+
+        Self::from_ymd(
+            year.parse().ok()?,
+            month.parse().ok()?,
+            day.parse().ok()?,
+        )
+    }
+}
+
+/// Read at most `max_digits` leading digits of `text`
+#[inline]
+fn take_number(text: &[u8], max_digits: usize) -> Option<(u32, &[u8])> {
+    let len = text
+        .iter()
+        .take(max_digits)
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+
+    if len == 0 {
+        return None;
+    }
+
+    let value = text[..len]
+        .iter()
+        .fold(0, |value, c| value * 10 + u32::from(c - b'0'));
+
+    Some((value, &text[len..]))
+}
+
+impl std::fmt::Display for Date {
+    /// ISO 8601 date: `YYYY-MM-DD`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Nanoseconds in a second
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+/// Nanoseconds in a day
+const NANOS_PER_DAY: i64 = 86_400 * NANOS_PER_SECOND;
+
+/// Time of day, stored as nanoseconds since midnight
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Time(u64);
+
+/// Signed length of time, stored as nanoseconds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duration(i64);
+
+/// Read the digits of `text` after a decimal point as nanoseconds,
+/// ignoring digits after the ninth one
+#[inline]
+fn parse_fraction(text: &str) -> Option<u64> {
+    if text.is_empty() || !text.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(
+        text.bytes()
+            .chain(std::iter::repeat(b'0'))
+            .take(9)
+            .fold(0, |value, c| value * 10 + u64::from(c - b'0')),
+    )
+}
+
+/// Write `nanos` (below a second) as a fraction without trailing
+/// zeros, nothing if zero
+#[inline]
+fn write_fraction(
+    f: &mut std::fmt::Formatter<'_>,
+    nanos: u64,
+) -> std::fmt::Result {
+    if nanos == 0 {
+        return Ok(());
+    }
+
+    let digits = format!("{:09}", nanos);
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+impl Time {
+    /// Time from hour (0-23), minute (0-59) and second (0-59), `None`
+    /// if out of range
+    #[inline]
+    pub fn from_hms(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        Self::from_hms_nano(hour, minute, second, 0)
+    }
+
+    /// Time from hour, minute, second and nanoseconds (below a
+    /// second), `None` if out of range
+    pub fn from_hms_nano(
+        hour: u32,
+        minute: u32,
+        second: u32,
+        nano: u32,
+    ) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 || nano >= 1_000_000_000 {
+            return None;
+        }
+
+        let seconds = u64::from(hour * 3600 + minute * 60 + second);
+        Some(Self(seconds * NANOS_PER_SECOND as u64 + u64::from(nano)))
+    }
+
+    /// Time `nanos` nanoseconds after midnight, wrapping to the next
+    /// days
+    #[inline]
+    pub fn from_nanos_since_midnight(nanos: u64) -> Self {
+        Self(nanos % NANOS_PER_DAY as u64)
+    }
+
+    /// Total nanoseconds since midnight
+    #[inline]
+    pub fn nanos_since_midnight(self) -> u64 {
+        self.0
+    }
+
+    /// Hour, minute and second of the time
+    #[inline]
+    pub fn hms(self) -> (u32, u32, u32) {
+        let seconds = (self.0 / NANOS_PER_SECOND as u64) as u32;
+        (seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    /// Nanoseconds after the last whole second
+    #[inline]
+    pub fn nanosecond(self) -> u32 {
+        (self.0 % NANOS_PER_SECOND as u64) as u32
+    }
+
+    /// Parse `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fff` (up to nanoseconds).
+    /// The hour may be written with a single digit.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (text, fraction) = match text.split_once('.') {
+            Some((text, fraction)) => (text, parse_fraction(fraction)?),
+            None => (text, 0),
+        };
+
+        let mut fields = text.split(':');
+        let hour = fields.next()?;
+        let minute = fields.next()?;
+        let second = fields.next();
+
+        if fields.next().is_some()
+            || (second.is_none() && fraction != 0)
+            || !(1..=2).contains(&hour.len())
+            || minute.len() != 2
+            || second.is_some_and(|second| second.len() != 2)
+        {
+            return None;
+        }
+
+        let number = |field: &str| {
+            field
+                .bytes()
+                .all(|c| c.is_ascii_digit())
+                .then(|| field.parse().ok())
+                .flatten()
+        };
+
+        Self::from_hms_nano(
+            number(hour)?,
+            number(minute)?,
+            second.map_or(Some(0), number)?,
+            fraction as u32,
+        )
+    }
+}
+
+impl std::fmt::Display for Time {
+    /// `HH:MM:SS`, followed by the fraction of second if any
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (hour, minute, second) = self.hms();
+        write!(f, "{:02}:{:02}:{:02}", hour, minute, second)?;
+        write_fraction(f, u64::from(self.nanosecond()))
+    }
+}
+
+impl Duration {
+    /// Duration of `nanos` nanoseconds
+    #[inline]
+    pub fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    /// Duration of `seconds` seconds, saturating at the bounds of a
+    /// `Duration` (about 292 years)
+    #[inline]
+    pub fn from_seconds(seconds: i64) -> Self {
+        Self(seconds.saturating_mul(NANOS_PER_SECOND))
+    }
+
+    /// Total nanoseconds
+    #[inline]
+    pub fn as_nanos(self) -> i64 {
+        self.0
+    }
+
+    /// Total seconds, with the fraction
+    #[inline]
+    pub fn as_seconds_f64(self) -> f64 {
+        self.0 as f64 / NANOS_PER_SECOND as f64
+    }
+
+    /// `self + rhs`, `None` on overflow
+    #[inline]
+    pub fn checked_add(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// `self - rhs`, `None` on overflow
+    #[inline]
+    pub fn checked_sub(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// `self * rhs`, `None` on overflow
+    #[inline]
+    pub fn checked_mul(self, rhs: i64) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// Parse an ISO 8601 duration with fixed length components:
+    /// `PnWnDTnHnMnS`, e.g. `PT1H30M`, `P2DT12H` or `-PT0.5S`.
+    ///
+    /// Years and months are rejected, as their length depends on the
+    /// date they apply to. Only the seconds can have a fraction.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => (-1, text),
+            None => (1, text),
+        };
+        let text = text.strip_prefix('P')?;
+        let (date, time) = match text.split_once('T') {
+            Some((_, "")) => return None,
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+
+        let mut total: i64 = 0;
+        let mut components = 0;
+        let mut add = |part: &str, units: &[(u8, i64)]| -> Option<()> {
+            let mut rest = part;
+            let mut allowed = units;
+
+            while !rest.is_empty() {
+                let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+                let (number, unit) = (&rest[..end], rest.as_bytes()[end]);
+                let position = allowed.iter().position(|(u, _)| *u == unit)?;
+                let scale = allowed[position].1;
+                allowed = &allowed[position + 1..];
+
+                let nanos = match number.split_once('.') {
+                    Some((whole, fraction)) if unit == b'S' => {
+                        let whole: i64 = whole.parse().ok()?;
+                        whole
+                            .checked_mul(scale)?
+                            .checked_add(parse_fraction(fraction)? as i64)?
+                    }
+                    _ if number.bytes().all(|c| c.is_ascii_digit()) => {
+                        number.parse::<i64>().ok()?.checked_mul(scale)?
+                    }
+                    _ => return None,
+                };
+
+                total = total.checked_add(nanos)?;
+                components += 1;
+                rest = &rest[end + 1..];
+            }
+            Some(())
+        };
+
+        let hour = 3600 * NANOS_PER_SECOND;
+        add(date, &[(b'W', 7 * NANOS_PER_DAY), (b'D', NANOS_PER_DAY)])?;
+        if let Some(time) = time {
+            add(
+                time,
+                &[
+                    (b'H', hour),
+                    (b'M', 60 * NANOS_PER_SECOND),
+                    (b'S', NANOS_PER_SECOND),
+                ],
+            )?;
+        }
+
+        (components > 0).then(|| Self(sign * total))
+    }
+}
+
+impl std::fmt::Display for Duration {
+    /// ISO 8601 duration with days, hours, minutes and seconds,
+    /// e.g. `P1DT2H30M` or `-PT0.5S`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+
+        let nanos = self.0.unsigned_abs();
+        let seconds = nanos / NANOS_PER_SECOND as u64;
+        let (days, hours) = (seconds / 86_400, seconds / 3600 % 24);
+        let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+        let fraction = nanos % NANOS_PER_SECOND as u64;
+
+        f.write_str("P")?;
+        if days > 0 {
+            write!(f, "{}D", days)?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && fraction == 0 {
+            return if days == 0 {
+                f.write_str("T0S")
+            } else {
+                Ok(())
+            };
+        }
+
+        f.write_str("T")?;
+        if hours > 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if seconds > 0 || fraction > 0 {
+            write!(f, "{}", seconds)?;
+            write_fraction(f, fraction)?;
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Add<Duration> for Time {
+    type Output = Time;
+
+    /// Time after `rhs`, wrapping around midnight
+    #[inline]
+    fn add(self, rhs: Duration) -> Time {
+        let nanos = (self.0 as i64 + rhs.0.rem_euclid(NANOS_PER_DAY))
+            .rem_euclid(NANOS_PER_DAY);
+        Time(nanos as u64)
+    }
+}
+
+impl std::ops::Sub<Duration> for Time {
+    type Output = Time;
+
+    /// Time before `rhs`, wrapping around midnight
+    #[inline]
+    fn sub(self, rhs: Duration) -> Time {
+        self + -rhs
+    }
+}
+
+impl std::ops::Sub for Time {
+    type Output = Duration;
+
+    /// Signed duration from `rhs` to `self` within the same day. Both
+    /// times are below a day, so this does not overflow.
+    #[inline]
+    fn sub(self, rhs: Time) -> Duration {
+        Duration(self.0 as i64 - rhs.0 as i64)
+    }
+}
+
+impl std::ops::Add for Duration {
+    type Output = Duration;
+
+    /// Sum of the durations, saturating at the bounds of a `Duration`
+    /// (see [`Duration::checked_add`])
+    #[inline]
+    fn add(self, rhs: Duration) -> Duration {
+        Duration(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl std::ops::Sub for Duration {
+    type Output = Duration;
+
+    /// Difference of the durations, saturating at the bounds of a
+    /// `Duration` (see [`Duration::checked_sub`])
+    #[inline]
+    fn sub(self, rhs: Duration) -> Duration {
+        Duration(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl std::ops::Mul<i64> for Duration {
+    type Output = Duration;
+
+    /// Duration `rhs` times as long, saturating at the bounds of a
+    /// `Duration` (see [`Duration::checked_mul`])
+    #[inline]
+    fn mul(self, rhs: i64) -> Duration {
+        Duration(self.0.saturating_mul(rhs))
+    }
+}
+
+impl std::ops::Neg for Duration {
+    type Output = Duration;
+
+    /// Opposite duration, the negation of the smallest `Duration`
+    /// saturating to the largest
+    #[inline]
+    fn neg(self) -> Duration {
+        Duration(self.0.saturating_neg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguous_dates_follow_the_order() {
+        let (jan_5, may_1) = (
+            Date::from_ymd(2024, 1, 5).unwrap(),
+            Date::from_ymd(2024, 5, 1).unwrap(),
+        );
+        assert_eq!(
+            Date::parse_with_order("05/01/2024", DateOrder::DMY),
+            Some(jan_5)
+        );
+        assert_eq!(
+            Date::parse_with_order("05/01/2024", DateOrder::MDY),
+            Some(may_1)
+        );
+        assert_eq!(
+            Date::parse_with_order("5.1.2024", DateOrder::DMY),
+            Some(jan_5)
+        );
+
+        // Only one reading is valid, the order still decides
+        assert_eq!(Date::parse_with_order("31/01/2024", DateOrder::MDY), None);
+        assert_eq!(Date::parse_with_order("01/31/2024", DateOrder::DMY), None);
+    }
+
+    #[test]
+    fn dates_starting_with_the_year_ignore_the_order() {
+        let expected = Date::from_ymd(2024, 1, 31);
+        for order in [DateOrder::DMY, DateOrder::MDY, DateOrder::YMD] {
+            assert_eq!(Date::parse_with_order("2024-01-31", order), expected);
+        }
+        assert_eq!(Date::parse_with_order("24-01-31", DateOrder::YMD), None);
+    }
+
+    #[test]
+    fn malformed_dates_are_rejected() {
+        for text in [
+            "",
+            "2024",
+            "2024-01",
+            "2024-01-31-1",
+            "2024-1a-31",
+            "2024--31",
+            "02024-01-31",
+        ] {
+            assert_eq!(
+                Date::parse_with_order(text, DateOrder::YMD),
+                None,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_days_are_rejected() {
+        assert_eq!(Date::from_ymd(2024, 2, 31), None);
+        assert_eq!(Date::from_ymd(2024, 4, 31), None);
+        assert_eq!(Date::from_ymd(2024, 13, 1), None);
+        assert_eq!(Date::from_ymd(2024, 1, 0), None);
+        assert_eq!(Date::parse_with_order("31/02/2024", DateOrder::DMY), None);
+        assert_eq!(Date::parse_with_format("31/02/2024", "%d/%m/%Y"), None);
+        assert_eq!(Date::parse_with_format("2024-04-31", "%Y-%m-%d"), None);
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Date::from_ymd(2024, 2, 29).is_some());
+        assert!(Date::from_ymd(2000, 2, 29).is_some());
+        assert_eq!(Date::from_ymd(2023, 2, 29), None);
+        assert_eq!(Date::from_ymd(1900, 2, 29), None);
+        assert_eq!(
+            Date::parse_with_order("29/02/2024", DateOrder::DMY),
+            Date::from_ymd(2024, 2, 29)
+        );
+        assert_eq!(Date::parse_with_format("29 Feb 2023", "%d %b %Y"), None);
+
+        let next = Date::from_days_since_epoch(
+            Date::from_ymd(2024, 2, 29).unwrap().days_since_epoch() + 1,
+        );
+        assert_eq!(next.ymd(), (2024, 3, 1));
+    }
+
+    #[test]
+    fn format_directives() {
+        let expected = Date::from_ymd(2024, 1, 31);
+        assert_eq!(
+            Date::parse_with_format("31 JAN 2024", "%d %b %Y"),
+            expected
+        );
+        assert_eq!(Date::parse_with_format("01/31/24", "%m/%d/%y"), expected);
+        assert_eq!(
+            Date::parse_with_format("31/12/69", "%d/%m/%y"),
+            Date::from_ymd(2069, 12, 31)
+        );
+        assert_eq!(
+            Date::parse_with_format("31/12/70", "%d/%m/%y"),
+            Date::from_ymd(1970, 12, 31)
+        );
+        assert_eq!(
+            Date::parse_with_format("2024%01%31", "%Y%%%m%%%d"),
+            expected
+        );
+
+        // Trailing text and missing fields
+        assert_eq!(Date::parse_with_format("2024-01-31 ", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse_with_format("2024-01", "%Y-%m"), None);
+        assert_eq!(Date::parse_with_format("2024-01-31", "%Y-%m-%q"), None);
+    }
+
+    #[test]
+    fn dates_display_as_iso_8601() {
+        let date = Date::from_ymd(1969, 12, 31).unwrap();
+        assert_eq!(date.days_since_epoch(), -1);
+        assert_eq!(date.to_string(), "1969-12-31");
+        assert_eq!(Date::from_days_since_epoch(0).to_string(), "1970-01-01");
+    }
+
+    #[test]
+    fn times_parse() {
+        assert_eq!(Time::parse("7:05"), Time::from_hms(7, 5, 0));
+        assert_eq!(Time::parse("23:59:59"), Time::from_hms(23, 59, 59));
+        assert_eq!(
+            Time::parse("00:00:01.25"),
+            Time::from_hms_nano(0, 0, 1, 250_000_000)
+        );
+        // Digits past nanoseconds are dropped
+        assert_eq!(
+            Time::parse("00:00:00.1234567891"),
+            Time::from_hms_nano(0, 0, 0, 123_456_789)
+        );
+
+        for text in [
+            "",
+            "12",
+            "24:00",
+            "12:60",
+            "12:00:60",
+            "123:00",
+            "12:0",
+            "12:00:0",
+            "12:00.5",
+            "12:00:00.",
+            "12:00:0a",
+            "+1:00",
+            "12:00:00:00",
+        ] {
+            assert_eq!(Time::parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn durations_parse() {
+        let hour = Duration::from_seconds(3600);
+        assert_eq!(Duration::parse("PT1H"), Some(hour));
+        assert_eq!(
+            Duration::parse("PT1H30M"),
+            Some(hour + Duration::from_seconds(1800))
+        );
+        assert_eq!(
+            Duration::parse("P1W2DT3S"),
+            Some(Duration::from_seconds(9 * 86_400 + 3))
+        );
+        assert_eq!(
+            Duration::parse("-PT0.5S"),
+            Some(Duration::from_nanos(-500_000_000))
+        );
+
+        for text in [
+            "", "P", "PT", "P1Y", "P1M", "PT1H1H", "PT1M1H", "P1.5D", "PT-1S",
+            "1D", "P1DT", "P1H", "-", "PT1.S",
+        ] {
+            assert_eq!(Duration::parse(text), None, "{}", text);
+        }
+
+        // Overflowing components are rejected, not wrapped
+        assert_eq!(Duration::parse("P9223372036854775807D"), None);
+        assert_eq!(
+            Duration::parse("PT9223372036S"),
+            Some(Duration::from_seconds(9_223_372_036))
+        );
+        assert_eq!(Duration::parse("PT9223372037S"), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for text in ["00:00:00", "23:59:59", "07:05:00.5", "12:34:56.000000001"]
+        {
+            let time = Time::parse(text).unwrap();
+            assert_eq!(time.to_string(), text);
+            assert_eq!(Time::parse(&time.to_string()), Some(time));
+        }
+
+        for text in
+            ["PT0S", "P1D", "P1DT2H30M", "-PT0.5S", "PT1M0.001S", "P14D"]
+        {
+            let duration = Duration::parse(text).unwrap();
+            assert_eq!(duration.to_string(), text);
+            assert_eq!(Duration::parse(&duration.to_string()), Some(duration));
+        }
+
+        // Weeks are written as days
+        assert_eq!(Duration::parse("P2W").unwrap().to_string(), "P14D");
+        for nanos in [i64::MIN + 1, i64::MAX, -1, 86_400 * NANOS_PER_SECOND] {
+            let duration = Duration::from_nanos(nanos);
+            assert_eq!(Duration::parse(&duration.to_string()), Some(duration));
+        }
+    }
+
+    #[test]
+    fn times_wrap_around_midnight() {
+        let time = Time::from_hms(23, 30, 0).unwrap();
+        let hour = Duration::from_seconds(3600);
+        assert_eq!(time + hour, Time::from_hms(0, 30, 0).unwrap());
+        assert_eq!(Time::from_hms(0, 30, 0).unwrap() - hour, time);
+        assert_eq!(time + hour * 48, time);
+        assert_eq!(time - hour * 48, time);
+        assert_eq!(
+            time + Duration::from_nanos(i64::MIN),
+            time + Duration::from_nanos(i64::MIN % NANOS_PER_DAY)
+        );
+        assert_eq!(
+            Time::from_nanos_since_midnight(NANOS_PER_DAY as u64 + 1),
+            Time::from_hms_nano(0, 0, 0, 1).unwrap()
+        );
+
+        // Differences stay within the day
+        assert_eq!(Time::from_hms(0, 30, 0).unwrap() - time, -(hour * 23));
+    }
+
+    #[test]
+    fn duration_arithmetic_saturates() {
+        let (max, min) = (
+            Duration::from_nanos(i64::MAX),
+            Duration::from_nanos(i64::MIN),
+        );
+        let one = Duration::from_nanos(1);
+
+        assert_eq!(max + one, max);
+        assert_eq!(min - one, min);
+        assert_eq!(max * 2, max);
+        assert_eq!(max * -2, min);
+        assert_eq!(-min, max);
+        assert_eq!(Duration::from_seconds(i64::MAX), max);
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(one.checked_add(one), Some(Duration::from_nanos(2)));
+        assert_eq!(one.checked_sub(one), Some(Duration::from_nanos(0)));
+        assert_eq!(one.checked_mul(-3), Some(Duration::from_nanos(-3)));
+    }
+}
@@ -0,0 +1,301 @@
+//! Exact decimal numbers, for values like prices and amounts that
+//! `f64` cannot hold without rounding.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use crate::io::float::RoundTripF64;
+
+/// Largest scale of a [`Decimal128`]: digits after the point
+pub const MAX_SCALE: u8 = 38;
+
+/// `10^exponent`, `None` if it does not fit in an `i128`
+#[inline]
+fn power_of_ten(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+/// Exact decimal number, stored as an integer `mantissa` scaled by
+/// `10^-scale`: `12.50` is `1250` with scale `2`.
+///
+/// The scale is kept as written, so that `12.50` is displayed with its
+/// two digits, but decimals compare, hash and group by value: `12.5`
+/// and `12.50` are equal.
+///
+/// ```
+/// use nn::decimal::Decimal128;
+///
+/// let price = Decimal128::parse("12.50").unwrap();
+/// assert_eq!((price.mantissa(), price.scale()), (1250, 2));
+/// assert_eq!(price.to_string(), "12.50");
+///
+/// let tax = Decimal128::parse("0.1").unwrap();
+/// let total = price.checked_add(tax).unwrap();
+/// assert_eq!(total.to_string(), "12.60");
+/// assert_eq!(total, Decimal128::parse("12.6").unwrap());
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decimal128 {
+    /// Digits of the number, without the point
+    mantissa: i128,
+    /// Digits after the point
+    scale: u8,
+}
+
+impl Decimal128 {
+    /// `mantissa` scaled by `10^-scale`, `None` if `scale` is above
+    /// [`MAX_SCALE`]
+    #[inline]
+    pub fn new(mantissa: i128, scale: u8) -> Option<Self> {
+        (scale <= MAX_SCALE).then_some(Self { mantissa, scale })
+    }
+
+    /// Digits of the number, without the point
+    #[inline(always)]
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Digits after the point
+    #[inline(always)]
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Decimal written in `text` as digits with an optional sign, point
+    /// and exponent (`-12.50`, `.5`, `1.5e-3`), `None` if it is not one
+    /// or does not fit.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (text, exponent) = match text.find(['e', 'E']) {
+            Some(index) => (&text[..index], text[index + 1..].parse().ok()?),
+            None => (text, 0i32),
+        };
+        let (negative, text) = match text.as_bytes().first()? {
+            b'-' => (true, &text[1..]),
+            b'+' => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let mut mantissa: i128 = 0;
+        for byte in integer.bytes().chain(fraction.bytes()) {
+            let digit = byte.wrapping_sub(b'0');
+            if digit > 9 {
+                return None;
+            }
+            mantissa = mantissa.checked_mul(10)?.checked_add(digit as i128)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let scale =
+            i32::try_from(fraction.len()).ok()?.checked_sub(exponent)?;
+        if scale < 0 {
+            let factor = power_of_ten(scale.unsigned_abs())?;
+            return Self::new(mantissa.checked_mul(factor)?, 0);
+        }
+        Self::new(mantissa, u8::try_from(scale).ok()?)
+    }
+
+    /// Integer part, rounded toward zero
+    #[inline]
+    pub fn trunc(&self) -> i128 {
+        // `10^MAX_SCALE` fits in an `i128`
+        self.mantissa / power_of_ten(self.scale as u32).unwrap_or(i128::MAX)
+    }
+
+    /// Decimal with the shortest digits reading back as `decimal`,
+    /// `None` for infinities and NaN
+    pub fn from_f64(decimal: f64) -> Option<Self> {
+        if !decimal.is_finite() {
+            return None;
+        }
+        Self::parse(&RoundTripF64(decimal).to_string())
+    }
+
+    /// Nearest `f64`
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        // Parsed from the text, which is exact unlike a division
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Same value with `scale` digits after the point, `None` if digits
+    /// would be lost or the mantissa does not fit
+    pub fn rescale(&self, scale: u8) -> Option<Self> {
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Some(*self),
+            Ordering::Greater => {
+                let factor = power_of_ten((scale - self.scale) as u32)?;
+                Self::new(self.mantissa.checked_mul(factor)?, scale)
+            }
+            Ordering::Less => {
+                let factor = power_of_ten((self.scale - scale) as u32)?;
+                (self.mantissa % factor == 0)
+                    .then(|| Self::new(self.mantissa / factor, scale))?
+            }
+        }
+    }
+
+    /// Same value without trailing zeros after the point
+    pub fn normalize(&self) -> Self {
+        let mut normalized = *self;
+        while normalized.scale > 0 && normalized.mantissa % 10 == 0 {
+            normalized.mantissa /= 10;
+            normalized.scale -= 1;
+        }
+        normalized
+    }
+
+    /// Both operands with the larger of their scales
+    #[inline]
+    fn aligned(self, other: Self) -> Option<(Self, Self)> {
+        let scale = self.scale.max(other.scale);
+        Some((self.rescale(scale)?, other.rescale(scale)?))
+    }
+
+    /// Sum of `self` and `other`, with the larger of their scales.
+    /// `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (left, right) = self.aligned(other)?;
+        Self::new(left.mantissa.checked_add(right.mantissa)?, left.scale)
+    }
+
+    /// Difference of `self` and `other`, with the larger of their
+    /// scales. `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (left, right) = self.aligned(other)?;
+        Self::new(left.mantissa.checked_sub(right.mantissa)?, left.scale)
+    }
+
+    /// Product of `self` and `other`, with the sum of their scales.
+    /// `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Self::new(
+            self.mantissa.checked_mul(other.mantissa)?,
+            self.scale.checked_add(other.scale)?,
+        )
+    }
+}
+
+impl From<i64> for Decimal128 {
+    #[inline]
+    fn from(number: i64) -> Self {
+        Self {
+            mantissa: number as i128,
+            scale: 0,
+        }
+    }
+}
+
+impl From<u64> for Decimal128 {
+    #[inline]
+    fn from(number: u64) -> Self {
+        Self {
+            mantissa: number as i128,
+            scale: 0,
+        }
+    }
+}
+
+impl Ord for Decimal128 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.aligned(*other) {
+            Some((left, right)) => left.mantissa.cmp(&right.mantissa),
+            // The operand with the smaller scale overflows once
+            // rescaled, so it is the largest in magnitude
+            None if self.scale < other.scale => self.mantissa.cmp(&0),
+            None => 0.cmp(&other.mantissa),
+        }
+    }
+}
+
+impl PartialOrd for Decimal128 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal128 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Decimal128 {}
+
+impl Hash for Decimal128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl std::fmt::Display for Decimal128 {
+    /// Digits with `scale` digits after the point, `-0.05` for `-5`
+    /// with scale `2`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        if scale == 0 {
+            return f.pad(&format!("{}{}", sign, digits));
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        f.pad(&format!("{}{}.{}", sign, integer, fraction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decimal128;
+
+    /// Mantissa and scale of `text`, `None` if it is not a decimal
+    fn parts(text: &str) -> Option<(i128, u8)> {
+        Decimal128::parse(text).map(|d| (d.mantissa(), d.scale()))
+    }
+
+    #[test]
+    fn exponents() {
+        assert_eq!(parts("1.5e-3"), Some((15, 4)));
+        assert_eq!(parts("1.5E3"), Some((1500, 0)));
+        assert_eq!(parts("12.50e+1"), Some((1250, 1)));
+        assert_eq!(parts("1e38"), Some((10i128.pow(38), 0)));
+        assert_eq!(parts("1e-38"), Some((1, 38)));
+        assert_eq!(parts("1e-39"), None);
+        assert_eq!(parts("1e39"), None);
+        assert_eq!(parts("1e"), None);
+        assert_eq!(parts("e5"), None);
+    }
+
+    #[test]
+    fn signs() {
+        assert_eq!(parts("-12.50"), Some((-1250, 2)));
+        assert_eq!(parts("+.5"), Some((5, 1)));
+        assert_eq!(parts("-0"), Some((0, 0)));
+        assert_eq!(parts("-"), None);
+        assert_eq!(parts("+-5"), None);
+        assert_eq!(parts("--5"), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(parts("1e-2147483648"), None);
+        assert_eq!(parts("0.5e-2147483647"), None);
+        assert_eq!(parts("1e2147483647"), None);
+        assert_eq!(parts("1e99999999999"), None);
+        assert_eq!(parts(&"9".repeat(39)), None);
+        assert_eq!(parts(&"9".repeat(38)), Some((10i128.pow(38) - 1, 0)));
+    }
+}
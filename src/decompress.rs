@@ -0,0 +1,129 @@
+use crate::error::{Error, Result};
+
+/// Compression format of an input buffer, detected from the
+/// magic bytes at the start of the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Plain, uncompressed data
+    None,
+    /// Gzip stream (`1f 8b`)
+    Gzip,
+    /// Zstandard frame (`28 b5 2f fd`)
+    Zstd,
+}
+
+impl Compression {
+    /// Detect compression format from the leading bytes of `buffer`
+    #[inline]
+    pub fn detect(buffer: &[u8]) -> Self {
+        match buffer {
+            [0x1f, 0x8b, ..] => Self::Gzip,
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Decompress `buffer` into memory if it is compressed.
+///
+/// Returns `None` for plain buffers, so that the caller can keep
+/// working on the memory mapped file without copying.
+///
+/// Fails if the buffer is compressed with a format whose feature
+/// (`gzip` or `zstd`) is not enabled, or if the stream is corrupt.
+pub fn decompress(buffer: &[u8]) -> Result<Option<Vec<u8>>> {
+    match Compression::detect(buffer) {
+        Compression::None => Ok(None),
+
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => {
+            use std::io::Read;
+
+            let mut decoded = Vec::new();
+            flate2::read::MultiGzDecoder::new(buffer)
+                .read_to_end(&mut decoded)?;
+            Ok(Some(decoded))
+        }
+
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Some(zstd::stream::decode_all(buffer)?)),
+
+        #[allow(unreachable_patterns)]
+        compression => Err(Error::UnsupportedCompression(compression)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_magic_is_plain() {
+        assert_eq!(Compression::detect(b""), Compression::None);
+        assert_eq!(Compression::detect(&[0x1f]), Compression::None);
+        assert_eq!(Compression::detect(&[0x28, 0xb5, 0x2f]), Compression::None);
+        assert_eq!(Compression::detect(&[0x8b, 0x1f]), Compression::None);
+        assert!(matches!(decompress(&[0x1f]), Ok(None)));
+        assert!(matches!(decompress(&[0x28, 0xb5]), Ok(None)));
+    }
+
+    #[test]
+    fn magic_alone_is_detected() {
+        assert_eq!(Compression::detect(&[0x1f, 0x8b]), Compression::Gzip);
+        assert_eq!(
+            Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Compression::Zstd
+        );
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn gzip_without_feature_is_unsupported() {
+        assert!(matches!(
+            decompress(&[0x1f, 0x8b, 0x08]),
+            Err(Error::UnsupportedCompression(Compression::Gzip))
+        ));
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn zstd_without_feature_is_unsupported() {
+        assert!(matches!(
+            decompress(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Err(Error::UnsupportedCompression(Compression::Zstd))
+        ));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn truncated_gzip_is_an_error() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(b"id,name\n1,pen\n2,ink\n").unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        let decoded = decompress(&encoded).unwrap().unwrap();
+        assert_eq!(decoded, b"id,name\n1,pen\n2,ink\n");
+        for len in [2, 10, encoded.len() / 2, encoded.len() - 4] {
+            assert!(decompress(&encoded[..len]).is_err(), "{}", len);
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn truncated_zstd_is_an_error() {
+        let encoded =
+            zstd::stream::encode_all(&b"id,name\n1,pen\n2,ink\n"[..], 0)
+                .unwrap();
+
+        let decoded = decompress(&encoded).unwrap().unwrap();
+        assert_eq!(decoded, b"id,name\n1,pen\n2,ink\n");
+        for len in [4, 6, encoded.len() / 2, encoded.len() - 1] {
+            assert!(decompress(&encoded[..len]).is_err(), "{}", len);
+        }
+    }
+}
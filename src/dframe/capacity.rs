@@ -0,0 +1,168 @@
+use vector::Vector;
+
+use crate::cell::{Cell, CellType};
+use crate::decimal::Decimal128;
+use crate::dframe::DataFrame;
+use crate::error::{Error, Result};
+
+impl DataFrame {
+    /// Empty Data Frame with the columns of `schema` (name and type of
+    /// each column), with room for `rows` rows before reallocating.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::dframe::DataFrame;
+    ///
+    /// let mut df = DataFrame::with_schema_capacity(
+    ///     &[("id", CellType::I64), ("name", CellType::String)],
+    ///     100,
+    /// )
+    /// .unwrap();
+    /// assert!(df.is_empty());
+    /// assert_eq!(df.capacity(), 100);
+    ///
+    /// df.reserve(150);
+    /// assert!(df.capacity() >= 150);
+    /// ```
+    pub fn with_schema_capacity(
+        schema: &[(&str, CellType)],
+        rows: usize,
+    ) -> Result<DataFrame> {
+        let mut header: Vec<String> = Vec::with_capacity(schema.len());
+        for (name, _) in schema {
+            if header.iter().any(|c| c == name) {
+                return Err(Error::DuplicateColumn((*name).to_owned()));
+            }
+            header.push((*name).to_owned());
+        }
+
+        let mut dtype: Vector<CellType> = Vector::zeroed(schema.len());
+        dtype
+            .iter_mut()
+            .zip(schema)
+            .for_each(|(slot, (_, cell_type))| *slot = *cell_type);
+
+        let mut df = DataFrame::new(Vector::zeroed(0), header, dtype);
+        df.reserve(rows);
+        Ok(df)
+    }
+
+    /// Number of rows the Data Frame can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.column_data
+            .len()
+            .checked_div(self.hlen())
+            .unwrap_or(self.rows)
+    }
+
+    /// Make room for at least `additional` more rows, growing at least
+    /// twofold so that repeated appends reallocate rarely
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.rows.saturating_add(additional);
+        if required > self.capacity() {
+            self.reallocate(required.max(self.capacity() * 2));
+        }
+    }
+
+    /// Make room for exactly `additional` more rows
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required = self.rows.saturating_add(additional);
+        if required > self.capacity() {
+            self.reallocate(required);
+        }
+    }
+
+    /// Release the room kept for rows not held yet
+    pub fn shrink_to_fit(&mut self) {
+        if self.capacity() > self.rows {
+            self.reallocate(self.rows);
+        }
+    }
+
+    /// Move the rows held into a buffer with room for `rows` rows
+    fn reallocate(&mut self, rows: usize) {
+        let width = self.hlen();
+        if width == 0 {
+            return;
+        }
+
+        let mut column_data: Vector<Cell> = Vector::zeroed(rows * width);
+        column_data.iter_mut().zip(self.cells_mut()).for_each(
+            |(slot, cell)| *slot = std::mem::replace(cell, Cell::Null),
+        );
+        self.column_data = column_data;
+    }
+
+    /// Bytes taken by each column, as `(column, bytes)` in the order of
+    /// the columns: the cells themselves, plus the heap bytes of
+    /// strings, binary payloads and exact decimals, and the dictionary
+    /// of categorical columns.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id,city\n1,Lyon\n2,Paris\n", 1);
+    /// let usage = df.memory_usage();
+    /// let cell = std::mem::size_of::<nn::cell::Cell>();
+    ///
+    /// assert_eq!(usage[0], ("id".to_owned(), 2 * cell));
+    /// assert!(usage[1].1 >= 2 * cell + "LyonParis".len());
+    /// ```
+    pub fn memory_usage(&self) -> Vec<(String, usize)> {
+        let mut usage: Vec<usize> = self
+            .dictionaries()
+            .into_iter()
+            .map(|dictionary| {
+                dictionary
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|value| {
+                        std::mem::size_of::<String>() + value.capacity()
+                    })
+                    .sum()
+            })
+            .collect();
+
+        self.iter().for_each(|row| {
+            usage.iter_mut().zip(row).for_each(|(bytes, cell)| {
+                *bytes += std::mem::size_of::<Cell>() + heap_size(cell)
+            })
+        });
+
+        self.header.iter().cloned().zip(usage).collect()
+    }
+}
+
+/// Heap bytes owned by `cell`
+fn heap_size(cell: &Cell) -> usize {
+    match cell {
+        Cell::String(string) => string.capacity(),
+        Cell::Bytes(bytes) => bytes.capacity(),
+        Cell::Decimal128(_) => std::mem::size_of::<Decimal128>(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_usage_counts_boxed_decimals() {
+        let price =
+            |text| Cell::Decimal128(Box::new(Decimal128::parse(text).unwrap()));
+        let df = DataFrame::from_cells(
+            vec![price("0.10"), Cell::Null, price("0.20")],
+            vec!["price".to_owned()],
+            &[CellType::Decimal128],
+        );
+
+        let cell = std::mem::size_of::<Cell>();
+        let decimal = std::mem::size_of::<Decimal128>();
+        assert_eq!(
+            df.memory_usage(),
+            [("price".to_owned(), 3 * cell + 2 * decimal)]
+        );
+    }
+}
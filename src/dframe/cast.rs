@@ -0,0 +1,339 @@
+use crate::cell::{Cell, CellType};
+use crate::datetime::{Date, Duration, Time};
+use crate::decimal::Decimal128;
+use crate::dframe::{ColId, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+use crate::warning::{ParseWarning, WarningKind};
+
+/// What [`DataFrame::cast`] does with cells that do not convert exactly
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CastPolicy {
+    /// Fail on the first cell that does not convert exactly
+    #[default]
+    Strict,
+    /// Accept conversions losing precision (decimals are truncated
+    /// into integers, large integers rounded into decimals), and fail
+    /// on cells that do not convert at all
+    Lossy,
+    /// Turn the cells that do not convert exactly into nulls
+    NullOnError,
+}
+
+/// `cell` converted to type `to`, `None` if it cannot be converted
+/// (exactly, unless `lossy`). Categories are read from `dictionary`,
+/// the dictionary of the column.
+fn cast_cell(
+    cell: &Cell,
+    dictionary: Option<&[String]>,
+    to: CellType,
+    lossy: bool,
+) -> Option<Cell> {
+    let text = match cell {
+        Cell::Null => return Some(Cell::Null),
+        Cell::String(string) => Some(string.trim()),
+        // Categories convert like the strings they stand for
+        Cell::Categorical(_) if to != CellType::Categorical => {
+            let string = Cell::String(cell.text(dictionary)?.to_owned());
+            return cast_cell(&string, None, to, lossy);
+        }
+        _ => None,
+    };
+
+    let cast = match (to, cell) {
+        (_, cell) if cell.cell_type() == to => cell.clone(),
+        (CellType::String, cell) => Cell::String(cell.to_string()),
+        // Encoded once every cell is a string
+        (CellType::Categorical, cell) => Cell::String(cell.to_string()),
+        (CellType::I64, Cell::UInt(number)) => {
+            Cell::Number(i64::try_from(*number).ok()?)
+        }
+        (CellType::I64, Cell::Decimal128(decimal)) => {
+            if !lossy && decimal.rescale(0).is_none() {
+                return None;
+            }
+            Cell::Number(i64::try_from(decimal.trunc()).ok()?)
+        }
+        (CellType::U64, Cell::Decimal128(decimal)) => {
+            if !lossy && decimal.rescale(0).is_none() {
+                return None;
+            }
+            Cell::UInt(u64::try_from(decimal.trunc()).ok()?)
+        }
+        (CellType::F64, Cell::Decimal128(decimal)) => {
+            let value = decimal.to_f64();
+            if !lossy && Decimal128::from_f64(value) != Some(**decimal) {
+                return None;
+            }
+            Cell::Decimal(value)
+        }
+        (CellType::Decimal128, Cell::Number(number)) => {
+            Cell::from(Decimal128::from(*number))
+        }
+        (CellType::Decimal128, Cell::UInt(number)) => {
+            Cell::from(Decimal128::from(*number))
+        }
+        (CellType::Decimal128, Cell::Decimal(decimal)) => {
+            Cell::from(Decimal128::from_f64(*decimal)?)
+        }
+        (CellType::Decimal128, Cell::String(_)) => {
+            Cell::from(Decimal128::parse(text?)?)
+        }
+        (CellType::I64, Cell::Decimal(decimal)) => {
+            let in_range = (i64::MIN as f64..i64::MAX as f64).contains(decimal);
+            if !in_range || (!lossy && decimal.fract() != 0.0) {
+                return None;
+            }
+            Cell::Number(*decimal as i64)
+        }
+        (CellType::I64, Cell::String(_)) => match text?.parse() {
+            Ok(number) => Cell::Number(number),
+            Err(_) if lossy => {
+                return cast_cell(
+                    &cast_cell(cell, None, CellType::F64, lossy)?,
+                    None,
+                    to,
+                    lossy,
+                )
+            }
+            Err(_) => return None,
+        },
+        (CellType::F64, Cell::Number(number)) => {
+            // Compared as `i128`, since `i64::MAX` rounds to `2^63`
+            // which saturates back to `i64::MAX` as an `i64`
+            let decimal = *number as f64;
+            if !lossy && decimal as i128 != *number as i128 {
+                return None;
+            }
+            Cell::Decimal(decimal)
+        }
+        (CellType::F64, Cell::UInt(number)) => {
+            let decimal = *number as f64;
+            if !lossy && decimal as u128 != *number as u128 {
+                return None;
+            }
+            Cell::Decimal(decimal)
+        }
+        (CellType::U64, Cell::Number(number)) => {
+            Cell::UInt(u64::try_from(*number).ok()?)
+        }
+        (CellType::U64, Cell::Decimal(decimal)) => {
+            let in_range = (0.0..u64::MAX as f64).contains(decimal);
+            if !in_range || (!lossy && decimal.fract() != 0.0) {
+                return None;
+            }
+            Cell::UInt(*decimal as u64)
+        }
+        (CellType::U64, Cell::String(_)) => match text?.parse() {
+            Ok(number) => Cell::UInt(number),
+            Err(_) if lossy => {
+                return cast_cell(
+                    &cast_cell(cell, None, CellType::F64, lossy)?,
+                    None,
+                    to,
+                    lossy,
+                )
+            }
+            Err(_) => return None,
+        },
+        (CellType::F64, Cell::String(_)) => Cell::Decimal(text?.parse().ok()?),
+        (CellType::Date, Cell::String(_)) => {
+            Cell::Date(Date::parse_with_format(text?, "%Y-%m-%d")?)
+        }
+        (CellType::Time, Cell::String(_)) => Cell::Time(Time::parse(text?)?),
+        (CellType::Duration, Cell::String(_)) => {
+            Cell::Duration(Duration::parse(text?)?)
+        }
+        (CellType::Bytes, Cell::String(string)) => {
+            Cell::Bytes(string.as_bytes().to_vec())
+        }
+        (CellType::Bool, Cell::String(_)) => Cell::Bool(text?.parse().ok()?),
+        _ => return None,
+    };
+    Some(cast)
+}
+
+impl DataFrame {
+    /// Convert the cells of column `col` to type `to`, handling cells
+    /// that do not convert as told by `policy`. Nulls stay nulls, and
+    /// nothing is converted on error.
+    ///
+    /// Strings are trimmed and parsed (dates are read as `YYYY-MM-DD`,
+    /// booleans as `true` or `false`),
+    /// any value converts to a string and strings convert to bytes and
+    /// to categories (see [`Self::to_categorical`]).
+    /// Integers, unsigned integers, decimals and exact decimals convert
+    /// to each other, decimals to the exact decimal with the shortest
+    /// digits reading back as them.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::dframe::CastPolicy;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"count\n12\n7.5\nn/a\n", 1);
+    /// assert_eq!(df.dtypes(), [CellType::String]);
+    /// assert!(df.cast("count", CellType::F64, CastPolicy::Strict).is_err());
+    ///
+    /// df.cast("count", CellType::I64, CastPolicy::NullOnError).unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::I64]);
+    /// assert_eq!(df.column("count").unwrap().as_i64(), [Some(12), None, None]);
+    /// ```
+    pub fn cast<C: ColumnRef>(
+        &mut self,
+        col: C,
+        to: CellType,
+        policy: CastPolicy,
+    ) -> Result<()> {
+        let index = self.column_index(col)?;
+        let lossy = policy == CastPolicy::Lossy;
+        let dictionary = self.dictionary(index);
+
+        let cells = self
+            .iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                let cast = cast_cell(&cells[index], dictionary, to, lossy);
+                match (cast, policy) {
+                    (Some(cell), _) => Ok(cell),
+                    (None, CastPolicy::NullOnError) => Ok(Cell::Null),
+                    (None, _) => Err(Error::InvalidCast {
+                        column: self.header[index].clone(),
+                        row,
+                        to,
+                    }),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let width = self.hlen();
+        self.cells_mut()
+            .chunks_exact_mut(width)
+            .zip(cells)
+            .for_each(|(row, cell)| row[index] = cell);
+
+        self.refresh_dtype(index);
+        if to == CellType::Categorical {
+            self.to_categorical(ColId(index))?;
+        }
+        // A column nulled by `NullOnError` still has the type asked for
+        self.dtype[index] = to;
+        Ok(())
+    }
+
+    /// Make the columns inferred as [`CellType::U64`] hold unsigned
+    /// numbers only: their integers turn to unsigned numbers, or the
+    /// whole column to decimals if one of the integers is negative,
+    /// see [`Self::unify_unsigned_to`].
+    pub(crate) fn unify_unsigned(&mut self) {
+        let targets: Vec<Option<CellType>> = (0..self.hlen())
+            .map(|index| self.unsigned_target(index))
+            .collect();
+        self.unify_unsigned_to(&targets);
+    }
+
+    /// Make column `index` hold unsigned numbers only if it is inferred
+    /// as [`CellType::U64`], see [`Self::unify_unsigned`]
+    pub(crate) fn unify_unsigned_column(&mut self, index: usize) {
+        if let Some(to) = self.unsigned_target(index) {
+            let mut targets = vec![None; self.hlen()];
+            targets[index] = Some(to);
+            self.unify_unsigned_to(&targets);
+        }
+    }
+
+    /// Type the integers of column `index` turn to, `None` unless the
+    /// column is inferred as [`CellType::U64`] while holding integers
+    fn unsigned_target(&self, index: usize) -> Option<CellType> {
+        if self.dtype[index] != CellType::U64 {
+            return None;
+        }
+        let mut integers = self
+            .iter()
+            .filter_map(|row| match row[index] {
+                Cell::Number(n) => Some(n),
+                _ => None,
+            })
+            .peekable();
+        integers.peek()?;
+
+        Some(if integers.any(|n| n < 0) {
+            CellType::F64
+        } else {
+            CellType::U64
+        })
+    }
+
+    /// Convert the integers of each column with a target in `targets`
+    /// to that type, [`CellType::U64`] or [`CellType::F64`], with an
+    /// [`IntegerOverflow`] warning for each unsigned number turned to
+    /// a decimal. The column is typed as its target even if it holds
+    /// nulls only, so that chunks of a file share the decision taken
+    /// over the whole file.
+    ///
+    /// [`IntegerOverflow`]: crate::warning::WarningKind::IntegerOverflow
+    pub(crate) fn unify_unsigned_to(&mut self, targets: &[Option<CellType>]) {
+        for (index, target) in targets.iter().enumerate() {
+            let Some(to) = *target else {
+                continue;
+            };
+
+            if to == CellType::F64 {
+                let overflows: Vec<ParseWarning> = self
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| matches!(row[index], Cell::UInt(_)))
+                    .map(|(row, _)| ParseWarning {
+                        row,
+                        column: index,
+                        kind: WarningKind::IntegerOverflow,
+                    })
+                    .collect();
+                self.warnings.extend(overflows);
+                self.warnings.sort_by_key(|warning| warning.row);
+            }
+
+            self.cast(ColId(index), to, CastPolicy::Lossy)
+                .expect("integers convert to numbers");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CastPolicy;
+    use crate::cell::{Cell, CellType};
+    use crate::dframe::DataFrame;
+
+    /// Single column frame of `cells`
+    fn frame(cells: Vec<Cell>, dtype: CellType) -> DataFrame {
+        DataFrame::from_cells(cells, vec!["n".to_owned()], &[dtype])
+    }
+
+    #[test]
+    fn strict_decimals_of_the_largest_integers() {
+        let mut df = frame(vec![Cell::Number(i64::MAX)], CellType::I64);
+        assert!(df.cast("n", CellType::F64, CastPolicy::Strict).is_err());
+        df.cast("n", CellType::F64, CastPolicy::Lossy).unwrap();
+
+        let mut df = frame(vec![Cell::UInt(u64::MAX)], CellType::U64);
+        assert!(df.cast("n", CellType::F64, CastPolicy::Strict).is_err());
+
+        let mut df = frame(
+            vec![Cell::Number(1 << 53), Cell::Number(i64::MIN)],
+            CellType::I64,
+        );
+        df.cast("n", CellType::F64, CastPolicy::Strict).unwrap();
+        assert_eq!(df.dtypes(), [CellType::F64]);
+    }
+
+    #[test]
+    fn nulled_columns_keep_the_cast_type() {
+        let cells = vec![Cell::String("a".into()), Cell::String("b".into())];
+        let mut df = frame(cells, CellType::String);
+        df.cast("n", CellType::I64, CastPolicy::NullOnError)
+            .unwrap();
+        assert_eq!(df.dtypes(), [CellType::I64]);
+        assert_eq!(df.column("n").unwrap().as_i64(), [None, None]);
+    }
+}
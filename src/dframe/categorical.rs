@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+#[cfg(feature = "parser")]
+use crate::intern::StringPool;
+
+/// How a column takes the categories of another Data Frame, coded in
+/// a dictionary of their own
+pub(crate) enum Merged {
+    /// Codes stay as they are, in the dictionary held
+    Kept(Arc<[String]>),
+    /// Codes are given again, the new code of each former code held
+    /// with the dictionary of both sides
+    Recoded(Arc<[String]>, Vec<u32>),
+    /// The column holds no categories, they turn into strings
+    Decoded,
+}
+
+impl DataFrame {
+    /// Store the strings of column `col` as categories: each distinct
+    /// string is kept once in the dictionary of the column, and cells
+    /// only hold its code. Columns repeating a few values over many
+    /// rows (regions, age groups) take far less memory this way.
+    ///
+    /// Codes follow the order of first appearance, nulls stay nulls.
+    /// Categories compare, sort, group and are written like the strings
+    /// they stand for, see [`Self::categories`] to read them.
+    ///
+    /// Fails with [`Error::TypeMismatch`] unless the column holds
+    /// strings (or categories, which are encoded again), and with
+    /// [`Error::TooManyCategories`] if it holds more distinct strings
+    /// than a `u32` code can tell apart.
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"region\nNorth\nSouth\nNorth\n\n", 1);
+    /// df.to_categorical("region").unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::Categorical]);
+    ///
+    /// assert!(matches!(df.get(2, "region"), Some(Cell::Categorical(0))));
+    /// assert_eq!(df.categories("region").unwrap(), ["North", "South"]);
+    /// assert_eq!(df.query("region == 'North'").unwrap().len(), 2);
+    ///
+    /// df.from_categorical("region").unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String]);
+    /// assert!(df.categories("region").is_none());
+    /// ```
+    pub fn to_categorical<C: ColumnRef>(&mut self, col: C) -> Result<()> {
+        let index = self.column_index(col)?;
+        let found = self.dtype[index];
+        if !matches!(
+            found,
+            CellType::String | CellType::Categorical | CellType::Null
+        ) {
+            return Err(Error::TypeMismatch {
+                column: self.header[index].clone(),
+                expected: CellType::Categorical,
+                found,
+            });
+        }
+
+        // Codes are all found before any cell changes, so that the
+        // column is left as it is on error
+        let dictionary = self.dictionary(index);
+        let mut codes: HashMap<&str, u32> = HashMap::new();
+        let mut values: Vec<String> = Vec::new();
+        let mut rows: Vec<Option<u32>> = Vec::with_capacity(self.len());
+        for row in self.iter() {
+            let Some(value) = row[index].text(dictionary) else {
+                rows.push(None);
+                continue;
+            };
+            let code = match codes.get(value) {
+                Some(code) => *code,
+                None => {
+                    let code = u32::try_from(values.len()).map_err(|_| {
+                        Error::TooManyCategories(self.header[index].clone())
+                    })?;
+                    codes.insert(value, code);
+                    values.push(value.to_owned());
+                    code
+                }
+            };
+            rows.push(Some(code));
+        }
+
+        let width = self.hlen();
+        self.cells_mut()
+            .chunks_exact_mut(width)
+            .zip(rows)
+            .filter_map(|(row, code)| Some((row, code?)))
+            .for_each(|(row, code)| row[index] = Cell::Categorical(code));
+
+        self.dictionaries
+            .insert(self.header[index].clone(), values.into());
+        self.refresh_dtype(index);
+        Ok(())
+    }
+
+    /// Store the categories of column `col` as plain strings again.
+    /// Columns of other types are left as they are.
+    pub fn from_categorical<C: ColumnRef>(&mut self, col: C) -> Result<()> {
+        let index = self.column_index(col)?;
+        if self.dtype[index] != CellType::Categorical {
+            return Ok(());
+        }
+
+        self.decode_categories(index);
+        self.refresh_dtype(index);
+        Ok(())
+    }
+
+    /// Strings the categories of column `col` stand for, by code:
+    /// the category `Cell::Categorical(code)` of the column stands for
+    /// `categories[code]`. `None` unless the column is categorical.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"size\nS\nL\nS\n", 1);
+    /// assert!(df.categories("size").is_none());
+    ///
+    /// df.to_categorical("size").unwrap();
+    /// let sizes = df.categories("size").unwrap();
+    /// let Some(Cell::Categorical(code)) = df.get(1, "size") else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(sizes[*code as usize], "L");
+    /// ```
+    #[inline]
+    pub fn categories<C: ColumnRef>(&self, col: C) -> Option<&[String]> {
+        self.dictionary(col.resolve(&self.header)?)
+    }
+
+    /// Dictionary of column `index`, `None` unless it is categorical
+    #[inline]
+    pub(crate) fn dictionary(&self, index: usize) -> Option<&[String]> {
+        self.dictionaries
+            .get(&self.header[index])
+            .map(|values| &**values)
+    }
+
+    /// Dictionary of each column, in the order of the columns
+    pub(crate) fn dictionaries(&self) -> Vec<Option<&[String]>> {
+        (0..self.hlen())
+            .map(|index| self.dictionary(index))
+            .collect()
+    }
+
+    /// Turn the categories of column `index` back into the strings they
+    /// stand for, and drop its dictionary. The type of the column is
+    /// left to the caller.
+    pub(crate) fn decode_categories(&mut self, index: usize) {
+        let Some(dictionary) = self.dictionaries.remove(&self.header[index])
+        else {
+            return;
+        };
+
+        let width = self.hlen();
+        self.cells_mut().chunks_exact_mut(width).for_each(|row| {
+            if let Cell::Categorical(code) = row[index] {
+                row[index] = Cell::String(dictionary[code as usize].clone());
+            }
+        });
+    }
+
+    /// Give the text columns read with `pool` its strings as
+    /// dictionary. Columns where text mixes with other values (numbers
+    /// among codes) get their strings back.
+    #[cfg(feature = "parser")]
+    pub(crate) fn adopt_pool(&mut self, pool: &StringPool) {
+        let mut dictionary = None;
+        for index in 0..self.hlen() {
+            let dtype = self.dtype[index];
+            if !matches!(dtype, CellType::Categorical | CellType::String)
+                || self.dictionaries.contains_key(&self.header[index])
+            {
+                continue;
+            }
+
+            let dictionary =
+                dictionary.get_or_insert_with(|| pool.dictionary());
+            self.dictionaries
+                .insert(self.header[index].clone(), Arc::clone(dictionary));
+            if dtype == CellType::String {
+                self.decode_categories(index);
+            }
+        }
+    }
+
+    /// Set the dictionary of column `name`, holding categories coded in
+    /// `dictionary`
+    #[inline]
+    pub(crate) fn set_dictionary(
+        &mut self,
+        name: &str,
+        dictionary: Arc<[String]>,
+    ) {
+        self.dictionaries.insert(name.to_owned(), dictionary);
+    }
+
+    /// Move the dictionary of column `previous` to column `name`
+    #[inline]
+    pub(crate) fn rename_dictionary(&mut self, previous: &str, name: &str) {
+        if let Some(dictionary) = self.dictionaries.remove(previous) {
+            self.dictionaries.insert(name.to_owned(), dictionary);
+        }
+    }
+
+    /// Copy the dictionaries of `other` for the columns sharing their
+    /// name, whose categories were taken from `other`. Columns left with
+    /// other types beside their categories get the strings back.
+    pub(crate) fn with_dictionaries_of(mut self, other: &DataFrame) -> Self {
+        for index in 0..self.hlen() {
+            let name = &self.header[index];
+            if self.dictionaries.contains_key(name) {
+                continue;
+            }
+            let Some(dictionary) = other.dictionaries.get(name) else {
+                continue;
+            };
+
+            self.dictionaries
+                .insert(name.clone(), Arc::clone(dictionary));
+            if self.dtype[index] != CellType::Categorical {
+                self.decode_categories(index);
+            }
+        }
+        self
+    }
+
+    /// How column `index` takes the categories coded in `theirs`, see
+    /// [`Merged`]
+    pub(crate) fn merge_dictionary(
+        &self,
+        index: usize,
+        theirs: &Arc<[String]>,
+    ) -> Result<Merged> {
+        let Some(ours) = self.dictionaries.get(&self.header[index]) else {
+            return Ok(match self.dtype[index] {
+                CellType::Null => Merged::Kept(Arc::clone(theirs)),
+                _ => Merged::Decoded,
+            });
+        };
+        if Arc::ptr_eq(ours, theirs) || ours.starts_with(theirs) {
+            return Ok(Merged::Kept(Arc::clone(ours)));
+        }
+        if theirs.starts_with(ours) {
+            return Ok(Merged::Kept(Arc::clone(theirs)));
+        }
+
+        let mut codes: HashMap<&str, u32> = ours
+            .iter()
+            .enumerate()
+            .map(|(code, value)| (value.as_str(), code as u32))
+            .collect();
+        let mut values = ours.to_vec();
+        let mut recoded = Vec::with_capacity(theirs.len());
+        for value in theirs.iter() {
+            let code = match codes.get(value.as_str()) {
+                Some(code) => *code,
+                None => {
+                    let code = u32::try_from(values.len()).map_err(|_| {
+                        Error::TooManyCategories(self.header[index].clone())
+                    })?;
+                    codes.insert(value, code);
+                    values.push(value.clone());
+                    code
+                }
+            };
+            recoded.push(code);
+        }
+        Ok(Merged::Recoded(values.into(), recoded))
+    }
+
+    /// Code again the categories of column `index` with `codes`, giving
+    /// the new code of each former code
+    pub(crate) fn recode_categories(&mut self, index: usize, codes: &[u32]) {
+        let width = self.hlen();
+        self.cells_mut().chunks_exact_mut(width).for_each(|row| {
+            if let Cell::Categorical(code) = &mut row[index] {
+                *code = codes[*code as usize];
+            }
+        });
+    }
+}
@@ -0,0 +1,70 @@
+/// Index of a column, resolved once from its name with
+/// [`crate::dframe::DataFrame::col_id`].
+///
+/// Accepted wherever a column name is, without comparing names again.
+/// An id stays valid as long as the columns of the Data Frame it was
+/// resolved from are not moved, added or removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ColId(pub(crate) usize);
+
+impl ColId {
+    /// Position of the column in the Data Frame
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Reference to a column: its name or its [`ColId`]
+pub trait ColumnRef {
+    /// Position of the column within `header`, `None` if it does not
+    /// exist
+    fn resolve(&self, header: &[String]) -> Option<usize>;
+
+    /// Description of the column for error messages
+    fn describe(&self) -> String;
+}
+
+impl ColumnRef for ColId {
+    #[inline(always)]
+    fn resolve(&self, header: &[String]) -> Option<usize> {
+        (self.0 < header.len()).then_some(self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("#{}", self.0)
+    }
+}
+
+impl ColumnRef for str {
+    #[inline]
+    fn resolve(&self, header: &[String]) -> Option<usize> {
+        header.iter().position(|name| name == self)
+    }
+
+    fn describe(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl ColumnRef for String {
+    #[inline]
+    fn resolve(&self, header: &[String]) -> Option<usize> {
+        self.as_str().resolve(header)
+    }
+
+    fn describe(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<C: ColumnRef + ?Sized> ColumnRef for &C {
+    #[inline(always)]
+    fn resolve(&self, header: &[String]) -> Option<usize> {
+        (**self).resolve(header)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
@@ -0,0 +1,306 @@
+use crate::cell::{Cell, CellType};
+use crate::decimal::Decimal128;
+use crate::dframe::summary::Moments;
+use crate::error::{Error, Result};
+use crate::iter::DataFrameColumnIterator;
+
+/// Read-only handle on a column of a `DataFrame`, created by
+/// [`crate::dframe::DataFrame::column`].
+///
+/// The typed extractors return one value per row, with `None` for
+/// nulls and cells that cannot be coerced to the requested type.
+#[derive(Clone, Copy)]
+pub struct Column<'a> {
+    /// Name of the column
+    name: &'a str,
+    /// Inferred type of the column
+    dtype: CellType,
+    /// Row major cells of the data frame
+    data: &'a [Cell],
+    /// Total columns of the data frame
+    width: usize,
+    /// Index of the column
+    index: usize,
+    /// Dictionary of the categories of the column
+    dictionary: Option<&'a [String]>,
+}
+
+impl<'a> Column<'a> {
+    #[inline(always)]
+    pub(crate) fn new(
+        name: &'a str,
+        dtype: CellType,
+        data: &'a [Cell],
+        width: usize,
+        index: usize,
+        dictionary: Option<&'a [String]>,
+    ) -> Self {
+        Self {
+            name,
+            dtype,
+            data,
+            width,
+            index,
+            dictionary,
+        }
+    }
+
+    /// Name of the column
+    #[inline(always)]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Inferred type of the column
+    #[inline(always)]
+    pub fn dtype(&self) -> CellType {
+        self.dtype
+    }
+
+    /// Total cells in the column
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.data.len() / self.width
+    }
+
+    /// Returns `true` if the column holds no cells
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterator over the cells of the column
+    #[inline(always)]
+    pub fn iter(&self) -> DataFrameColumnIterator<'a> {
+        DataFrameColumnIterator::new(self.data, self.width, self.index)
+    }
+
+    /// Strings the categories of the column stand for, by code, `None`
+    /// unless the column is categorical
+    #[inline(always)]
+    pub fn categories(&self) -> Option<&'a [String]> {
+        self.dictionary
+    }
+
+    /// Text of `cell` of the column, for strings and categories
+    #[inline(always)]
+    pub(crate) fn text(&self, cell: &'a Cell) -> Option<&'a str> {
+        cell.text(self.dictionary)
+    }
+
+    /// Copy of the cells of the column, with categories turned into the
+    /// strings they stand for, ready to be held by another column
+    pub(crate) fn decoded(&self) -> Vec<Cell> {
+        self.iter()
+            .map(|cell| cell.decoded(self.dictionary))
+            .collect()
+    }
+
+    /// Values as decimals: integers are converted and strings are
+    /// parsed
+    pub fn as_f64(&self) -> Vec<Option<f64>> {
+        self.iter()
+            .map(|cell| match self.text(cell) {
+                Some(string) => string.trim().parse().ok(),
+                None => cell.as_f64(),
+            })
+            .collect()
+    }
+
+    /// Values as integers: decimals without fractional part (within
+    /// `i64` range) are converted and strings are parsed
+    pub fn as_i64(&self) -> Vec<Option<i64>> {
+        self.iter()
+            .map(|cell| match cell {
+                Cell::Number(number) => Some(*number),
+                Cell::UInt(number) => i64::try_from(*number).ok(),
+                Cell::Decimal(decimal)
+                    if decimal.fract() == 0.0
+                        && (i64::MIN as f64..i64::MAX as f64)
+                            .contains(decimal) =>
+                {
+                    Some(*decimal as i64)
+                }
+                cell => self.text(cell)?.trim().parse().ok(),
+            })
+            .collect()
+    }
+
+    /// Values as unsigned integers: non negative integers and decimals
+    /// without fractional part (within `u64` range) are converted and
+    /// strings are parsed
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id\n18446744073709551615\n42\n", 1);
+    /// assert_eq!(df.dtypes(), [CellType::U64]);
+    /// assert_eq!(df.column("id").unwrap().as_u64(), [Some(u64::MAX), Some(42)]);
+    ///
+    /// let df = CsvParser::parse_buffer(b"id\n18446744073709551615\n-1\n", 1);
+    /// assert_eq!(df.dtypes(), [CellType::F64]);
+    /// assert_eq!(df.warnings().len(), 1);
+    /// ```
+    pub fn as_u64(&self) -> Vec<Option<u64>> {
+        self.iter()
+            .map(|cell| match cell {
+                Cell::UInt(number) => Some(*number),
+                Cell::Number(number) => u64::try_from(*number).ok(),
+                Cell::Decimal(decimal)
+                    if decimal.fract() == 0.0
+                        && (0.0..u64::MAX as f64).contains(decimal) =>
+                {
+                    Some(*decimal as u64)
+                }
+                cell => self.text(cell)?.trim().parse().ok(),
+            })
+            .collect()
+    }
+
+    /// Values as string slices, for string and categorical cells only
+    pub fn as_str(&self) -> Vec<Option<&'a str>> {
+        self.iter().map(|cell| self.text(cell)).collect()
+    }
+
+    /// Fail unless the column holds only numbers and nulls
+    pub(crate) fn check_numeric(&self) -> Result<()> {
+        if !self.dtype.is_numeric() && self.dtype != CellType::Null {
+            return Err(Error::TypeMismatch {
+                column: self.name.to_owned(),
+                expected: CellType::F64,
+                found: self.dtype,
+            });
+        }
+        Ok(())
+    }
+
+    /// Numeric values of the column, skipping nulls. Fails unless the
+    /// column holds only numbers and nulls.
+    fn numbers(&self) -> Result<impl Iterator<Item = f64> + 'a> {
+        self.check_numeric()?;
+        Ok(self.iter().filter_map(Cell::as_f64))
+    }
+
+    /// Count, mean, variance and bounds of the numeric values
+    pub(crate) fn moments(&self) -> Result<Moments> {
+        let mut moments = Moments::default();
+        self.numbers()?.for_each(|value| moments.push(value));
+        Ok(moments)
+    }
+
+    /// Sum of the values, ignoring nulls (`0` for a column of nulls).
+    ///
+    /// The values are added as `f64`, so the sum of exact decimals may
+    /// be rounded: see [`Self::exact_sum`] for their exact total.
+    ///
+    /// Like every reduction, fails with [`Error::TypeMismatch`] on a
+    /// column that is not numeric.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"name,price\na,2\nb,\nc,4.5\n", 1);
+    /// let price = df.column("price").unwrap();
+    /// assert_eq!(price.sum().unwrap(), 6.5);
+    /// assert_eq!(price.mean().unwrap(), Some(3.25));
+    /// assert_eq!(price.max().unwrap(), Some(4.5));
+    /// assert_eq!(price.median().unwrap(), Some(3.25));
+    /// assert!(price.quantile(1.5).is_err());
+    /// assert!(price.quantile(f64::NAN).is_err());
+    /// assert!(df.column("name").unwrap().mean().is_err());
+    /// ```
+    pub fn sum(&self) -> Result<f64> {
+        Ok(self.numbers()?.sum())
+    }
+
+    /// Exact sum of the integers and exact decimals of the column,
+    /// ignoring nulls, with the largest scale of the values. `None` if
+    /// the column holds an `f64` decimal or the sum overflows.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"item,price\npen,0.10\nink,0.20\n";
+    /// let options = ParserOptions::new().exact_decimals("price");
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// let price = df.column("price").unwrap();
+    /// assert_eq!(price.exact_sum().unwrap().unwrap().to_string(), "0.30");
+    /// assert_eq!(price.cumsum().unwrap()[1].to_string(), "0.30");
+    /// ```
+    pub fn exact_sum(&self) -> Result<Option<Decimal128>> {
+        self.check_numeric()?;
+        Ok(self.iter().try_fold(
+            Decimal128::from(0i64),
+            |sum, cell| match cell {
+                Cell::Null => Some(sum),
+                Cell::Number(number) => sum.checked_add((*number).into()),
+                Cell::UInt(number) => sum.checked_add((*number).into()),
+                Cell::Decimal128(decimal) => sum.checked_add(**decimal),
+                _ => None,
+            },
+        ))
+    }
+
+    /// Mean of the values, ignoring nulls
+    pub fn mean(&self) -> Result<Option<f64>> {
+        let moments = self.moments()?;
+        Ok((moments.count > 0).then_some(moments.mean))
+    }
+
+    /// Smallest value, ignoring nulls
+    pub fn min(&self) -> Result<Option<f64>> {
+        Ok(self.numbers()?.reduce(f64::min))
+    }
+
+    /// Largest value, ignoring nulls
+    pub fn max(&self) -> Result<Option<f64>> {
+        Ok(self.numbers()?.reduce(f64::max))
+    }
+
+    /// Sample standard deviation of the values, ignoring nulls. `None`
+    /// with less than two values.
+    pub fn std(&self) -> Result<Option<f64>> {
+        Ok(self.moments()?.std())
+    }
+
+    /// Median of the values, ignoring nulls, see [`Self::quantile`]
+    #[inline]
+    pub fn median(&self) -> Result<Option<f64>> {
+        self.quantile(0.5)
+    }
+
+    /// Quantile `q` of the values, ignoring nulls, interpolating
+    /// linearly between the two closest values.
+    ///
+    /// The values are copied once to be partially ordered, the cells
+    /// are not. Fails with [`Error::InvalidArgument`] if `q` is not
+    /// within `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Result<Option<f64>> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(Error::InvalidArgument(format!(
+                "quantile {} not within 0..=1",
+                q
+            )));
+        }
+
+        let mut values: Vec<f64> = self.numbers()?.collect();
+        if values.is_empty() {
+            return Ok(None);
+        }
+
+        let rank = q * (values.len() - 1) as f64;
+        let below = rank.floor() as usize;
+        let (_, lower, above) =
+            values.select_nth_unstable_by(below, f64::total_cmp);
+        let lower = *lower;
+
+        let fraction = rank - below as f64;
+        if fraction == 0.0 {
+            return Ok(Some(lower));
+        }
+        let upper = above.iter().copied().reduce(f64::min).unwrap_or(lower);
+        Ok(Some(lower + (upper - lower) * fraction))
+    }
+}
@@ -0,0 +1,272 @@
+use crate::cell::Cell;
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Move the first item of `slice` to the end if `forward`, the last
+/// item to the front otherwise
+#[inline]
+fn rotate<T>(slice: &mut [T], forward: bool) {
+    if forward {
+        slice.rotate_left(1)
+    } else {
+        slice.rotate_right(1)
+    }
+}
+
+impl DataFrame {
+    /// New Data Frame holding the columns at `indices`, in that order
+    pub(crate) fn select_indices(&self, indices: &[usize]) -> DataFrame {
+        let cells = self
+            .iter()
+            .flat_map(|row| indices.iter().map(|index| row[*index].clone()))
+            .collect();
+        let header = indices
+            .iter()
+            .map(|index| self.header[*index].clone())
+            .collect();
+        let dtype: Vec<_> =
+            indices.iter().map(|index| self.dtype[*index]).collect();
+
+        DataFrame::from_cells(cells, header, &dtype)
+            .with_units_of(self)
+            .with_dictionaries_of(self)
+    }
+
+    /// New Data Frame holding only the columns named in `cols`, in the
+    /// order of `cols`.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"a,b,c\n1,2,3\n", 1);
+    /// assert_eq!(df.select(&["c", "a"]).unwrap().header(), ["c", "a"]);
+    /// assert_eq!(df.drop_columns(&["b"]).unwrap().header(), ["a", "c"]);
+    /// ```
+    pub fn select<C: ColumnRef>(&self, cols: &[C]) -> Result<DataFrame> {
+        let mut indices = Vec::with_capacity(cols.len());
+        for col in cols {
+            let index = self.column_index(col)?;
+            if indices.contains(&index) {
+                return Err(Error::DuplicateColumn(col.describe()));
+            }
+            indices.push(index);
+        }
+
+        Ok(self.select_indices(&indices))
+    }
+
+    /// New Data Frame without the columns named in `cols`. Other columns
+    /// keep their order.
+    pub fn drop_columns<C: ColumnRef>(&self, cols: &[C]) -> Result<DataFrame> {
+        let dropped = cols
+            .iter()
+            .map(|col| self.column_index(col))
+            .collect::<Result<Vec<_>>>()?;
+
+        let indices: Vec<_> = (0..self.hlen())
+            .filter(|index| !dropped.contains(index))
+            .collect();
+
+        Ok(self.select_indices(&indices))
+    }
+
+    /// Indices of the columns whose name matches `pattern` (or does not,
+    /// if `matching` is `false`), in the order of the Data Frame
+    #[cfg(feature = "regex")]
+    fn indices_by_regex(
+        &self,
+        pattern: &str,
+        matching: bool,
+    ) -> Result<Vec<usize>> {
+        let regex = regex::Regex::new(pattern)?;
+
+        Ok(self
+            .header
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| regex.is_match(name) == matching)
+            .map(|(index, _)| index)
+            .collect())
+    }
+
+    /// New Data Frame holding the columns whose name matches the regular
+    /// expression `pattern`, e.g. `^sales_`. Columns keep their order.
+    #[cfg(feature = "regex")]
+    pub fn select_regex(&self, pattern: &str) -> Result<DataFrame> {
+        Ok(self.select_indices(&self.indices_by_regex(pattern, true)?))
+    }
+
+    /// New Data Frame without the columns whose name matches the regular
+    /// expression `pattern`, e.g. `_tmp$`. Columns keep their order.
+    #[cfg(feature = "regex")]
+    pub fn drop_regex(&self, pattern: &str) -> Result<DataFrame> {
+        Ok(self.select_indices(&self.indices_by_regex(pattern, false)?))
+    }
+
+    /// Move column `col` to `position`, shifting the columns in between
+    /// by one. Other columns keep their relative order.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"a,b,c\n1,2,3\n", 1);
+    /// df.move_column("c", 0).unwrap();
+    /// assert_eq!(df.header(), ["c", "a", "b"]);
+    ///
+    /// df.move_column("c", 2).unwrap();
+    /// assert_eq!(df.header(), ["a", "b", "c"]);
+    /// ```
+    pub fn move_column<C: ColumnRef>(
+        &mut self,
+        col: C,
+        position: usize,
+    ) -> Result<()> {
+        let index = self.column_index(col)?;
+        let width = self.hlen();
+
+        if position >= width {
+            return Err(Error::IndexOutOfBounds {
+                index: position,
+                len: width,
+            });
+        }
+
+        let (start, end) = (index.min(position), index.max(position) + 1);
+        let forward = index < position;
+
+        rotate(&mut self.header[start..end], forward);
+        rotate(&mut self.dtype[start..end], forward);
+        self.cells_mut()
+            .chunks_exact_mut(width)
+            .for_each(|row| rotate(&mut row[start..end], forward));
+
+        Ok(())
+    }
+
+    /// Rename column `col` to `name`
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"a,b,c\n1,2,3\n", 1);
+    /// df.rename_column("a", "x").unwrap();
+    /// df.rename_all(&[("b", "y"), ("c", "z")]).unwrap();
+    /// assert_eq!(df.header(), ["x", "y", "z"]);
+    ///
+    /// assert!(df.rename_column("x", "y").is_err());
+    /// ```
+    pub fn rename_column<C: ColumnRef>(
+        &mut self,
+        col: C,
+        name: &str,
+    ) -> Result<()> {
+        let index = self.column_index(col)?;
+        if self.header[index] != name {
+            self.check_new_column(name)?;
+            let previous =
+                std::mem::replace(&mut self.header[index], name.to_owned());
+            if let Some(unit) = self.units.remove(&previous) {
+                self.units.insert(name.to_owned(), unit);
+            }
+            self.rename_dictionary(&previous, name);
+        }
+        Ok(())
+    }
+
+    /// Rename each column of `mapping` to the name it is paired with.
+    ///
+    /// Names are swapped all at once, so `[("a", "b"), ("b", "a")]`
+    /// exchanges the names of two columns. Nothing is renamed if a
+    /// column does not exist or two columns would end up with the same
+    /// name.
+    pub fn rename_all<C: ColumnRef, S: AsRef<str>>(
+        &mut self,
+        mapping: &[(C, S)],
+    ) -> Result<()> {
+        let mut header = self.header.clone();
+        for (col, name) in mapping {
+            let index = self.column_index(col)?;
+            header[index] = name.as_ref().to_owned();
+        }
+
+        for (index, name) in header.iter().enumerate() {
+            if header[..index].contains(name) {
+                return Err(Error::DuplicateColumn(name.clone()));
+            }
+        }
+
+        self.units = std::mem::take(&mut self.units)
+            .into_iter()
+            .filter_map(|(name, unit)| {
+                let index = self.header.iter().position(|h| *h == name)?;
+                Some((header[index].clone(), unit))
+            })
+            .collect();
+        self.dictionaries = std::mem::take(&mut self.dictionaries)
+            .into_iter()
+            .filter_map(|(name, dictionary)| {
+                let index = self.header.iter().position(|h| *h == name)?;
+                Some((header[index].clone(), dictionary))
+            })
+            .collect();
+        self.header = header;
+        Ok(())
+    }
+
+    /// Put the columns in the order of `cols`, which must name every
+    /// column exactly once.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"a,b,c\n1,2,3\n", 1);
+    /// df.reorder(&["c", "a", "b"]).unwrap();
+    /// assert_eq!(df.header(), ["c", "a", "b"]);
+    ///
+    /// assert!(df.reorder(&["a", "b"]).is_err());
+    /// ```
+    pub fn reorder<C: ColumnRef>(&mut self, cols: &[C]) -> Result<()> {
+        let width = self.hlen();
+        let mut indices = Vec::with_capacity(width);
+        for col in cols {
+            let index = self.column_index(col)?;
+            if indices.contains(&index) {
+                return Err(Error::DuplicateColumn(col.describe()));
+            }
+            indices.push(index);
+        }
+
+        // Without duplicates, naming as many columns means naming them all
+        if indices.len() != width {
+            return Err(Error::LengthMismatch {
+                expected: width,
+                found: indices.len(),
+            });
+        }
+        if width == 0 {
+            return Ok(());
+        }
+
+        let mut scratch = Vec::with_capacity(width);
+        self.cells_mut().chunks_exact_mut(width).for_each(|row| {
+            scratch.clear();
+            scratch.extend(
+                row.iter_mut()
+                    .map(|cell| std::mem::replace(cell, Cell::Null)),
+            );
+            row.iter_mut().zip(&indices).for_each(|(slot, index)| {
+                *slot = std::mem::replace(&mut scratch[*index], Cell::Null)
+            });
+        });
+
+        self.header = indices
+            .iter()
+            .map(|index| std::mem::take(&mut self.header[*index]))
+            .collect();
+        let dtype: Vec<_> =
+            indices.iter().map(|index| self.dtype[*index]).collect();
+        self.dtype.copy_from_slice(&dtype);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::cell::Cell;
+use crate::dframe::DataFrame;
+
+/// Rows printed by `Display` before the middle rows are elided
+const DISPLAY_ROWS: usize = 10;
+/// Characters of a cell printed before it is truncated
+const MAX_CELL_WIDTH: usize = 24;
+
+/// Aligned ASCII table of a `DataFrame`, created by
+/// [`DataFrame::table`]
+pub struct Table<'a> {
+    /// Data Frame to print
+    df: &'a DataFrame,
+    /// Rows printed before the middle rows are elided
+    max_rows: usize,
+    /// Print every cell in full, quoting strings
+    snapshot: bool,
+}
+
+/// Cut `text` to [`MAX_CELL_WIDTH`] characters, ending with `...` if
+/// it was truncated
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_CELL_WIDTH {
+        return text.to_owned();
+    }
+
+    let mut truncated: String = text.chars().take(MAX_CELL_WIDTH - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Text of `cell`, giving categories coded in `dictionary` as the
+/// strings they stand for
+fn plain_text(cell: &Cell, dictionary: Option<&[String]>) -> String {
+    match (cell, cell.text(dictionary)) {
+        (Cell::Categorical(_), Some(text)) => text.to_owned(),
+        _ => cell.to_string(),
+    }
+}
+
+/// Text of `cell` on a single line
+fn cell_text(cell: &Cell, dictionary: Option<&[String]>) -> String {
+    let text = plain_text(cell, dictionary);
+    if text.contains(['\n', '\r', '\t']) {
+        return truncate(&text.escape_default().to_string());
+    }
+    truncate(&text)
+}
+
+/// Text of `cell` for snapshots: strings are quoted and escaped so
+/// that they cannot be mistaken for other values
+fn snapshot_text(cell: &Cell, dictionary: Option<&[String]>) -> String {
+    match cell {
+        Cell::String(string) => format!("{:?}", string),
+        cell => plain_text(cell, dictionary),
+    }
+}
+
+/// Write `cells` as a table line, right aligned where `right` is set
+fn write_line<'t>(
+    f: &mut Formatter<'_>,
+    cells: impl Iterator<Item = &'t str>,
+    widths: &[usize],
+    right: &[bool],
+) -> Result {
+    f.write_str("|")?;
+    for ((cell, width), right) in cells.zip(widths).zip(right) {
+        if *right {
+            write!(f, " {:>1$} |", cell, width)?;
+        } else {
+            write!(f, " {:<1$} |", cell, width)?;
+        }
+    }
+    f.write_str("\n")
+}
+
+/// Write the border line between the header and the rows
+fn write_border(f: &mut Formatter<'_>, widths: &[usize]) -> Result {
+    f.write_str("+")?;
+    for width in widths {
+        write!(f, "{:-<1$}+", "", width + 2)?;
+    }
+    f.write_str("\n")
+}
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let width = self.df.hlen();
+        let len = if width == 0 { 0 } else { self.df.len() };
+
+        if width > 0 {
+            let render = if self.snapshot {
+                snapshot_text
+            } else {
+                cell_text
+            };
+            let (head, tail) = if len <= self.max_rows {
+                (len, 0)
+            } else {
+                (self.max_rows.div_ceil(2), self.max_rows / 2)
+            };
+            let elided = head + tail < len;
+            let dictionaries = self.df.dictionaries();
+
+            let rows: Vec<Vec<String>> = self
+                .df
+                .iter()
+                .take(head)
+                .chain(self.df.iter().skip(len - tail))
+                .map(|row| {
+                    row.iter()
+                        .zip(&dictionaries)
+                        .map(|(cell, dictionary)| render(cell, *dictionary))
+                        .collect()
+                })
+                .collect();
+            let header: Vec<String> = self
+                .df
+                .header()
+                .iter()
+                .map(|name| {
+                    if self.snapshot {
+                        name.escape_default().to_string()
+                    } else {
+                        truncate(name)
+                    }
+                })
+                .collect();
+            let types: Vec<String> =
+                self.df.dtypes().iter().map(ToString::to_string).collect();
+            let right: Vec<bool> =
+                self.df.dtypes().iter().map(|t| t.is_numeric()).collect();
+
+            let widths: Vec<usize> = (0..width)
+                .map(|col| {
+                    rows.iter()
+                        .map(|row| &row[col])
+                        .chain([&header[col], &types[col]])
+                        .map(|text| text.chars().count())
+                        .max()
+                        .unwrap_or(0)
+                        .max(if elided { 3 } else { 0 })
+                })
+                .collect();
+
+            write_border(f, &widths)?;
+            let left = vec![false; width];
+            write_line(f, header.iter().map(String::as_str), &widths, &left)?;
+            write_line(f, types.iter().map(String::as_str), &widths, &left)?;
+            write_border(f, &widths)?;
+
+            for (index, row) in rows.iter().enumerate() {
+                if elided && index == head {
+                    let dots = std::iter::repeat_n("...", width);
+                    write_line(f, dots, &widths, &right)?;
+                }
+                write_line(f, row.iter().map(String::as_str), &widths, &right)?;
+            }
+            if elided && head == rows.len() {
+                let dots = std::iter::repeat_n("...", width);
+                write_line(f, dots, &widths, &right)?;
+            }
+            // Without rows, the border below the types closes the table
+            if !rows.is_empty() {
+                write_border(f, &widths)?;
+            }
+        }
+
+        write!(f, "{} rows x {} columns", len, width)
+    }
+}
+
+impl Display for DataFrame {
+    /// Aligned ASCII table with the header, the type of each column
+    /// and the first and last rows, eliding the middle ones for large
+    /// Data Frames. Long cells are truncated.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.table(DISPLAY_ROWS).fmt(f)
+    }
+}
+
+impl DataFrame {
+    /// Aligned ASCII table of the Data Frame (as printed by `Display`),
+    /// showing at most `max_rows` rows
+    #[inline]
+    pub fn table(&self, max_rows: usize) -> Table<'_> {
+        Table {
+            df: self,
+            max_rows,
+            snapshot: false,
+        }
+    }
+
+    /// Canonical plain text rendering of the Data Frame, meant for
+    /// snapshot tests.
+    ///
+    /// Unlike `Display`, every row is printed and cells are never
+    /// truncated. Strings are quoted and escaped, so that nulls (`null`)
+    /// and numbers are told apart from strings. Rows keep their order.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"name,score\nada,\nbob,7\n", 1);
+    /// assert_eq!(
+    ///     df.to_snapshot(),
+    ///     "+-------+-------+\n\
+    ///      | name  | score |\n\
+    ///      | str   | i64   |\n\
+    ///      +-------+-------+\n\
+    ///      | \"ada\" |  null |\n\
+    ///      | \"bob\" |     7 |\n\
+    ///      +-------+-------+\n\
+    ///      2 rows x 2 columns\n"
+    /// );
+    /// ```
+    pub fn to_snapshot(&self) -> String {
+        let table = Table {
+            df: self,
+            max_rows: usize::MAX,
+            snapshot: true,
+        };
+        format!("{}\n", table)
+    }
+
+    /// Print the Data Frame as a table showing at most `max_rows` rows
+    #[inline]
+    pub fn show(&self, max_rows: usize) {
+        println!("{}", self.table(max_rows));
+    }
+}
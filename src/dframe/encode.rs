@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::cell::Cell;
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::Result;
+
+/// Integer code of each label of a column, created by
+/// [`DataFrame::label_encode`].
+///
+/// Codes start from `0`, in order of first appearance of the labels.
+/// The vocabulary is rebuilt from its [`labels`](Self::labels) with
+/// [`Vocabulary::from_labels`] (or serialized, with the `serde`
+/// feature), so that the same codes are given to future frames.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "Vec<String>", into = "Vec<String>")
+)]
+pub struct Vocabulary {
+    /// Label of each code
+    labels: Vec<String>,
+    /// Code of each label
+    codes: HashMap<String, i64>,
+}
+
+impl Vocabulary {
+    /// Vocabulary giving each of `labels` its position as code. Repeated
+    /// labels keep their first code.
+    pub fn from_labels(labels: Vec<String>) -> Self {
+        let mut codes = HashMap::with_capacity(labels.len());
+        for (code, label) in labels.iter().enumerate() {
+            codes.entry(label.clone()).or_insert(code as i64);
+        }
+        Self { labels, codes }
+    }
+
+    /// Label of each code, in order of the codes
+    #[inline]
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Total labels
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns `true` if the vocabulary holds no label
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Code of `label`, `None` if unknown
+    #[inline]
+    pub fn code(&self, label: &str) -> Option<i64> {
+        self.codes.get(label).copied()
+    }
+
+    /// Label of `code`, `None` if unknown
+    #[inline]
+    pub fn label(&self, code: i64) -> Option<&str> {
+        let index = usize::try_from(code).ok()?;
+        self.labels.get(index).map(String::as_str)
+    }
+
+    /// Label of `cell`: its text, `None` for nulls. Categories are
+    /// coded in `dictionary`.
+    #[inline]
+    fn label_of(cell: &Cell, dictionary: Option<&[String]>) -> Option<String> {
+        match (cell, cell.text(dictionary)) {
+            (Cell::Null, _) => None,
+            (_, Some(text)) => Some(text.to_owned()),
+            (cell, None) => Some(cell.to_string()),
+        }
+    }
+
+    /// Code of each cell of column `col` of `df`, one per row. Nulls
+    /// and labels missing from the vocabulary are nulls.
+    pub fn encode<C: ColumnRef>(
+        &self,
+        df: &DataFrame,
+        col: C,
+    ) -> Result<Vec<Cell>> {
+        let index = df.column_index(col)?;
+        let dictionary = df.dictionary(index);
+        Ok(df
+            .iter()
+            .map(|row| {
+                Self::label_of(&row[index], dictionary)
+                    .and_then(|label| self.code(&label))
+                    .map_or(Cell::Null, Cell::Number)
+            })
+            .collect())
+    }
+
+    /// Label of each code of `codes`, nulls where the code is unknown
+    pub fn decode(&self, codes: &[Cell]) -> Vec<Cell> {
+        codes
+            .iter()
+            .map(|cell| match cell {
+                Cell::Number(code) => self
+                    .label(*code)
+                    .map_or(Cell::Null, |label| Cell::String(label.to_owned())),
+                _ => Cell::Null,
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<String>> for Vocabulary {
+    #[inline]
+    fn from(labels: Vec<String>) -> Self {
+        Self::from_labels(labels)
+    }
+}
+
+impl From<Vocabulary> for Vec<String> {
+    #[inline]
+    fn from(vocabulary: Vocabulary) -> Self {
+        vocabulary.labels
+    }
+}
+
+impl DataFrame {
+    /// Integer code of each cell of column `col`, one per row, and the
+    /// vocabulary giving the codes.
+    ///
+    /// Labels are the text of the cells, coded from `0` in order of
+    /// first appearance. Nulls stay nulls.
+    ///
+    /// ```
+    /// use nn::dframe::Vocabulary;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let train = CsvParser::parse_buffer(b"category\nbird\ncat\nbird\n\n", 1);
+    /// let (codes, vocabulary) = train.label_encode("category").unwrap();
+    /// assert_eq!(vocabulary.labels(), ["bird", "cat"]);
+    /// assert_eq!(codes[2].to_string(), "0");
+    ///
+    /// // Same codes for the frames seen later, unknown labels are nulls
+    /// let saved = vocabulary.labels().to_vec();
+    /// let vocabulary = Vocabulary::from_labels(saved);
+    /// let test = CsvParser::parse_buffer(b"category\ncat\ndog\n", 1);
+    /// let codes = vocabulary.encode(&test, "category").unwrap();
+    /// assert_eq!(codes[0].to_string(), "1");
+    /// assert!(matches!(codes[1], nn::cell::Cell::Null));
+    /// ```
+    pub fn label_encode<C: ColumnRef>(
+        &self,
+        col: C,
+    ) -> Result<(Vec<Cell>, Vocabulary)> {
+        let index = self.column_index(col)?;
+
+        let dictionary = self.dictionary(index);
+        let mut vocabulary = Vocabulary::default();
+        let codes = self
+            .iter()
+            .map(|row| {
+                let Some(label) = Vocabulary::label_of(&row[index], dictionary)
+                else {
+                    return Cell::Null;
+                };
+                let next = vocabulary.labels.len() as i64;
+                let code = *vocabulary.codes.entry(label).or_insert_with_key(
+                    |label| {
+                        vocabulary.labels.push(label.clone());
+                        next
+                    },
+                );
+                Cell::Number(code)
+            })
+            .collect();
+
+        Ok((codes, vocabulary))
+    }
+}
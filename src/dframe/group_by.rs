@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use crate::cell::{Cell, CellType};
+use crate::decimal::Decimal128;
+use crate::dframe::sort::compare_cells;
+use crate::dframe::summary::Moments;
+use crate::dframe::{infer_dtypes, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Aggregation of the cells of a column within each group, see
+/// [`GroupBy::agg`]. Nulls are skipped by every aggregation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Agg {
+    /// Number of non null cells
+    Count,
+    /// Sum of the numbers, an integer if all of them are
+    Sum,
+    /// Mean of the numbers
+    Mean,
+    /// Sample standard deviation of the numbers
+    Std,
+    /// Smallest value
+    Min,
+    /// Largest value
+    Max,
+    /// First non null value
+    First,
+    /// Last non null value
+    Last,
+}
+
+impl Agg {
+    /// Whether the aggregation only applies to numeric columns
+    #[inline(always)]
+    fn is_numeric(self) -> bool {
+        matches!(self, Self::Sum | Self::Mean | Self::Std)
+    }
+
+    /// Aggregate of `cells`, whose categories are coded in `dictionary`.
+    /// Categories are given as the strings they stand for.
+    fn apply<'c>(
+        self,
+        cells: impl Iterator<Item = &'c Cell>,
+        dictionary: Option<&[String]>,
+    ) -> Cell {
+        let mut values = cells.filter(|cell| !matches!(cell, Cell::Null));
+
+        match self {
+            Self::Count => Cell::Number(values.count() as i64),
+            Self::Sum => {
+                // Integers and exact decimals are summed exactly until
+                // one overflows or a decimal shows up
+                let (mut integer, mut decimal) = (Some(0i64), 0.0);
+                let mut exact = Some(Decimal128::from(0i64));
+                values.for_each(|cell| {
+                    integer = match (integer, cell) {
+                        (Some(sum), Cell::Number(number)) => {
+                            sum.checked_add(*number)
+                        }
+                        _ => None,
+                    };
+                    exact = match (exact, cell) {
+                        (Some(sum), Cell::Decimal128(value)) => {
+                            sum.checked_add(**value)
+                        }
+                        (Some(sum), Cell::Number(number)) => {
+                            sum.checked_add((*number).into())
+                        }
+                        _ => None,
+                    };
+                    decimal += cell.as_f64().unwrap_or_default();
+                });
+                match (integer, exact) {
+                    (Some(integer), _) => Cell::Number(integer),
+                    (None, Some(exact)) => Cell::from(exact),
+                    (None, None) => Cell::Decimal(decimal),
+                }
+            }
+            Self::Mean | Self::Std => {
+                let mut moments = Moments::default();
+                values
+                    .filter_map(Cell::as_f64)
+                    .for_each(|value| moments.push(value));
+
+                let value = match self {
+                    Self::Mean => (moments.count > 0).then_some(moments.mean),
+                    _ => moments.std(),
+                };
+                value.map_or(Cell::Null, Cell::Decimal)
+            }
+            Self::Min => values
+                .min_by(|a, b| compare_cells(a, b, dictionary))
+                .map_or(Cell::Null, |cell| cell.decoded(dictionary)),
+            Self::Max => values
+                .max_by(|a, b| compare_cells(a, b, dictionary))
+                .map_or(Cell::Null, |cell| cell.decoded(dictionary)),
+            Self::First => values
+                .next()
+                .map_or(Cell::Null, |cell| cell.decoded(dictionary)),
+            Self::Last => values
+                .last()
+                .map_or(Cell::Null, |cell| cell.decoded(dictionary)),
+        }
+    }
+}
+
+impl std::fmt::Display for Agg {
+    /// Name of the aggregation, used as suffix of the aggregated column
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Std => "std",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::First => "first",
+            Self::Last => "last",
+        })
+    }
+}
+
+/// Rows of a `DataFrame` grouped on the values of key columns, created
+/// by [`DataFrame::group_by`]
+pub struct GroupBy<'a> {
+    /// Grouped Data Frame
+    df: &'a DataFrame,
+    /// Index of the key columns
+    keys: Vec<usize>,
+    /// Rows of each group, groups in order of first appearance
+    groups: Vec<Vec<usize>>,
+}
+
+impl DataFrame {
+    /// Group the rows sharing the same values in the columns `keys`.
+    ///
+    /// Nulls are a value like any other: rows with a null key are
+    /// grouped together.
+    ///
+    /// ```
+    /// use nn::dframe::Agg;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(
+    ///     b"region,sales,price\nNorth,10,2.5\nSouth,4,3\nNorth,6,3.5\n,1,1\n",
+    ///     1,
+    /// );
+    /// let report = df
+    ///     .group_by(&["region"])
+    ///     .unwrap()
+    ///     .agg(&[("sales", Agg::Sum), ("price", Agg::Mean)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(report.header(), ["region", "sales_sum", "price_mean"]);
+    /// assert_eq!(
+    ///     report.column("sales_sum").unwrap().as_i64(),
+    ///     [Some(16), Some(4), Some(1)]
+    /// );
+    /// ```
+    pub fn group_by<C: ColumnRef>(&self, keys: &[C]) -> Result<GroupBy<'_>> {
+        let keys = keys
+            .iter()
+            .map(|col| self.column_index(col))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut index: HashMap<Vec<_>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        self.iter().enumerate().for_each(|(row_index, row)| {
+            let key = keys.iter().map(|col| row[*col].key()).collect();
+            let group = *index.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(row_index);
+        });
+
+        Ok(GroupBy {
+            df: self,
+            keys,
+            groups,
+        })
+    }
+}
+
+impl GroupBy<'_> {
+    /// Number of groups
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns `true` if there are no groups (the Data Frame holds no
+    /// rows)
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// New Data Frame with one row per group, holding the key columns
+    /// then a column per aggregation in `aggs`, as `(column, Agg)`,
+    /// named like `sales_sum`.
+    ///
+    /// Numeric aggregations ([`Agg::Sum`], [`Agg::Mean`] and
+    /// [`Agg::Std`]) are only allowed on numeric columns.
+    pub fn agg<C: ColumnRef>(&self, aggs: &[(C, Agg)]) -> Result<DataFrame> {
+        let df = self.df;
+        let mut header: Vec<String> = self
+            .keys
+            .iter()
+            .map(|col| df.header[*col].clone())
+            .collect();
+
+        let mut columns = Vec::with_capacity(aggs.len());
+        for (col, agg) in aggs {
+            let index = df.column_index(col)?;
+            let dtype = df.dtype[index];
+            if agg.is_numeric()
+                && !dtype.is_numeric()
+                && dtype != CellType::Null
+            {
+                return Err(Error::TypeMismatch {
+                    column: df.header[index].clone(),
+                    expected: CellType::F64,
+                    found: dtype,
+                });
+            }
+
+            let name = format!("{}_{}", df.header[index], agg);
+            if header.contains(&name) {
+                return Err(Error::DuplicateColumn(name));
+            }
+            header.push(name);
+            columns.push((index, *agg));
+        }
+
+        let mut cells = Vec::with_capacity(self.groups.len() * header.len());
+        self.groups.iter().for_each(|rows| {
+            let first = df.row(rows[0]).unwrap_or_default();
+            cells.extend(self.keys.iter().map(|col| first[*col].clone()));
+            cells.extend(columns.iter().map(|(index, agg)| {
+                agg.apply(
+                    rows.iter().map(|row| &df.cells()[row * df.hlen() + index]),
+                    df.dictionary(*index),
+                )
+            }));
+        });
+
+        let dtype = infer_dtypes(&cells, header.len());
+        Ok(DataFrame::from_cells(cells, header, &dtype)
+            .with_dictionaries_of(df))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Agg;
+    use crate::cell::{Cell, CellType};
+    use crate::decimal::Decimal128;
+    use crate::dframe::DataFrame;
+
+    fn decimal(text: &str) -> Cell {
+        Cell::from(Decimal128::parse(text).unwrap())
+    }
+
+    #[test]
+    fn sum_of_exact_decimals() {
+        let df = DataFrame::from_cells(
+            vec![
+                Cell::String("a".into()),
+                decimal("0.10"),
+                Cell::String("b".into()),
+                decimal("1.5"),
+                Cell::String("a".into()),
+                decimal("0.20"),
+            ],
+            vec!["key".to_owned(), "price".to_owned()],
+            &[CellType::String, CellType::Decimal128],
+        );
+        let report = df
+            .group_by(&["key"])
+            .unwrap()
+            .agg(&[("price", Agg::Sum), ("price", Agg::Mean)])
+            .unwrap();
+
+        assert_eq!(report.dtypes()[1], CellType::Decimal128);
+        let sums: Vec<_> =
+            report.iter().map(|row| row[1].to_string()).collect();
+        assert_eq!(sums, ["0.30", "1.5"]);
+        assert_eq!(report.dtypes()[2], CellType::F64);
+    }
+}
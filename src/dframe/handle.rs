@@ -0,0 +1,149 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::dframe::DataFrame;
+
+/// Owned copy of a `DataFrame` being edited, created by
+/// [`DataFrame::thaw`].
+///
+/// The builder dereferences to its Data Frame, so every edit is
+/// available on it. Edits are applied to the copy only: readers of the
+/// thawed frame (e.g. through a [`DataFrameHandle`]) keep seeing it
+/// unchanged, and [`Self::freeze`] shares the edited frame as a whole
+/// once every edit is applied.
+///
+/// ```
+/// use nn::cell::Cell;
+/// use nn::parser::CsvParser;
+///
+/// let current = CsvParser::parse_buffer(b"id,qty\n1,5\n2,0\n", 1).freeze();
+///
+/// let mut next = current.thaw();
+/// next.set(1, "qty", Cell::Number(3)).unwrap();
+/// next.push_row(vec![Cell::Number(3), Cell::Number(1)]).unwrap();
+/// let next = next.freeze();
+///
+/// assert_eq!(current.len(), 2);
+/// assert_eq!(next.column("qty").unwrap().as_i64(), [Some(5), Some(3), Some(1)]);
+/// ```
+pub struct DataFrameBuilder {
+    /// Data Frame being edited
+    df: DataFrame,
+}
+
+/// Shared, immutable handle on a `DataFrame`, cheap to clone and meant
+/// to be handed to many threads at once, e.g. the request handlers of
+/// a server exploring a parsed file.
+///
+/// The handle dereferences to the Data Frame, so every read-only query
+/// ([`DataFrame::select`], [`DataFrame::filter`], [`DataFrame::page`],
+/// ...) can run from any thread without locking. The Data Frame cannot
+/// be modified while it is shared.
+///
+/// ```
+/// use nn::dframe::DataFrameHandle;
+/// use nn::parser::CsvParser;
+///
+/// let df = CsvParser::parse_buffer(b"id,qty\n1,5\n2,0\n3,8\n", 1);
+/// let handle = DataFrameHandle::new(df);
+///
+/// std::thread::scope(|scope| {
+///     for page_no in 0..handle.total_pages(2) {
+///         let handle = handle.clone();
+///         scope.spawn(move || {
+///             let page = handle.page(page_no, 2);
+///             assert!(page.len() <= 2);
+///             assert_eq!(handle.select(&["qty"]).unwrap().hlen(), 1);
+///         });
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct DataFrameHandle {
+    /// Shared Data Frame
+    df: Arc<DataFrame>,
+}
+
+impl DataFrameHandle {
+    /// Share `df` behind a new handle
+    #[inline]
+    pub fn new(df: DataFrame) -> Self {
+        Self { df: Arc::new(df) }
+    }
+
+    /// Total handles sharing the Data Frame
+    #[inline]
+    pub fn handles(&self) -> usize {
+        Arc::strong_count(&self.df)
+    }
+
+    /// Take the Data Frame back to modify it, if this is the last
+    /// handle on it. Otherwise the handle is returned as is.
+    #[inline]
+    pub fn try_into_inner(self) -> Result<DataFrame, Self> {
+        Arc::try_unwrap(self.df).map_err(|df| Self { df })
+    }
+}
+
+impl From<DataFrame> for DataFrameHandle {
+    #[inline]
+    fn from(df: DataFrame) -> Self {
+        Self::new(df)
+    }
+}
+
+impl Deref for DataFrameHandle {
+    type Target = DataFrame;
+
+    #[inline(always)]
+    fn deref(&self) -> &DataFrame {
+        &self.df
+    }
+}
+
+impl DataFrame {
+    /// Copy of the Data Frame to edit, see [`DataFrameBuilder`]
+    pub fn thaw(&self) -> DataFrameBuilder {
+        DataFrameBuilder {
+            df: self
+                .slice(0..self.len())
+                .with_warnings(self.warnings.clone()),
+        }
+    }
+
+    /// Share the Data Frame behind a new handle, see [`DataFrameHandle`]
+    #[inline]
+    pub fn freeze(self) -> DataFrameHandle {
+        DataFrameHandle::new(self)
+    }
+}
+
+impl DataFrameBuilder {
+    /// Share the edited Data Frame behind a new handle
+    #[inline]
+    pub fn freeze(self) -> DataFrameHandle {
+        DataFrameHandle::new(self.df)
+    }
+
+    /// Edited Data Frame
+    #[inline]
+    pub fn into_frame(self) -> DataFrame {
+        self.df
+    }
+}
+
+impl Deref for DataFrameBuilder {
+    type Target = DataFrame;
+
+    #[inline(always)]
+    fn deref(&self) -> &DataFrame {
+        &self.df
+    }
+}
+
+impl DerefMut for DataFrameBuilder {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut DataFrame {
+        &mut self.df
+    }
+}
@@ -0,0 +1,168 @@
+use crate::cell::Cell;
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::Result;
+
+/// How nulls (and other non numeric cells) are handled when
+/// aggregating values
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// Ignore nulls, the result is null only if there is no value
+    #[default]
+    Skip,
+    /// The result is null as soon as one cell is null
+    Propagate,
+}
+
+/// Aggregation computed across the columns of each row
+#[derive(Clone, Copy)]
+enum RowAggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl RowAggregate {
+    /// Aggregate the numeric `cells` of a row.
+    ///
+    /// Sums, minimums and maximums of integers stay integers (sums
+    /// overflowing `i64` become decimals), means are always decimals.
+    fn reduce<'c>(
+        self,
+        cells: impl Iterator<Item = &'c Cell>,
+        nulls: NullPolicy,
+    ) -> Cell {
+        let mut values = Vec::new();
+        for cell in cells {
+            match cell {
+                Cell::Number(_)
+                | Cell::UInt(_)
+                | Cell::Decimal(_)
+                | Cell::Decimal128(_) => values.push(cell),
+                _ if nulls == NullPolicy::Propagate => return Cell::Null,
+                _ => {}
+            }
+        }
+
+        if values.is_empty() {
+            return Cell::Null;
+        }
+
+        let integers: Option<Vec<i64>> = values
+            .iter()
+            .map(|cell| match cell {
+                Cell::Number(number) => Some(*number),
+                _ => None,
+            })
+            .collect();
+        let decimals = values.iter().filter_map(|cell| cell.as_f64());
+
+        match (self, integers) {
+            (Self::Sum, Some(integers)) => integers
+                .iter()
+                .try_fold(0i64, |sum, value| sum.checked_add(*value))
+                .map_or_else(|| Cell::Decimal(decimals.sum()), Cell::Number),
+            (Self::Min, Some(integers)) => {
+                Cell::Number(*integers.iter().min().unwrap())
+            }
+            (Self::Max, Some(integers)) => {
+                Cell::Number(*integers.iter().max().unwrap())
+            }
+            (Self::Sum, None) => Cell::Decimal(decimals.sum()),
+            (Self::Mean, _) => {
+                Cell::Decimal(decimals.sum::<f64>() / values.len() as f64)
+            }
+            (Self::Min, None) => {
+                Cell::Decimal(decimals.fold(f64::INFINITY, f64::min))
+            }
+            (Self::Max, None) => {
+                Cell::Decimal(decimals.fold(f64::NEG_INFINITY, f64::max))
+            }
+        }
+    }
+}
+
+impl DataFrame {
+    /// Add column `name` aggregating the cells of `cols` in each row
+    fn row_aggregate<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+        name: &str,
+        nulls: NullPolicy,
+        aggregate: RowAggregate,
+    ) -> Result<()> {
+        let indices = cols
+            .iter()
+            .map(|col| self.column_index(col))
+            .collect::<Result<Vec<_>>>()?;
+        self.check_new_column(name)?;
+
+        let cells = self
+            .iter()
+            .map(|row| {
+                aggregate
+                    .reduce(indices.iter().map(|index| &row[*index]), nulls)
+            })
+            .collect();
+
+        self.append_column(name.to_owned(), cells);
+        Ok(())
+    }
+
+    /// Add column `name` holding the sum of the cells of `cols` in each
+    /// row. Cells that are not numbers count as nulls, handled as
+    /// described by `nulls`.
+    ///
+    /// ```
+    /// use nn::dframe::NullPolicy;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"q1,q2\n1,2\n3,\n", 1);
+    /// df.row_sum(&["q1", "q2"], "total", NullPolicy::Skip).unwrap();
+    /// assert_eq!(df.column("total").unwrap().as_i64(), [Some(3), Some(3)]);
+    /// ```
+    pub fn row_sum<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+        name: &str,
+        nulls: NullPolicy,
+    ) -> Result<()> {
+        self.row_aggregate(cols, name, nulls, RowAggregate::Sum)
+    }
+
+    /// Add column `name` holding the mean of the cells of `cols` in each
+    /// row, as decimals. Cells that are not numbers count as nulls,
+    /// handled as described by `nulls`.
+    pub fn row_mean<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+        name: &str,
+        nulls: NullPolicy,
+    ) -> Result<()> {
+        self.row_aggregate(cols, name, nulls, RowAggregate::Mean)
+    }
+
+    /// Add column `name` holding the smallest of the cells of `cols` in
+    /// each row. Cells that are not numbers count as nulls, handled as
+    /// described by `nulls`.
+    pub fn row_min<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+        name: &str,
+        nulls: NullPolicy,
+    ) -> Result<()> {
+        self.row_aggregate(cols, name, nulls, RowAggregate::Min)
+    }
+
+    /// Add column `name` holding the largest of the cells of `cols` in
+    /// each row. Cells that are not numbers count as nulls, handled as
+    /// described by `nulls`.
+    pub fn row_max<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+        name: &str,
+        nulls: NullPolicy,
+    ) -> Result<()> {
+        self.row_aggregate(cols, name, nulls, RowAggregate::Max)
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::cell::Cell;
+use crate::dframe::{infer_dtypes, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Rows kept by [`DataFrame::join`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JoinType {
+    /// Rows with a match on both sides
+    #[default]
+    Inner,
+    /// Every row of the left frame, with nulls where the right frame
+    /// has no match
+    Left,
+    /// Every row of the right frame, with nulls where the left frame
+    /// has no match
+    Right,
+    /// Every row of both frames
+    Outer,
+}
+
+impl DataFrame {
+    /// Join the rows of `self` with the rows of `other` having the same
+    /// values in the key columns `on`, present in both frames.
+    ///
+    /// The result holds the columns of `self`, then the columns of
+    /// `other` except the keys; columns of `other` whose name is taken
+    /// get the `_right` suffix. Keys match when their cells have the
+    /// same type and value, categories matching like the strings they
+    /// stand for, and null keys match nothing.
+    ///
+    /// Rows follow the order of `self`, each followed by its matches in
+    /// the order of `other`; rows of `other` without a match (kept by
+    /// [`JoinType::Right`] and [`JoinType::Outer`]) come last.
+    ///
+    /// ```
+    /// use nn::dframe::JoinType;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let sales = CsvParser::parse_buffer(b"id,units\n1,10\n2,4\n3,7\n", 1);
+    /// let names = CsvParser::parse_buffer(b"id,name\n1,pen\n3,ink\n4,cap\n", 1);
+    ///
+    /// let inner = sales.join(&names, &["id"], JoinType::Inner).unwrap();
+    /// assert_eq!(inner.header(), ["id", "units", "name"]);
+    /// assert_eq!(inner.len(), 2);
+    ///
+    /// let outer = sales.join(&names, &["id"], JoinType::Outer).unwrap();
+    /// assert_eq!(outer.column("id").unwrap().as_i64(), [Some(1), Some(2), Some(3), Some(4)]);
+    /// ```
+    pub fn join<C: ColumnRef>(
+        &self,
+        other: &DataFrame,
+        on: &[C],
+        how: JoinType,
+    ) -> Result<DataFrame> {
+        let left_keys = on
+            .iter()
+            .map(|col| self.column_index(col))
+            .collect::<Result<Vec<_>>>()?;
+        let right_keys = on
+            .iter()
+            .map(|col| other.column_index(col))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Columns of `other` added to the result
+        let right_columns: Vec<usize> = (0..other.hlen())
+            .filter(|index| !right_keys.contains(index))
+            .collect();
+
+        let mut header = self.header.clone();
+        for index in &right_columns {
+            let name = &other.header[*index];
+            let name = if header.contains(name) {
+                format!("{}_right", name)
+            } else {
+                name.clone()
+            };
+            if header.contains(&name) {
+                return Err(Error::DuplicateColumn(name));
+            }
+            header.push(name);
+        }
+
+        // Categories of `other` are given as the strings they stand for,
+        // as their codes mean nothing in the dictionaries of `self`
+        let right_cell =
+            |row: &[Cell], col: usize| row[col].decoded(other.dictionary(col));
+
+        // Rows of `other` by key, rows with a null key match nothing
+        let mut index: HashMap<Vec<_>, Vec<usize>> = HashMap::new();
+        other.iter().enumerate().for_each(|(row_index, row)| {
+            if right_keys
+                .iter()
+                .all(|col| !matches!(row[*col], Cell::Null))
+            {
+                let key = right_keys
+                    .iter()
+                    .map(|col| row[*col].key_in(other.dictionary(*col)))
+                    .collect();
+                index.entry(key).or_default().push(row_index);
+            }
+        });
+
+        let keep_left = matches!(how, JoinType::Left | JoinType::Outer);
+        let keep_right = matches!(how, JoinType::Right | JoinType::Outer);
+        let mut matched = vec![false; other.len()];
+        let mut cells =
+            Vec::with_capacity(self.len().max(other.len()) * header.len());
+
+        self.iter().for_each(|row| {
+            let key: Vec<_> = left_keys
+                .iter()
+                .map(|col| row[*col].key_in(self.dictionary(*col)))
+                .collect();
+            let matches = if left_keys
+                .iter()
+                .any(|col| matches!(row[*col], Cell::Null))
+            {
+                None
+            } else {
+                index.get(&key)
+            };
+
+            match matches {
+                Some(matches) => matches.iter().for_each(|right| {
+                    matched[*right] = true;
+                    let right = other.row(*right).unwrap_or_default();
+                    cells.extend(row.iter().cloned());
+                    cells.extend(
+                        right_columns.iter().map(|col| right_cell(right, *col)),
+                    );
+                }),
+                None if keep_left => {
+                    cells.extend(row.iter().cloned());
+                    cells.extend(right_columns.iter().map(|_| Cell::Null));
+                }
+                None => {}
+            }
+        });
+
+        if keep_right {
+            other
+                .iter()
+                .zip(&matched)
+                .filter(|(_, matched)| !**matched)
+                .for_each(|(right, _)| {
+                    let mut left = vec![Cell::Null; self.hlen()];
+                    left_keys
+                        .iter()
+                        .zip(&right_keys)
+                        .for_each(|(l, r)| left[*l] = right_cell(right, *r));
+                    cells.extend(left);
+                    cells.extend(
+                        right_columns.iter().map(|col| right_cell(right, *col)),
+                    );
+                });
+        }
+
+        let dtype = infer_dtypes(&cells, header.len());
+        let mut joined = DataFrame::from_cells(cells, header, &dtype)
+            .with_units_of(self)
+            .with_units_of(other)
+            .with_dictionaries_of(self);
+        // Keys of the right rows may be unsigned in a signed column
+        joined.unify_unsigned();
+        Ok(joined)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::cell::Cell;
+use crate::dframe::{ColId, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Value replacing the nulls of a column, see [`DataFrame::fill_null`]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillStrategy {
+    /// The same cell everywhere
+    Value(Cell),
+    /// Mean of the values of the column, which must be numeric
+    Mean,
+    /// Closest value above in the column, nulls before the first value
+    /// are kept
+    ForwardFill,
+    /// Closest value below in the column, nulls after the last value
+    /// are kept
+    BackwardFill,
+}
+
+/// Replace each null of `cells` by the last value seen before it
+fn fill_from_previous<'a>(cells: impl Iterator<Item = &'a mut Cell>) {
+    let mut previous: Option<&Cell> = None;
+    cells.for_each(|cell| match (&cell, previous) {
+        (Cell::Null, Some(value)) => *cell = value.clone(),
+        _ => previous = Some(cell),
+    });
+}
+
+impl DataFrame {
+    /// Replace the nulls of column `col` as told by `strategy`.
+    ///
+    /// The type of the column is inferred again, so filling an integer
+    /// column with its mean turns it into a decimal column. Filling
+    /// with [`FillStrategy::Mean`] fails on a column that is not
+    /// numeric.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::dframe::FillStrategy;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"day,price\n1,\n2,3\n3,\n4,5\n", 1);
+    /// df.fill_null("price", FillStrategy::ForwardFill).unwrap();
+    /// assert_eq!(df.column("price").unwrap().as_i64(), [None, Some(3), Some(3), Some(5)]);
+    ///
+    /// df.fill_null("price", FillStrategy::Mean).unwrap();
+    /// assert_eq!(df.column("price").unwrap().as_f64()[0], Some(11.0 / 3.0));
+    /// assert!(df.fill_null("day", FillStrategy::Value(Cell::Number(0))).is_ok());
+    /// ```
+    pub fn fill_null<C: ColumnRef>(
+        &mut self,
+        col: C,
+        strategy: FillStrategy,
+    ) -> Result<()> {
+        let index = self.column_index(col)?;
+        let width = self.hlen();
+
+        let cells = self
+            .cells_mut()
+            .chunks_exact_mut(width)
+            .map(|row| &mut row[index]);
+
+        match strategy {
+            FillStrategy::Value(value) => cells
+                .filter(|cell| matches!(cell, Cell::Null))
+                .for_each(|cell| *cell = value.clone()),
+            FillStrategy::Mean => {
+                // Every index is a column of the Data Frame
+                let mean = self.column(ColId(index)).unwrap().mean()?;
+                if let Some(mean) = mean {
+                    return self.fill_null(
+                        ColId(index),
+                        FillStrategy::Value(Cell::Decimal(mean)),
+                    );
+                }
+            }
+            FillStrategy::ForwardFill => fill_from_previous(cells),
+            FillStrategy::BackwardFill => fill_from_previous(cells.rev()),
+        }
+
+        self.refresh_dtype(index);
+        Ok(())
+    }
+
+    /// New Data Frame without the rows holding a null in any of the
+    /// columns of `subset`, or in any column if `subset` is `None`.
+    /// Columns keep their types.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id,price,note\n1,2,\n2,,x\n3,4,y\n", 1);
+    /// assert_eq!(df.drop_null_rows(Some(&["price"])).unwrap().len(), 2);
+    /// assert_eq!(df.drop_null_rows(None::<&[&str]>).unwrap().len(), 1);
+    /// ```
+    pub fn drop_null_rows<C: ColumnRef>(
+        &self,
+        subset: Option<&[C]>,
+    ) -> Result<DataFrame> {
+        let columns = match subset {
+            Some(cols) => cols
+                .iter()
+                .map(|col| self.column_index(col))
+                .collect::<Result<Vec<_>>>()?,
+            None => (0..self.hlen()).collect(),
+        };
+
+        Ok(self.filter(|row| {
+            columns
+                .iter()
+                .all(|index| !matches!(row[*index], Cell::Null))
+        }))
+    }
+
+    /// Whether the cell of column `col` is null, for each row.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id,price\n1,2\n2,\n,\n", 1);
+    /// assert_eq!(df.null_mask("price").unwrap(), [false, true, true]);
+    /// assert_eq!(
+    ///     df.null_counts(),
+    ///     [("id".to_owned(), 1), ("price".to_owned(), 2)]
+    /// );
+    /// ```
+    pub fn null_mask<C: ColumnRef>(&self, col: C) -> Result<Vec<bool>> {
+        let index = self.column_index(col)?;
+        Ok(self
+            .iter()
+            .map(|row| matches!(row[index], Cell::Null))
+            .collect())
+    }
+
+    /// Total nulls of each column, as `(column, nulls)` in the order of
+    /// the columns
+    pub fn null_counts(&self) -> Vec<(String, usize)> {
+        let mut counts = vec![0; self.hlen()];
+        self.iter().for_each(|row| {
+            counts
+                .iter_mut()
+                .zip(row)
+                .filter(|(_, cell)| matches!(cell, Cell::Null))
+                .for_each(|(count, _)| *count += 1)
+        });
+
+        self.header.iter().cloned().zip(counts).collect()
+    }
+
+    /// Add, for each column of `cols`, a column `<column>_is_null`
+    /// holding `1` where the cell is null and `0` elsewhere, after the
+    /// last column. Call it before [`Self::fill_null`] to keep track of
+    /// the imputed cells.
+    ///
+    /// Nothing is added if a column does not exist, or if an indicator
+    /// column already exists.
+    ///
+    /// ```
+    /// use nn::dframe::FillStrategy;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"id,age\n1,30\n2,\n3,40\n", 1);
+    /// df.add_null_indicators(&["age"]).unwrap();
+    /// df.fill_null("age", FillStrategy::Mean).unwrap();
+    ///
+    /// assert_eq!(df.header(), ["id", "age", "age_is_null"]);
+    /// let imputed = df.column("age_is_null").unwrap();
+    /// assert_eq!(imputed.as_i64(), [Some(0), Some(1), Some(0)]);
+    /// assert!(df.add_null_indicators(&["age"]).is_err());
+    /// ```
+    pub fn add_null_indicators<C: ColumnRef>(
+        &mut self,
+        cols: &[C],
+    ) -> Result<()> {
+        let mut indicators = Vec::with_capacity(cols.len());
+        for col in cols {
+            let index = self.column_index(col)?;
+            let name = format!("{}_is_null", self.header[index]);
+            self.check_new_column(&name)?;
+            if indicators.iter().any(|(added, _)| *added == name) {
+                return Err(Error::DuplicateColumn(name));
+            }
+            indicators.push((name, index));
+        }
+
+        for (name, index) in indicators {
+            let cells = self
+                .iter()
+                .map(|row| {
+                    Cell::Number(matches!(row[index], Cell::Null).into())
+                })
+                .collect();
+            self.append_column(name, cells);
+        }
+        Ok(())
+    }
+}
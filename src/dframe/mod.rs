@@ -0,0 +1,498 @@
+mod capacity;
+mod cast;
+mod categorical;
+mod col_id;
+mod column;
+mod columns;
+mod display;
+mod encode;
+mod group_by;
+mod handle;
+mod horizontal;
+mod join;
+mod missing;
+mod query;
+mod rows;
+#[cfg(feature = "serde")]
+mod serialize;
+mod series;
+mod snapshot;
+mod sort;
+mod spill;
+mod stack;
+mod strings;
+mod summary;
+mod tokenize;
+mod unique;
+mod update;
+mod versioned;
+mod view;
+mod when;
+mod window;
+
+pub use cast::CastPolicy;
+pub use col_id::{ColId, ColumnRef};
+pub use column::Column;
+pub use display::Table;
+pub use encode::Vocabulary;
+pub use group_by::{Agg, GroupBy};
+pub use handle::{DataFrameBuilder, DataFrameHandle};
+pub use horizontal::NullPolicy;
+pub use join::JoinType;
+pub use missing::FillStrategy;
+pub use query::Expr;
+pub use series::Series;
+pub use sort::{NullOrder, SortOrder};
+pub use spill::DiskBackedDataFrame;
+pub use strings::{StrColumn, StrSplit};
+pub use tokenize::{TokenizeFn, Tokenizer};
+pub use unique::Keep;
+pub use versioned::{Operation, Pipeline, VersionedFrame};
+pub use view::{ColumnsView, ColumnsViewIterator, DataFrameView, RowView};
+pub use when::{when, Conditional, When};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use vector::Vector;
+
+use crate::cell::{Cell, CellType};
+use crate::error::{Error, Result};
+#[cfg(feature = "serde")]
+use crate::iter::typed_iter::DataFrameTypedIterator;
+use crate::iter::{dframe_iter::DataFrameIterator, DataFrameColumnIterator};
+use crate::warning::ParseWarning;
+
+/// A generic cell for storing parsed data.
+///
+/// ## Column order
+/// Columns keep the order of the source (the header of the file, or
+/// the schema of an arrow batch). No operation reorders columns unless
+/// it is meant to, like [`DataFrame::move_column`]: updates keep every
+/// column in place, and the order of the header, types and cells of a
+/// row always match.
+///
+/// ## Empty frames
+/// A Data Frame may hold no rows, e.g. parsed from a file holding only
+/// a header: it keeps its columns and types, writes only its header and
+/// stacks with other frames like any other. A Data Frame without
+/// columns (parsed from an empty file) holds no rows either: `len` is
+/// always `0`, and pushing an empty row into it does nothing.
+///
+/// ```
+/// use nn::parser::CsvParser;
+///
+/// let header_only = CsvParser::parse_buffer(b"a,b\n", 1);
+/// assert_eq!((header_only.len(), header_only.hlen()), (0, 2));
+/// assert!(header_only.is_empty());
+/// assert_eq!(header_only.iter().count(), 0);
+/// assert!(header_only.to_string().ends_with("0 rows x 2 columns"));
+///
+/// let no_columns = CsvParser::parse_buffer(b"", 1);
+/// assert_eq!((no_columns.len(), no_columns.hlen()), (0, 0));
+/// assert_eq!(no_columns.iter().size_hint(), (0, Some(0)));
+/// assert_eq!(no_columns.to_string(), "0 rows x 0 columns");
+///
+/// let rows = CsvParser::parse_buffer(b"a,b\n1,2\n", 1);
+/// assert_eq!(header_only.vstack(&rows).unwrap().len(), 1);
+/// assert_eq!(no_columns.hstack(&rows).unwrap().len(), 1);
+/// ```
+pub struct DataFrame {
+    /// Column data, with room for more rows past the first `rows`
+    column_data: Vector<Cell>,
+    /// Number of rows held in `column_data`
+    rows: usize,
+    /// Header name for each string
+    header: Vec<String>,
+    /// Data Type of each header type
+    dtype: Vector<CellType>,
+    /// Issues found in cells while parsing
+    warnings: Vec<ParseWarning>,
+    /// Unit of the columns that have one, by column name
+    units: HashMap<String, String>,
+    /// Dictionary of the categorical columns, by column name: the
+    /// strings their cells stand for, by code
+    dictionaries: HashMap<String, Arc<[String]>>,
+}
+
+/// Type of each of the `width` columns of row major `cells`
+pub(crate) fn infer_dtypes(cells: &[Cell], width: usize) -> Vec<CellType> {
+    let mut dtype = vec![CellType::Null; width];
+    if width == 0 {
+        return dtype;
+    }
+
+    cells.chunks_exact(width).for_each(|row| {
+        dtype
+            .iter_mut()
+            .zip(row)
+            .for_each(|(t, cell)| *t = t.infer_type(cell.cell_type()))
+    });
+    dtype
+}
+
+impl DataFrame {
+    /// Collects data from the parser only, should not be accessible to user
+    pub(super) fn new(
+        column_data: Vector<Cell>,
+        header: Vec<String>,
+        dtype: Vector<CellType>,
+    ) -> Self {
+        let rows = column_data
+            .len()
+            .checked_div(header.len())
+            .unwrap_or_default();
+        Self {
+            column_data,
+            rows,
+            header,
+            dtype,
+            warnings: Vec::new(),
+            units: HashMap::new(),
+            dictionaries: HashMap::new(),
+        }
+    }
+
+    /// Data Frame from row major `cells`, with `dtype.len()` cells per row
+    pub(crate) fn from_cells(
+        cells: Vec<Cell>,
+        header: Vec<String>,
+        dtype: &[CellType],
+    ) -> Self {
+        let mut column_data: Vector<Cell> = Vector::zeroed(cells.len());
+        column_data
+            .iter_mut()
+            .zip(cells)
+            .for_each(|(slot, cell)| *slot = cell);
+
+        let mut types: Vector<CellType> = Vector::zeroed(dtype.len());
+        types.copy_from_slice(dtype);
+
+        Self::new(column_data, header, types)
+    }
+
+    /// Data Frame from the cells of each column, as written to disk by
+    /// spilled chunks: `rows` cells per column, with categories written
+    /// as the strings they stand for
+    pub(crate) fn from_stored_columns(
+        columns: Vec<Vec<Cell>>,
+        rows: usize,
+        header: Vec<String>,
+        dtype: &[CellType],
+    ) -> Result<Self> {
+        let width = columns.len();
+        let mut columns: Vec<_> =
+            columns.into_iter().map(Vec::into_iter).collect();
+        let cells: Vec<Cell> = (0..rows * width)
+            .map(|index| {
+                // Every column holds `rows` cells
+                columns[index % width].next().unwrap()
+            })
+            .collect();
+
+        let mut frame = Self::from_cells(cells, header, dtype);
+        for (index, dtype) in dtype.iter().enumerate() {
+            if *dtype == CellType::Categorical {
+                frame.to_categorical(ColId(index))?;
+            }
+        }
+        Ok(frame)
+    }
+
+    /// Attach the issues found while parsing the data
+    #[cfg_attr(not(feature = "parser"), allow(dead_code))]
+    pub(crate) fn with_warnings(mut self, warnings: Vec<ParseWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Attach the `units` of the columns, as `(column, unit)`
+    #[cfg_attr(not(feature = "parser"), allow(dead_code))]
+    pub(crate) fn with_units(mut self, units: HashMap<String, String>) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Copy the units of `other` for the columns sharing their name
+    pub(crate) fn with_units_of(mut self, other: &DataFrame) -> Self {
+        self.units.extend(
+            other
+                .units
+                .iter()
+                .filter(|(name, _)| self.header.contains(name))
+                .map(|(name, unit)| (name.clone(), unit.clone())),
+        );
+        self
+    }
+
+    /// Unit of column `col`, read from the units line of the file (see
+    /// [`ParserOptions::units_row`]) or set with [`Self::set_unit`].
+    ///
+    /// [`ParserOptions::units_row`]: crate::options::ParserOptions::units_row
+    #[inline]
+    pub fn unit<C: ColumnRef>(&self, col: C) -> Option<&str> {
+        let index = col.resolve(&self.header)?;
+        self.units.get(&self.header[index]).map(String::as_str)
+    }
+
+    /// Set the unit of column `col`, or remove it if `unit` is empty
+    pub fn set_unit<C: ColumnRef>(&mut self, col: C, unit: &str) -> Result<()> {
+        let name = self.header[self.column_index(col)?].clone();
+        if unit.is_empty() {
+            self.units.remove(&name);
+        } else {
+            self.units.insert(name, unit.to_owned());
+        }
+        Ok(())
+    }
+
+    /// Issues found in cells while parsing, such as integers promoted
+    /// to decimals
+    #[inline(always)]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Custom iterator that returns the chunk of data to the user.
+    ///
+    /// To test: performance
+    #[inline(always)]
+    pub fn iter(&self) -> DataFrameIterator<'_> {
+        DataFrameIterator::new(self.cells(), self.header.len())
+    }
+
+    /// Iterator deserializing each row into `T`, matching the header
+    /// names with the fields of `T` (or the cells in order, for tuples).
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Sale<'a> {
+    ///     region: &'a str,
+    ///     units: Option<u32>,
+    /// }
+    ///
+    /// let df = CsvParser::parse_buffer(b"region,units\nNorth,12\nEast,\n", 1);
+    /// let sales = df.iter_as::<Sale>().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(sales[0].region, "North");
+    /// assert_eq!(sales[1].units, None);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub fn iter_as<'a, T: serde::Deserialize<'a>>(
+        &'a self,
+    ) -> DataFrameTypedIterator<'a, T> {
+        DataFrameTypedIterator::new(self, self.iter())
+    }
+
+    /// Cells of row `index`, `None` if out of bounds
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    /// use nn::cell::Cell;
+    ///
+    /// let df = CsvParser::parse_buffer(b"a,b\n1,x\n2,y\n", 1);
+    /// assert!(matches!(df.row(1), Some([Cell::Number(2), _])));
+    /// assert!(matches!(df.get(0, "b"), Some(Cell::String(b)) if b == "x"));
+    /// assert!(df.get_at(2, 0).is_none());
+    /// ```
+    #[inline]
+    pub fn row(&self, index: usize) -> Option<&[Cell]> {
+        let width = self.hlen();
+        let start = index.checked_mul(width)?;
+        self.cells()
+            .get(start..start.checked_add(width)?)
+            .filter(|_| width > 0)
+    }
+
+    /// Cell of row `index` in column `col`, `None` if the row is out
+    /// of bounds or the column does not exist
+    #[inline]
+    pub fn get<C: ColumnRef>(&self, index: usize, col: C) -> Option<&Cell> {
+        self.get_at(index, col.resolve(&self.header)?)
+    }
+
+    /// Cell of row `index` in column `col`, `None` if out of bounds
+    #[inline]
+    pub fn get_at(&self, index: usize, col: usize) -> Option<&Cell> {
+        self.row(index)?.get(col)
+    }
+
+    /// Number of rows held, `0` when the Data Frame has no columns
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns `true` if the Data Frame holds no rows
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Row major cells of the rows held
+    #[inline(always)]
+    pub(crate) fn cells(&self) -> &[Cell] {
+        &self.column_data[..self.rows * self.hlen()]
+    }
+
+    /// Mutable row major cells of the rows held
+    #[inline(always)]
+    pub(crate) fn cells_mut(&mut self) -> &mut [Cell] {
+        let end = self.rows * self.hlen();
+        &mut self.column_data[..end]
+    }
+
+    /// Header length of the Data Frame
+    #[inline(always)]
+    pub fn hlen(&self) -> usize {
+        self.header.len()
+    }
+
+    /// Header length of the Data Frame
+    #[inline(always)]
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    /// Header length of the Data Frame
+    #[inline(always)]
+    pub fn dtypes(&self) -> &[CellType] {
+        &self.dtype
+    }
+
+    /// Id of the column named `col`, to access it without comparing
+    /// names again.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"a,b\n1,2\n", 1);
+    /// let b = df.col_id("b").unwrap();
+    /// assert!(matches!(df.get(0, b), Some(Cell::Number(2))));
+    /// assert_eq!(df.iter_col_id(b).unwrap().count(), 1);
+    /// ```
+    #[inline]
+    pub fn col_id(&self, col: &str) -> Result<ColId> {
+        self.column_index(col).map(ColId)
+    }
+
+    /// Index of the column `col`
+    #[inline]
+    pub(crate) fn column_index<C: ColumnRef>(&self, col: C) -> Result<usize> {
+        col.resolve(&self.header)
+            .ok_or_else(|| Error::ColumnNotFound(col.describe()))
+    }
+
+    /// Add column `name` holding `cells` (one per row) after the last
+    /// column
+    pub(crate) fn append_column(&mut self, name: String, cells: Vec<Cell>) {
+        let width = self.hlen();
+        debug_assert!(width == 0 || cells.len() == self.len());
+
+        let mut column_data: Vector<Cell> =
+            Vector::zeroed(cells.len() * (width + 1));
+        self.rows = cells.len();
+        column_data
+            .chunks_exact_mut(width + 1)
+            .zip(cells)
+            .enumerate()
+            .for_each(|(index, (row, cell))| {
+                row[..width]
+                    .iter_mut()
+                    .zip(
+                        &mut self.column_data
+                            [index * width..(index + 1) * width],
+                    )
+                    .for_each(|(slot, old)| {
+                        *slot = std::mem::replace(old, Cell::Null)
+                    });
+                row[width] = cell;
+            });
+
+        let mut dtype: Vector<CellType> = Vector::zeroed(width + 1);
+        dtype[..width].copy_from_slice(&self.dtype);
+        dtype[width] = CellType::Null;
+
+        self.column_data = column_data;
+        self.dtype = dtype;
+        self.header.push(name);
+        self.refresh_dtype(width);
+    }
+
+    /// Check that no column is named `col` yet
+    #[inline]
+    pub(crate) fn check_new_column(&self, col: &str) -> Result<()> {
+        if self.header.iter().any(|c| c == col) {
+            return Err(Error::DuplicateColumn(col.to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Infer again the type of column `index` from its cells, after
+    /// they were modified. Integers mixed with unsigned numbers are
+    /// unified, see [`Self::unify_unsigned`].
+    pub(crate) fn refresh_dtype(&mut self, index: usize) {
+        self.dtype[index] = self.iter().fold(CellType::Null, |t, row| {
+            t.infer_type(row[index].cell_type())
+        });
+        if self.dtype[index] != CellType::Categorical {
+            self.decode_categories(index);
+        }
+        self.unify_unsigned_column(index);
+    }
+
+    /// Handle on column `col`, with typed extraction of its values.
+    ///
+    /// Returns the handle if column exists
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"price\n2.5\n\n4\n", 1);
+    /// let price = df.column("price").unwrap();
+    /// assert_eq!(price.as_f64(), [Some(2.5), None, Some(4.0)]);
+    /// ```
+    #[inline]
+    pub fn column<C: ColumnRef>(&self, col: C) -> Option<Column<'_>> {
+        let index = col.resolve(&self.header)?;
+
+        Some(Column::new(
+            &self.header[index],
+            self.dtype[index],
+            self.cells(),
+            self.header.len(),
+            index,
+            self.dictionary(index),
+        ))
+    }
+
+    /// Column iterator for the array.
+    ///
+    /// Returns the iterator if column exists
+    #[inline(always)]
+    pub fn iter_col<C: ColumnRef>(
+        &self,
+        col: C,
+    ) -> Option<DataFrameColumnIterator<'_>> {
+        let index = col.resolve(&self.header)?;
+
+        Some(DataFrameColumnIterator::new(
+            self.cells(),
+            self.header.len(),
+            index,
+        ))
+    }
+
+    /// Column iterator for the column `col`, resolved with
+    /// [`Self::col_id`]
+    #[inline(always)]
+    pub fn iter_col_id(
+        &self,
+        col: ColId,
+    ) -> Option<DataFrameColumnIterator<'_>> {
+        self.iter_col(col)
+    }
+}
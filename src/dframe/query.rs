@@ -0,0 +1,856 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use crate::cell::Cell;
+use crate::datetime::Date;
+use crate::decimal::Decimal128;
+use crate::dframe::sort::compare_cells;
+use crate::dframe::{DataFrame, Series};
+use crate::error::{Error, Result};
+
+/// Operator between two operands of an expression
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    /// Whether the operator gives a boolean
+    #[inline]
+    fn is_predicate(self) -> bool {
+        !matches!(
+            self,
+            Self::Add | Self::Sub | Self::Mul | Self::Div | Self::Rem
+        )
+    }
+}
+
+/// Node of the tree of an expression
+#[derive(Clone, Debug)]
+enum Node {
+    /// Literal value, `true` and `false` being [`Cell::Bool`]
+    Literal(Cell),
+    /// Column, as its position in [`Expr::columns`]
+    Column(usize),
+    /// Opposite of a value
+    Neg(Box<Node>),
+    /// Negation of a condition
+    Not(Box<Node>),
+    /// Operator applied to two operands
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    /// Whether the node gives a boolean rather than a cell
+    #[inline]
+    fn is_predicate(&self) -> bool {
+        match self {
+            Self::Not(_) => true,
+            Self::Binary(op, _, _) => op.is_predicate(),
+            _ => false,
+        }
+    }
+
+    /// Whether the node can stand for a condition: predicates, and
+    /// columns and literals that may hold booleans, passing rows where
+    /// they are `true`
+    #[inline]
+    fn is_condition(&self) -> bool {
+        match self {
+            Self::Column(_) | Self::Literal(Cell::Bool(_)) => true,
+            _ => self.is_predicate(),
+        }
+    }
+}
+
+/// Expression over the columns of a `DataFrame`, parsed from text by
+/// [`Expr::parse`] and used by [`DataFrame::query`].
+///
+/// Expressions are made of:
+/// - column names, quoted with backticks when they are not
+///   identifiers (`` `unit price` ``),
+/// - integer, decimal and string literals (`"North"` or `'North'`),
+///   `true`, `false` and `null`,
+/// - arithmetic operators `+`, `-`, `*`, `/` and `%` (`+` also joins
+///   strings),
+/// - comparisons `==`, `!=`, `<`, `<=`, `>` and `>=`,
+/// - string operators `contains`, `startswith` and `endswith`,
+/// - boolean operators `&&`, `||` and `!`, and parentheses.
+///
+/// Integers are promoted to decimals when mixed with decimals, when
+/// they overflow, and by `/`. Arithmetic on nulls or on values of the
+/// wrong type gives nulls, and comparing them other than with `==` or
+/// `!=` is `false`. Dates compare with strings written `YYYY-MM-DD`,
+/// and categories like the strings they stand for. A boolean column
+/// is a condition on its own, passing the rows where it is `true`.
+#[derive(Clone, Debug)]
+pub struct Expr {
+    /// Root of the tree
+    node: Node,
+    /// Names of the columns used, in order of first use
+    columns: Vec<String>,
+}
+
+/// Token of the text of an expression
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(i64),
+    Decimal(f64),
+    Str(String),
+    Ident(String),
+    Column(String),
+    Op(&'static str),
+    End,
+}
+
+/// Operators, longest first so that `<=` is not read as `<`
+const OPERATORS: [&str; 16] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%",
+    "(", ")",
+];
+
+/// Error at byte `position` of the expression
+#[inline]
+fn invalid(position: usize, message: &str) -> Error {
+    Error::InvalidQuery {
+        position,
+        message: message.to_owned(),
+    }
+}
+
+/// Tokens of `text`, with the byte where each starts
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+
+    while let Some(c) = text[start..].chars().next() {
+        let rest = &text[start..];
+        let (token, len) = match c {
+            c if c.is_whitespace() => {
+                start += c.len_utf8();
+                continue;
+            }
+            '0'..='9' | '.' => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit() && c != '.')
+                    .unwrap_or(rest.len());
+                let literal = &rest[..len];
+                let token = match literal.parse() {
+                    Ok(number) => Token::Number(number),
+                    Err(_) => Token::Decimal(
+                        literal
+                            .parse()
+                            .map_err(|_| invalid(start, "invalid number"))?,
+                    ),
+                };
+                (token, len)
+            }
+            '"' | '\'' | '`' => {
+                let mut string = String::new();
+                let mut chars = rest.char_indices().skip(1);
+                let len = loop {
+                    match chars.next() {
+                        Some((_, '\\')) if c != '`' => match chars.next() {
+                            Some((_, escaped)) => string.push(escaped),
+                            None => break None,
+                        },
+                        Some((end, quote)) if quote == c => {
+                            break Some(end + 1)
+                        }
+                        Some((_, other)) => string.push(other),
+                        None => break None,
+                    }
+                }
+                .ok_or_else(|| invalid(start, "unterminated quote"))?;
+                match c {
+                    '`' => (Token::Column(string), len),
+                    _ => (Token::Str(string), len),
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (Token::Ident(rest[..len].to_owned()), len)
+            }
+            _ => {
+                let op = OPERATORS
+                    .iter()
+                    .find(|op| rest.starts_with(**op))
+                    .ok_or_else(|| invalid(start, "unexpected character"))?;
+                (Token::Op(op), op.len())
+            }
+        };
+        tokens.push((start, token));
+        start += len;
+    }
+
+    tokens.push((text.len(), Token::End));
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens of an expression
+struct Parser {
+    /// Tokens, ending with [`Token::End`]
+    tokens: Vec<(usize, Token)>,
+    /// Index of the next token
+    next: usize,
+    /// Names of the columns used
+    columns: Vec<String>,
+}
+
+impl Parser {
+    /// Next token, and the byte where it starts
+    #[inline]
+    fn peek(&self) -> (usize, &Token) {
+        let (position, token) = &self.tokens[self.next];
+        (*position, token)
+    }
+
+    /// Consume the next token if it is operator `op`
+    #[inline]
+    fn eat(&mut self, op: &str) -> bool {
+        let found = matches!(self.peek().1, Token::Op(next) if *next == op);
+        self.next += usize::from(found);
+        found
+    }
+
+    /// Check that `node`, starting at byte `position`, gives a boolean
+    /// if `predicate`, and a value otherwise
+    fn expect(position: usize, node: Node, predicate: bool) -> Result<Node> {
+        match predicate {
+            false if node.is_predicate() => {
+                Err(invalid(position, "expected a value"))
+            }
+            true if !node.is_condition() => {
+                Err(invalid(position, "expected a condition"))
+            }
+            _ => Ok(node),
+        }
+    }
+
+    /// Operands joined by `||`
+    fn or(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        let mut node = self.and()?;
+        while self.eat("||") {
+            let left = Self::expect(position, node, true)?;
+            let right_position = self.peek().0;
+            let right = Self::expect(right_position, self.and()?, true)?;
+            node = Node::Binary(BinaryOp::Or, Box::new(left), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    /// Operands joined by `&&`
+    fn and(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        let mut node = self.comparison()?;
+        while self.eat("&&") {
+            let left = Self::expect(position, node, true)?;
+            let right_position = self.peek().0;
+            let right = Self::expect(right_position, self.comparison()?, true)?;
+            node = Node::Binary(BinaryOp::And, Box::new(left), Box::new(right));
+        }
+        Ok(node)
+    }
+
+    /// Comparison of two values, or a single value
+    fn comparison(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        let left = self.additive()?;
+
+        let op = match self.peek().1 {
+            Token::Op("==") => BinaryOp::Eq,
+            Token::Op("!=") => BinaryOp::Ne,
+            Token::Op("<") => BinaryOp::Lt,
+            Token::Op("<=") => BinaryOp::Le,
+            Token::Op(">") => BinaryOp::Gt,
+            Token::Op(">=") => BinaryOp::Ge,
+            Token::Ident(name) if name == "contains" => BinaryOp::Contains,
+            Token::Ident(name) if name == "startswith" => BinaryOp::StartsWith,
+            Token::Ident(name) if name == "endswith" => BinaryOp::EndsWith,
+            _ => return Ok(left),
+        };
+        self.next += 1;
+
+        let left = Self::expect(position, left, false)?;
+        let right_position = self.peek().0;
+        let right = Self::expect(right_position, self.additive()?, false)?;
+        Ok(Node::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    /// Values joined by `+` and `-`
+    fn additive(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        let mut node = self.multiplicative()?;
+        loop {
+            let op = match self.peek().1 {
+                Token::Op("+") => BinaryOp::Add,
+                Token::Op("-") => BinaryOp::Sub,
+                _ => return Ok(node),
+            };
+            self.next += 1;
+            let left = Self::expect(position, node, false)?;
+            let right_position = self.peek().0;
+            let right =
+                Self::expect(right_position, self.multiplicative()?, false)?;
+            node = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// Values joined by `*`, `/` and `%`
+    fn multiplicative(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        let mut node = self.unary()?;
+        loop {
+            let op = match self.peek().1 {
+                Token::Op("*") => BinaryOp::Mul,
+                Token::Op("/") => BinaryOp::Div,
+                Token::Op("%") => BinaryOp::Rem,
+                _ => return Ok(node),
+            };
+            self.next += 1;
+            let left = Self::expect(position, node, false)?;
+            let right_position = self.peek().0;
+            let right = Self::expect(right_position, self.unary()?, false)?;
+            node = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// Value or condition, negated by `-` or `!`
+    fn unary(&mut self) -> Result<Node> {
+        let position = self.peek().0;
+        if self.eat("-") {
+            let operand = Self::expect(position + 1, self.unary()?, false)?;
+            return Ok(Node::Neg(Box::new(operand)));
+        }
+        if self.eat("!") {
+            let operand = Self::expect(position + 1, self.unary()?, true)?;
+            return Ok(Node::Not(Box::new(operand)));
+        }
+        self.primary()
+    }
+
+    /// Literal, column or parenthesized expression
+    fn primary(&mut self) -> Result<Node> {
+        let (position, token) = self.peek();
+        let node = match token.clone() {
+            Token::Number(number) => Node::Literal(Cell::Number(number)),
+            Token::Decimal(decimal) => Node::Literal(Cell::Decimal(decimal)),
+            Token::Str(string) => Node::Literal(Cell::String(string)),
+            Token::Ident(name) if name == "true" => {
+                Node::Literal(Cell::Bool(true))
+            }
+            Token::Ident(name) if name == "false" => {
+                Node::Literal(Cell::Bool(false))
+            }
+            Token::Ident(name) if name == "null" => Node::Literal(Cell::Null),
+            Token::Ident(name) | Token::Column(name) => {
+                let index = match self.columns.iter().position(|c| *c == name) {
+                    Some(index) => index,
+                    None => {
+                        self.columns.push(name);
+                        self.columns.len() - 1
+                    }
+                };
+                Node::Column(index)
+            }
+            Token::Op("(") => {
+                self.next += 1;
+                let node = self.or()?;
+                if !self.eat(")") {
+                    return Err(invalid(self.peek().0, "expected `)`"));
+                }
+                return Ok(node);
+            }
+            Token::End => {
+                return Err(invalid(position, "unexpected end of expression"))
+            }
+            Token::Op(_) => {
+                return Err(invalid(position, "unexpected operator"))
+            }
+        };
+        self.next += 1;
+        Ok(node)
+    }
+}
+
+/// Order of `left` and `right`, `None` if they cannot be compared
+fn order(left: &Cell, right: &Cell) -> Option<Ordering> {
+    match (left, right) {
+        (Cell::Null, _) | (_, Cell::Null) => None,
+        (Cell::Date(date), Cell::String(text)) => {
+            Some(date.cmp(&Date::parse_with_format(text, "%Y-%m-%d")?))
+        }
+        (Cell::String(_), Cell::Date(_)) => {
+            order(right, left).map(Ordering::reverse)
+        }
+        (Cell::String(_), Cell::String(_)) => {
+            Some(compare_cells(left, right, None))
+        }
+        (
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+        ) => Some(compare_cells(left, right, None)),
+        _ if left.cell_type() == right.cell_type() => {
+            Some(compare_cells(left, right, None))
+        }
+        _ => None,
+    }
+}
+
+/// Exact decimal value of integers and exact decimals, `None` for other
+/// cells
+#[inline]
+fn exact_decimal(cell: &Cell) -> Option<Decimal128> {
+    match cell {
+        Cell::Number(number) => Some((*number).into()),
+        Cell::UInt(number) => Some((*number).into()),
+        Cell::Decimal128(decimal) => Some(**decimal),
+        _ => None,
+    }
+}
+
+/// Result of arithmetic operator `op` on `left` and `right`: integers
+/// stay integers unless they overflow or are divided, exact decimals
+/// stay exact unless they overflow or are divided, and operands that
+/// are not numbers (or strings, for `+`) give nulls
+pub(crate) fn arithmetic(op: BinaryOp, left: &Cell, right: &Cell) -> Cell {
+    match (left, right) {
+        (Cell::Number(a), Cell::Number(b)) if op != BinaryOp::Div => {
+            let exact = match op {
+                BinaryOp::Add => a.checked_add(*b),
+                BinaryOp::Sub => a.checked_sub(*b),
+                BinaryOp::Mul => a.checked_mul(*b),
+                _ => return a.checked_rem(*b).map_or(Cell::Null, Cell::Number),
+            };
+            exact.map_or_else(
+                || arithmetic(op, &Cell::Decimal(*a as f64), right),
+                Cell::Number,
+            )
+        }
+        (Cell::Decimal128(_), _) | (_, Cell::Decimal128(_))
+            if matches!(op, BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul) =>
+        {
+            let exact = exact_decimal(left).zip(exact_decimal(right)).and_then(
+                |(a, b)| match op {
+                    BinaryOp::Add => a.checked_add(b),
+                    BinaryOp::Sub => a.checked_sub(b),
+                    _ => a.checked_mul(b),
+                },
+            );
+            let decimal =
+                |cell: &Cell| cell.as_f64().map_or(Cell::Null, Cell::Decimal);
+            exact.map_or_else(
+                || arithmetic(op, &decimal(left), &decimal(right)),
+                Cell::from,
+            )
+        }
+        (
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+        ) => {
+            let (a, b) = (left.as_f64().unwrap(), right.as_f64().unwrap());
+            Cell::Decimal(match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                BinaryOp::Div => a / b,
+                _ => a % b,
+            })
+        }
+        (Cell::String(a), Cell::String(b)) if op == BinaryOp::Add => {
+            Cell::String(format!("{}{}", a, b))
+        }
+        _ => Cell::Null,
+    }
+}
+
+impl Expr {
+    /// Expression read from `text`, see [`Expr`] for the syntax.
+    ///
+    /// Fails with [`Error::InvalidQuery`] if `text` is not a valid
+    /// expression, pointing at the byte of the issue.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            next: 0,
+            columns: Vec::new(),
+        };
+        let node = parser.or()?;
+        match parser.peek() {
+            (_, Token::End) => Ok(Self {
+                node,
+                columns: parser.columns,
+            }),
+            (position, _) => Err(invalid(position, "unexpected token")),
+        }
+    }
+
+    /// Whether the expression is a condition, giving a boolean per row
+    /// rather than a cell
+    #[inline]
+    pub fn is_predicate(&self) -> bool {
+        self.node.is_predicate()
+    }
+
+    /// Whether the expression can filter rows: a condition, or a column
+    /// or literal that may hold booleans
+    #[inline]
+    pub fn is_condition(&self) -> bool {
+        self.node.is_condition()
+    }
+
+    /// Names of the columns used by the expression
+    #[inline]
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Index in `header` of each column used by the expression
+    pub(crate) fn bind(&self, header: &[String]) -> Result<Vec<usize>> {
+        self.columns
+            .iter()
+            .map(|name| {
+                header
+                    .iter()
+                    .position(|h| h == name)
+                    .ok_or_else(|| Error::ColumnNotFound(name.clone()))
+            })
+            .collect()
+    }
+
+    /// Whether `row` passes the condition, with the columns bound to
+    /// `indices` by [`Self::bind`] and the categories of column `index`
+    /// coded in `dictionaries[index]`
+    #[inline]
+    pub(crate) fn test(
+        &self,
+        row: &[Cell],
+        indices: &[usize],
+        dictionaries: &[Option<&[String]>],
+    ) -> bool {
+        test(&self.node, &Bound::new(row, indices, dictionaries))
+    }
+
+    /// Cell of `row` given by the expression, a boolean for conditions,
+    /// like [`Self::test`]. Categories are given as the strings they
+    /// stand for.
+    #[inline]
+    pub(crate) fn eval(
+        &self,
+        row: &[Cell],
+        indices: &[usize],
+        dictionaries: &[Option<&[String]>],
+    ) -> Cell {
+        let row = Bound::new(row, indices, dictionaries);
+        match self.is_predicate() {
+            true => Cell::Bool(test(&self.node, &row)),
+            false => value(&self.node, &row).into_owned(),
+        }
+    }
+}
+
+/// Row with the columns of an expression bound to it
+struct Bound<'a> {
+    /// Cells of the row
+    row: &'a [Cell],
+    /// Index in the row of each column of the expression
+    indices: &'a [usize],
+    /// Dictionary of each column of the row, empty if none is
+    /// categorical
+    dictionaries: &'a [Option<&'a [String]>],
+}
+
+impl<'a> Bound<'a> {
+    #[inline(always)]
+    fn new(
+        row: &'a [Cell],
+        indices: &'a [usize],
+        dictionaries: &'a [Option<&'a [String]>],
+    ) -> Self {
+        Self {
+            row,
+            indices,
+            dictionaries,
+        }
+    }
+
+    /// Cell of column `index` of the expression, with the dictionary of
+    /// its categories
+    #[inline(always)]
+    fn column(&self, index: usize) -> (&'a Cell, Option<&'a [String]>) {
+        let index = self.indices[index];
+        let dictionary = self.dictionaries.get(index).copied().flatten();
+        (&self.row[index], dictionary)
+    }
+}
+
+/// Value of `node` for `row`
+fn value<'a>(node: &'a Node, row: &Bound<'a>) -> Cow<'a, Cell> {
+    match node {
+        Node::Literal(cell) => Cow::Borrowed(cell),
+        Node::Column(index) => match row.column(*index) {
+            (cell @ Cell::Categorical(_), dictionary) => {
+                Cow::Owned(cell.decoded(dictionary))
+            }
+            (cell, _) => Cow::Borrowed(cell),
+        },
+        Node::Neg(operand) => Cow::Owned(arithmetic(
+            BinaryOp::Sub,
+            &Cell::Number(0),
+            &value(operand, row),
+        )),
+        Node::Binary(op, left, right) if !op.is_predicate() => {
+            Cow::Owned(arithmetic(*op, &value(left, row), &value(right, row)))
+        }
+        // Conditions are not values, as checked while parsing
+        _ => Cow::Owned(Cell::Null),
+    }
+}
+
+/// Text of `node` for `row` if it is a string literal, or a column
+/// holding a string or a category, read without copying it
+#[inline]
+fn text<'a>(node: &'a Node, row: &Bound<'a>) -> Option<&'a str> {
+    match node {
+        Node::Literal(cell) => cell.as_str(),
+        Node::Column(index) => {
+            let (cell, dictionary) = row.column(*index);
+            cell.text(dictionary)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `row` passes the condition `node`
+fn test(node: &Node, row: &Bound<'_>) -> bool {
+    let (op, left, right) = match node {
+        Node::Not(operand) => return !test(operand, row),
+        Node::Binary(op, left, right) if op.is_predicate() => {
+            (*op, left, right)
+        }
+        _ => return matches!(*value(node, row), Cell::Bool(true)),
+    };
+    match op {
+        BinaryOp::And => return test(left, row) && test(right, row),
+        BinaryOp::Or => return test(left, row) || test(right, row),
+        _ => {}
+    }
+
+    // Strings and categories are compared in place
+    if let Some((left, right)) = text(left, row).zip(text(right, row)) {
+        return match op {
+            BinaryOp::Eq => left == right,
+            BinaryOp::Ne => left != right,
+            BinaryOp::Lt => left < right,
+            BinaryOp::Le => left <= right,
+            BinaryOp::Gt => left > right,
+            BinaryOp::Ge => left >= right,
+            BinaryOp::Contains => left.contains(right),
+            BinaryOp::StartsWith => left.starts_with(right),
+            BinaryOp::EndsWith => left.ends_with(right),
+            _ => false,
+        };
+    }
+
+    let (left, right) = (value(left, row), value(right, row));
+    let equal = || match (&*left, &*right) {
+        (Cell::Null, Cell::Null) => true,
+        (left, right) => order(left, right) == Some(Ordering::Equal),
+    };
+    let strings = left.as_str().zip(right.as_str());
+
+    match op {
+        BinaryOp::Eq => equal(),
+        BinaryOp::Ne => !equal(),
+        BinaryOp::Lt => order(&left, &right).is_some_and(Ordering::is_lt),
+        BinaryOp::Le => order(&left, &right).is_some_and(Ordering::is_le),
+        BinaryOp::Gt => order(&left, &right).is_some_and(Ordering::is_gt),
+        BinaryOp::Ge => order(&left, &right).is_some_and(Ordering::is_ge),
+        BinaryOp::Contains => strings.is_some_and(|(a, b)| a.contains(b)),
+        BinaryOp::StartsWith => strings.is_some_and(|(a, b)| a.starts_with(b)),
+        BinaryOp::EndsWith => strings.is_some_and(|(a, b)| a.ends_with(b)),
+        _ => false,
+    }
+}
+
+impl DataFrame {
+    /// New Data Frame holding the rows passing the condition `expr`,
+    /// written in the syntax of [`Expr`]. Columns keep their types.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(
+    ///     b"item,region,price,qty\npen,North,120,2\nink,South,80,1\ncap,North,95,3\n",
+    ///     1,
+    /// );
+    ///
+    /// let north = df.query("price > 100 && region == \"North\"").unwrap();
+    /// assert_eq!(north.len(), 1);
+    ///
+    /// let total = df.query("price * qty >= 200 || item startswith 'i'").unwrap();
+    /// assert_eq!(total.column("item").unwrap().as_str(), [Some("pen"), Some("ink"), Some("cap")]);
+    ///
+    /// assert!(df.query("price +").is_err());
+    /// assert!(df.query("price + 1").is_err());
+    /// assert!(df.query("weight > 1").is_err());
+    /// ```
+    pub fn query(&self, expr: &str) -> Result<DataFrame> {
+        self.filter_expr(&Expr::parse(expr)?)
+    }
+
+    /// Cell given by `expr`, written in the syntax of [`Expr`], for
+    /// each row: booleans for conditions. The result is named after the
+    /// text of the expression.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"price,qty\n2.5,4\n3,\n1,-2\n", 1);
+    ///
+    /// let flags = df.eval("qty == null || qty < 0").unwrap();
+    /// assert_eq!(flags.dtype(), CellType::Bool);
+    /// assert_eq!(flags.cells()[1].to_string(), "true");
+    ///
+    /// let total = df.eval("price * qty").unwrap();
+    /// assert_eq!(total.as_f64(), [Some(10.0), None, Some(-2.0)]);
+    /// ```
+    pub fn eval(&self, expr: &str) -> Result<Series> {
+        let cells = self.eval_expr(&Expr::parse(expr)?)?;
+        Ok(Series::new(expr, cells))
+    }
+
+    /// Cell given by `expr` for each row, booleans for conditions
+    pub fn eval_expr(&self, expr: &Expr) -> Result<Vec<Cell>> {
+        let indices = expr.bind(self.header())?;
+        let dictionaries = self.dictionaries();
+        Ok(self
+            .iter()
+            .map(|row| expr.eval(row, &indices, &dictionaries))
+            .collect())
+    }
+
+    /// New Data Frame holding the rows passing the condition `expr`.
+    /// Columns keep their types.
+    pub fn filter_expr(&self, expr: &Expr) -> Result<DataFrame> {
+        if !expr.is_condition() {
+            return Err(invalid(0, "expected a condition"));
+        }
+        let indices = expr.bind(self.header())?;
+        let dictionaries = self.dictionaries();
+        Ok(self.filter(|row| expr.test(row, &indices, &dictionaries)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::{Cell, CellType};
+    use crate::dframe::DataFrame;
+
+    /// Frame with a boolean column `active`, with a null in the last row
+    fn frame() -> DataFrame {
+        let name = |name: &str| Cell::String(name.to_owned());
+        DataFrame::from_cells(
+            vec![
+                name("ann"),
+                Cell::Bool(true),
+                name("bob"),
+                Cell::Bool(false),
+                name("cid"),
+                Cell::Null,
+            ],
+            vec!["name".to_owned(), "active".to_owned()],
+            &[CellType::String, CellType::Bool],
+        )
+    }
+
+    /// Names of the rows of `df` passing `expr`
+    fn names(df: &DataFrame, expr: &str) -> Vec<String> {
+        let rows = df.query(expr).unwrap();
+        let names = rows.column("name").unwrap().as_str();
+        names.iter().map(|name| name.unwrap().to_owned()).collect()
+    }
+
+    #[test]
+    fn compare_with_boolean_literals() {
+        let df = frame();
+        assert_eq!(names(&df, "active == true"), ["ann"]);
+        assert_eq!(names(&df, "active == false"), ["bob"]);
+        assert_eq!(names(&df, "active != true"), ["bob", "cid"]);
+        assert_eq!(names(&df, "true == active && name != 'x'"), ["ann"]);
+    }
+
+    #[test]
+    fn boolean_column_as_condition() {
+        let df = frame();
+        assert_eq!(names(&df, "active"), ["ann"]);
+        assert_eq!(names(&df, "!active"), ["bob", "cid"]);
+        assert_eq!(names(&df, "active || name == 'cid'"), ["ann", "cid"]);
+        assert_eq!(names(&df, "(active)"), ["ann"]);
+    }
+
+    #[test]
+    fn boolean_literals_as_conditions() {
+        let df = frame();
+        assert_eq!(names(&df, "true").len(), 3);
+        assert!(names(&df, "false").is_empty());
+        assert_eq!(df.eval("true").unwrap().cells()[0].to_string(), "true");
+    }
+
+    #[test]
+    fn non_boolean_column_passes_no_row() {
+        let df = frame();
+        assert!(names(&df, "name").is_empty());
+        assert!(df.query("1 + 2").is_err());
+    }
+
+    #[test]
+    fn categories_compare_like_strings() {
+        let mut df = frame();
+        df.to_categorical("name").unwrap();
+        assert_eq!(names(&df, "name >= 'bob'"), ["bob", "cid"]);
+        assert_eq!(
+            names(&df, "name == 'ann' || active == false"),
+            ["ann", "bob"]
+        );
+        assert_eq!(df.eval("name").unwrap().cells()[2].to_string(), "cid");
+
+        // Codes of the stacked frame are given again in one dictionary
+        let mut other = frame().slice(1..3);
+        other.to_categorical("name").unwrap();
+        let stacked = df.vstack(&other).unwrap();
+        assert_eq!(stacked.categories("name").unwrap(), ["ann", "bob", "cid"]);
+        assert_eq!(names(&stacked, "name == 'cid'"), ["cid", "cid"]);
+    }
+}
@@ -0,0 +1,215 @@
+use std::ops::Range;
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{infer_dtypes, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+impl DataFrame {
+    /// New Data Frame holding the rows in `range`, clamped to the rows
+    /// of the Data Frame. Columns keep their types.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"a\n1\n2\n3\n4\n", 1);
+    /// assert_eq!(df.slice(1..3).column("a").unwrap().as_i64(), [Some(2), Some(3)]);
+    /// assert_eq!(df.head(2).len(), 2);
+    /// assert_eq!(df.tail(10).len(), 4);
+    /// ```
+    pub fn slice(&self, range: Range<usize>) -> DataFrame {
+        let (len, width) = (self.len(), self.hlen());
+        let start = range.start.min(len);
+        let end = range.end.clamp(start, len);
+
+        DataFrame::from_cells(
+            self.cells()[start * width..end * width].to_vec(),
+            self.header.clone(),
+            &self.dtype,
+        )
+        .with_units_of(self)
+        .with_dictionaries_of(self)
+    }
+
+    /// New Data Frame holding the rows for which `predicate` returns
+    /// `true`. Columns keep their types.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"item,qty\npen,4\nink,12\ncap,30\n", 1);
+    ///
+    /// let large = df
+    ///     .filter_col("qty", |c| matches!(c, Cell::Number(n) if *n > 10))
+    ///     .unwrap();
+    /// assert_eq!(large.len(), 2);
+    ///
+    /// let pens = df.filter(|row| matches!(&row[0], Cell::String(s) if s == "pen"));
+    /// assert_eq!(pens.len(), 1);
+    ///
+    /// assert_eq!(df.filter_mask(&[true, false, false]).unwrap().len(), 1);
+    /// ```
+    pub fn filter<F>(&self, mut predicate: F) -> DataFrame
+    where
+        F: FnMut(&[Cell]) -> bool,
+    {
+        let cells = self
+            .iter()
+            .filter(|row| predicate(row))
+            .flat_map(|row| row.iter().cloned())
+            .collect();
+
+        DataFrame::from_cells(cells, self.header.clone(), &self.dtype)
+            .with_units_of(self)
+            .with_dictionaries_of(self)
+    }
+
+    /// New Data Frame holding the rows where `mask` is `true`. Columns
+    /// keep their types.
+    pub fn filter_mask(&self, mask: &[bool]) -> Result<DataFrame> {
+        self.check_mask(mask)?;
+
+        let mut keep = mask.iter();
+        Ok(self.filter(|_| keep.next().copied().unwrap_or_default()))
+    }
+
+    /// New Data Frame holding the rows whose cell in column `col`
+    /// passes `predicate`. Columns keep their types.
+    pub fn filter_col<C, F>(
+        &self,
+        col: C,
+        mut predicate: F,
+    ) -> Result<DataFrame>
+    where
+        C: ColumnRef,
+        F: FnMut(&Cell) -> bool,
+    {
+        let index = self.column_index(col)?;
+        Ok(self.filter(|row| predicate(&row[index])))
+    }
+
+    /// New Data Frame holding the first `n` rows (or all of them, if
+    /// there are less)
+    #[inline]
+    pub fn head(&self, n: usize) -> DataFrame {
+        self.slice(0..n)
+    }
+
+    /// New Data Frame holding the last `n` rows (or all of them, if
+    /// there are less)
+    #[inline]
+    pub fn tail(&self, n: usize) -> DataFrame {
+        self.slice(self.len().saturating_sub(n)..self.len())
+    }
+
+    /// Append `row` after the last row, one cell per column.
+    ///
+    /// Column types are unified with the types of the cells, like while
+    /// parsing.
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"id,qty\n1,2\n", 1);
+    /// df.push_row(vec![Cell::Number(2), Cell::Decimal(0.5)]).unwrap();
+    /// df.push_row(vec![Cell::Number(3), Cell::Null]).unwrap();
+    /// assert_eq!(df.len(), 3);
+    /// assert_eq!(df.dtypes(), [CellType::I64, CellType::F64]);
+    ///
+    /// df.delete_rows(&[0, 2]).unwrap();
+    /// assert_eq!(df.column("id").unwrap().as_i64(), [Some(2)]);
+    /// ```
+    pub fn push_row(&mut self, row: Vec<Cell>) -> Result<()> {
+        let width = self.hlen();
+        if row.len() != width {
+            return Err(Error::LengthMismatch {
+                expected: width,
+                found: row.len(),
+            });
+        }
+        // A Data Frame without columns holds no rows
+        if width == 0 {
+            return Ok(());
+        }
+
+        self.reserve(1);
+        let start = self.rows * width;
+        let previous: Vec<CellType> = self.dtype.to_vec();
+        let pushed: Vec<CellType> = row.iter().map(Cell::cell_type).collect();
+        self.column_data[start..start + width]
+            .iter_mut()
+            .zip(self.dtype.iter_mut())
+            .zip(row)
+            .for_each(|((slot, dtype), cell)| {
+                *dtype = dtype.infer_type(cell.cell_type());
+                *slot = cell;
+            });
+        self.rows += 1;
+
+        for index in 0..width {
+            // Strings pushed to categorical columns turn them into
+            // strings
+            if self.dtype[index] != CellType::Categorical {
+                self.decode_categories(index);
+            }
+            // Integers mixed with unsigned numbers
+            if self.dtype[index] == CellType::U64
+                && (previous[index] == CellType::I64
+                    || pushed[index] == CellType::I64)
+            {
+                self.unify_unsigned_column(index);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the rows at `indices` (in any order, repeated or not).
+    /// Other rows keep their order, and column types are inferred again
+    /// from the remaining cells.
+    pub fn delete_rows(&mut self, indices: &[usize]) -> Result<()> {
+        let len = self.len();
+        if let Some(index) = indices.iter().find(|index| **index >= len) {
+            return Err(Error::IndexOutOfBounds { index: *index, len });
+        }
+
+        let mut deleted = vec![false; len];
+        indices.iter().for_each(|index| deleted[*index] = true);
+
+        let width = self.hlen();
+        let mut kept = 0;
+        for (index, _) in deleted.iter().enumerate().filter(|(_, d)| !**d) {
+            if kept != index {
+                let (front, back) =
+                    self.column_data.split_at_mut(index * width);
+                front[kept * width..(kept + 1) * width]
+                    .swap_with_slice(&mut back[..width]);
+            }
+            kept += 1;
+        }
+
+        // Deleted cells were swapped past the kept rows
+        self.column_data[kept * width..len * width]
+            .iter_mut()
+            .for_each(|cell| *cell = Cell::Null);
+        self.rows = kept;
+
+        // Warnings follow their rows
+        let shift: Vec<usize> = deleted
+            .iter()
+            .scan(0, |removed, deleted| {
+                *removed += usize::from(*deleted);
+                Some(*removed)
+            })
+            .collect();
+        self.warnings.retain(|warning| !deleted[warning.row]);
+        self.warnings
+            .iter_mut()
+            .for_each(|warning| warning.row -= shift[warning.row]);
+
+        let dtype = infer_dtypes(self.cells(), width);
+        self.dtype.copy_from_slice(&dtype);
+        Ok(())
+    }
+}
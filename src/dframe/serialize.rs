@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::DataFrame;
+use crate::encoding::BytesEncoding;
+
+/// Serialized layout of a `DataFrame`: the header, the type of each
+/// column, the row major cells and the dictionary of each categorical
+/// column
+#[derive(Serialize)]
+struct DataFrameRef<'a> {
+    header: &'a [String],
+    dtype: &'a [CellType],
+    data: &'a [Cell],
+    dictionaries: HashMap<&'a str, &'a [String]>,
+}
+
+/// Owned counterpart of [`DataFrameRef`], for deserializing
+#[derive(Deserialize)]
+struct DataFrameOwned {
+    header: Vec<String>,
+    dtype: Vec<CellType>,
+    data: Vec<Cell>,
+    #[serde(default)]
+    dictionaries: HashMap<String, Vec<String>>,
+}
+
+impl Serialize for DataFrame {
+    /// Serialize the header, column types and cells. Warnings of the
+    /// parse are not serialized.
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        DataFrameRef {
+            header: &self.header,
+            dtype: &self.dtype,
+            data: self.cells(),
+            dictionaries: self
+                .dictionaries
+                .iter()
+                .map(|(name, values)| (name.as_str(), &**values))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataFrame {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let frame = DataFrameOwned::deserialize(deserializer)?;
+        let width = frame.header.len();
+
+        if frame.dtype.len() != width {
+            return Err(D::Error::custom(format!(
+                "expected {} column types, found {}",
+                width,
+                frame.dtype.len()
+            )));
+        }
+        if !frame.data.len().is_multiple_of(width) {
+            return Err(D::Error::custom(format!(
+                "{} cells do not fill rows of {} columns",
+                frame.data.len(),
+                width
+            )));
+        }
+
+        // Categories must be codes of the dictionary of their column
+        let dictionaries: Vec<Option<&Vec<String>>> = frame
+            .header
+            .iter()
+            .map(|name| frame.dictionaries.get(name))
+            .collect();
+        for (index, cell) in frame.data.iter().enumerate() {
+            let Cell::Categorical(code) = cell else {
+                continue;
+            };
+            let column = index % width;
+            let known = dictionaries[column]
+                .is_some_and(|values| (*code as usize) < values.len());
+            if !known {
+                return Err(D::Error::custom(format!(
+                    "unknown category {} in column {}",
+                    code, frame.header[column]
+                )));
+            }
+        }
+
+        let mut df = Self::from_cells(frame.data, frame.header, &frame.dtype);
+        for (name, values) in frame.dictionaries {
+            if let Some(index) = df.header.iter().position(|h| *h == name) {
+                df.set_dictionary(&name, values.into());
+                if df.dtype[index] != CellType::Categorical {
+                    df.decode_categories(index);
+                }
+            }
+        }
+        Ok(df)
+    }
+}
+
+/// `cell` as a JSON value, written like by `DataFrame::write_json`,
+/// categories coded in `dictionary` giving the strings they stand for:
+/// non finite decimals are nulls, dates, times and durations are
+/// strings and bytes are base64 strings
+fn json_value(cell: &Cell, dictionary: Option<&[String]>) -> Value {
+    match cell {
+        Cell::Null => Value::Null,
+        Cell::String(string) => Value::String(string.clone()),
+        Cell::Number(number) => Value::from(*number),
+        Cell::UInt(number) => Value::from(*number),
+        Cell::Decimal128(decimal) => {
+            serde_json::Number::from_f64(decimal.to_f64())
+                .map_or(Value::Null, Value::Number)
+        }
+        Cell::Decimal(decimal) => serde_json::Number::from_f64(*decimal)
+            .map_or(Value::Null, Value::Number),
+        Cell::Date(date) => Value::String(date.to_string()),
+        Cell::Time(time) => Value::String(time.to_string()),
+        Cell::Duration(duration) => Value::String(duration.to_string()),
+        Cell::Bytes(bytes) => {
+            Value::String(BytesEncoding::Base64.encode(bytes))
+        }
+        Cell::Bool(value) => Value::Bool(*value),
+        Cell::Categorical(code) => match cell.text(dictionary) {
+            Some(text) => Value::String(text.to_owned()),
+            None => Value::from(*code),
+        },
+    }
+}
+
+impl DataFrame {
+    /// Row `index` as a JSON object keyed by the header, in the order
+    /// of the columns, `None` if out of bounds.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"name,score\nada,\nbob,7.5\n", 1);
+    /// assert_eq!(
+    ///     df.row_json(1).unwrap(),
+    ///     serde_json::json!({"name": "bob", "score": 7.5})
+    /// );
+    /// assert!(df.row_json(2).is_none());
+    /// ```
+    pub fn row_json(&self, index: usize) -> Option<Value> {
+        let row = self.row(index)?;
+        let object: Map<String, Value> = self
+            .header
+            .iter()
+            .zip(row)
+            .enumerate()
+            .map(|(index, (name, cell))| {
+                (name.clone(), json_value(cell, self.dictionary(index)))
+            })
+            .collect();
+
+        Some(Value::Object(object))
+    }
+}
@@ -0,0 +1,280 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::query::{arithmetic, BinaryOp};
+use crate::dframe::{Column, DataFrame};
+use crate::error::Result;
+
+/// Owned column of cells, not attached to a `DataFrame`: the result of
+/// element-wise arithmetic on columns.
+///
+/// Columns ([`Column`] and `Series`) combine with each other and with
+/// `i64` and `f64` scalars through `+`, `-`, `*`, `/` and `%`:
+/// integers stay integers (unless they overflow, or are divided by
+/// `/`), decimals make decimals, and nulls (or cells that are not
+/// numbers) give nulls. `+` also joins strings.
+///
+/// The result is named after its left column.
+///
+/// # Panics
+///
+/// Combining two columns of different lengths panics.
+///
+/// ```
+/// use nn::cell::CellType;
+/// use nn::parser::CsvParser;
+///
+/// let mut df = CsvParser::parse_buffer(b"a,b\n1,2\n3,\n5,0.5\n", 1);
+/// let a = df.column("a").unwrap();
+/// let b = df.column("b").unwrap();
+///
+/// let total = a + b;
+/// assert_eq!(total.dtype(), CellType::F64);
+/// assert_eq!(total.as_f64(), [Some(3.0), None, Some(5.5)]);
+///
+/// let scaled = (a * 2) - 1;
+/// assert_eq!(scaled.dtype(), CellType::I64);
+/// assert_eq!(scaled.as_i64(), [Some(1), Some(5), Some(9)]);
+///
+/// df.add_series(scaled.with_name("scaled")).unwrap();
+/// assert_eq!(df.header(), ["a", "b", "scaled"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Series {
+    /// Name of the column
+    name: String,
+    /// Cells of the column, one per row
+    cells: Vec<Cell>,
+    /// Type of the column, inferred from its cells
+    dtype: CellType,
+}
+
+impl Series {
+    /// Column `name` holding `cells`, one per row. The type is inferred
+    /// from the cells.
+    pub fn new(name: &str, cells: Vec<Cell>) -> Self {
+        let dtype = cells
+            .iter()
+            .fold(CellType::Null, |t, cell| t.infer_type(cell.cell_type()));
+        Self {
+            name: name.to_owned(),
+            cells,
+            dtype,
+        }
+    }
+
+    /// Same cells, named `name`
+    #[inline]
+    pub fn with_name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.name);
+        self
+    }
+
+    /// Name of the column
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Inferred type of the column
+    #[inline(always)]
+    pub fn dtype(&self) -> CellType {
+        self.dtype
+    }
+
+    /// Total cells in the column
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if the column holds no cells
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Cells of the column
+    #[inline(always)]
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Take the cells of the column
+    #[inline(always)]
+    pub fn into_cells(self) -> Vec<Cell> {
+        self.cells
+    }
+
+    /// Values as decimals, `None` for nulls and cells that are not
+    /// numbers
+    pub fn as_f64(&self) -> Vec<Option<f64>> {
+        self.cells.iter().map(Cell::as_f64).collect()
+    }
+
+    /// Values as integers, `None` for nulls and cells that are not
+    /// integers
+    pub fn as_i64(&self) -> Vec<Option<i64>> {
+        self.cells
+            .iter()
+            .map(|cell| match cell {
+                Cell::Number(number) => Some(*number),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl From<Column<'_>> for Series {
+    #[inline]
+    fn from(column: Column<'_>) -> Self {
+        // Categories are kept as the strings they stand for, since the
+        // series holds no dictionary
+        let dtype = match column.dtype() {
+            CellType::Categorical => CellType::String,
+            dtype => dtype,
+        };
+        Self {
+            name: column.name().to_owned(),
+            cells: column.decoded(),
+            dtype,
+        }
+    }
+}
+
+impl DataFrame {
+    /// Add `series` as a column after the last column, see
+    /// [`Self::add_column`]
+    #[inline]
+    pub fn add_series(&mut self, series: Series) -> Result<()> {
+        self.add_column(&series.name, series.cells)
+    }
+}
+
+/// Operand of element-wise arithmetic
+enum Side<'a> {
+    /// Column of a Data Frame
+    Column(Column<'a>),
+    /// Cells of a series
+    Series(&'a Series),
+    /// Same value on every row
+    Scalar(Cell),
+}
+
+impl Side<'_> {
+    /// Name of the column, `None` for scalars
+    #[inline]
+    fn name(&self) -> Option<&str> {
+        match self {
+            Self::Column(column) => Some(column.name()),
+            Self::Series(series) => Some(series.name()),
+            Self::Scalar(_) => None,
+        }
+    }
+
+    /// Total cells, `None` for scalars
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        match self {
+            Self::Column(column) => Some(column.len()),
+            Self::Series(series) => Some(series.len()),
+            Self::Scalar(_) => None,
+        }
+    }
+
+    /// Cells of the operand, repeated forever for scalars
+    #[inline]
+    fn cells(&self) -> Box<dyn Iterator<Item = &Cell> + '_> {
+        match self {
+            Self::Column(column) => Box::new(column.iter()),
+            Self::Series(series) => Box::new(series.cells.iter()),
+            Self::Scalar(cell) => Box::new(std::iter::repeat(cell)),
+        }
+    }
+}
+
+/// Value usable as operand of element-wise arithmetic
+trait Operand {
+    fn side(&self) -> Side<'_>;
+}
+
+impl Operand for Column<'_> {
+    #[inline(always)]
+    fn side(&self) -> Side<'_> {
+        Side::Column(*self)
+    }
+}
+
+impl Operand for Series {
+    #[inline(always)]
+    fn side(&self) -> Side<'_> {
+        Side::Series(self)
+    }
+}
+
+impl Operand for &Series {
+    #[inline(always)]
+    fn side(&self) -> Side<'_> {
+        Side::Series(self)
+    }
+}
+
+impl Operand for i64 {
+    #[inline(always)]
+    fn side(&self) -> Side<'_> {
+        Side::Scalar(Cell::Number(*self))
+    }
+}
+
+impl Operand for f64 {
+    #[inline(always)]
+    fn side(&self) -> Side<'_> {
+        Side::Scalar(Cell::Decimal(*self))
+    }
+}
+
+/// Result of `op` on each pair of cells of `left` and `right`
+fn combine(op: BinaryOp, left: Side, right: Side) -> Series {
+    if let (Some(left), Some(right)) = (left.len(), right.len()) {
+        assert_eq!(left, right, "columns of different lengths");
+    }
+
+    let name = left.name().or(right.name()).unwrap_or_default();
+    let cells = left
+        .cells()
+        .zip(right.cells())
+        .map(|(a, b)| arithmetic(op, a, b))
+        .collect();
+    Series::new(name, cells)
+}
+
+macro_rules! binary_op {
+    ($trait:ident, $method:ident, $op:ident, $left:ty, $right:ty) => {
+        impl $trait<$right> for $left {
+            type Output = Series;
+
+            #[inline]
+            fn $method(self, rhs: $right) -> Series {
+                combine(BinaryOp::$op, self.side(), rhs.side())
+            }
+        }
+    };
+}
+
+macro_rules! binary_ops {
+    ($left:ty => $($right:ty),*) => {
+        $(
+            binary_op!(Add, add, Add, $left, $right);
+            binary_op!(Sub, sub, Sub, $left, $right);
+            binary_op!(Mul, mul, Mul, $left, $right);
+            binary_op!(Div, div, Div, $left, $right);
+            binary_op!(Rem, rem, Rem, $left, $right);
+        )*
+    };
+}
+
+binary_ops!(Column<'_> => Column<'_>, Series, &Series, i64, f64);
+binary_ops!(Series => Column<'_>, Series, &Series, i64, f64);
+binary_ops!(&Series => Column<'_>, Series, &Series, i64, f64);
+binary_ops!(i64 => Column<'_>, Series, &Series);
+binary_ops!(f64 => Column<'_>, Series, &Series);
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::Path;
+
+use vector::Vector;
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{ColId, DataFrame};
+use crate::error::{Error, Result};
+use crate::io::binary::{
+    read_array, read_cell, read_len_prefixed, write_cell, write_len_prefixed,
+};
+
+/// First bytes of a snapshot, followed by the version of the format
+const MAGIC: &[u8; 4] = b"NNDF";
+
+/// Version of the format written by [`DataFrame::save`]
+const VERSION: u8 = 1;
+
+/// Error for a file that is not a snapshot, or a damaged one
+#[inline]
+fn invalid(message: &str) -> Error {
+    Error::Io(std::io::Error::new(ErrorKind::InvalidData, message))
+}
+
+/// String read back from `reader`
+#[inline]
+fn read_string(reader: &mut &[u8]) -> Result<String> {
+    String::from_utf8(read_len_prefixed(reader)?)
+        .map_err(|_| invalid("invalid name in snapshot"))
+}
+
+impl DataFrame {
+    /// Write the Data Frame to `path` in a compact binary format, read
+    /// back with [`Self::load`] without parsing any text: each cell is
+    /// written as a tag byte followed by its value, so that loading
+    /// decodes values instead of inferring and converting fields.
+    ///
+    /// The snapshot holds the header, the type and the unit of each
+    /// column, then the cells column after column. Categories are
+    /// stored as the strings they stand for, and the warnings of the
+    /// parse are not kept.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::dframe::DataFrame;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"region,sales,qty\nnorth,10.5,3\nsouth,,4\n", 1);
+    /// df.to_categorical("region").unwrap();
+    ///
+    /// let path = std::env::temp_dir()
+    ///     .join(format!("nn_snapshot_{}.nndf", std::process::id()));
+    /// df.save(&path).unwrap();
+    /// let loaded = DataFrame::load(&path).unwrap();
+    /// assert_eq!(loaded.header(), df.header());
+    /// assert_eq!(loaded.dtypes(), [CellType::Categorical, CellType::F64, CellType::I64]);
+    /// assert_eq!(loaded.column("sales").unwrap().as_f64(), [Some(10.5), None]);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&[VERSION])?;
+        file.write_all(&(self.hlen() as u64).to_le_bytes())?;
+        file.write_all(&(self.len() as u64).to_le_bytes())?;
+
+        for (name, dtype) in self.header.iter().zip(self.dtype.iter()) {
+            let unit = self.units.get(name).map_or("", String::as_str);
+            write_len_prefixed(&mut file, name.as_bytes())?;
+            write_len_prefixed(&mut file, dtype.to_string().as_bytes())?;
+            write_len_prefixed(&mut file, unit.as_bytes())?;
+        }
+
+        for index in 0..self.hlen() {
+            let dictionary = self.dictionary(index);
+            for row in self.iter() {
+                write_cell(&mut file, &row[index], dictionary)?;
+            }
+        }
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Read a Data Frame written by [`Self::save`] from `path`.
+    ///
+    /// The file is memory mapped rather than read into a buffer, and
+    /// each cell is decoded from the mapped pages straight into its
+    /// slot in the Data Frame, so that only the Data Frame itself is
+    /// held in memory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<DataFrame> {
+        let file = File::open(path)?;
+        let mmaped = unsafe { memmap2::MmapOptions::new().map(&file)? };
+        let mut reader = &mmaped[..];
+
+        let magic: [u8; 4] = read_array(&mut reader)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a DataFrame snapshot"));
+        }
+        let [version] = read_array(&mut reader)?;
+        if version != VERSION {
+            return Err(invalid("unsupported snapshot version"));
+        }
+        let width = u64::from_le_bytes(read_array(&mut reader)?) as usize;
+        let rows = u64::from_le_bytes(read_array(&mut reader)?) as usize;
+
+        let (mut header, mut dtype) = (Vec::new(), Vec::new());
+        let mut units = HashMap::new();
+        for _ in 0..width {
+            let name = read_string(&mut reader)?;
+            let found = CellType::from_name(&read_string(&mut reader)?)
+                .ok_or_else(|| invalid("invalid type in snapshot"))?;
+            let unit = read_string(&mut reader)?;
+            if !unit.is_empty() {
+                units.insert(name.clone(), unit);
+            }
+            header.push(name);
+            dtype.push(found);
+        }
+
+        // Every cell takes at least its tag byte, which bounds the size
+        // of a damaged header before anything is allocated
+        let total = rows
+            .checked_mul(width)
+            .filter(|total| *total <= reader.len())
+            .ok_or_else(|| invalid("invalid size in snapshot"))?;
+
+        // Cells are stored column after column and held row after row
+        let mut column_data: Vector<Cell> = Vector::zeroed(total);
+        for col in 0..width {
+            for row in 0..rows {
+                column_data[row * width + col] = read_cell(&mut reader)?;
+            }
+        }
+
+        let mut types: Vector<CellType> = Vector::zeroed(width);
+        types.copy_from_slice(&dtype);
+        let mut frame = DataFrame::new(column_data, header, types);
+        for (index, dtype) in dtype.iter().enumerate() {
+            if *dtype == CellType::Categorical {
+                frame.to_categorical(ColId(index))?;
+            }
+        }
+        Ok(frame.with_units(units))
+    }
+}
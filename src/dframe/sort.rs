@@ -0,0 +1,250 @@
+use std::cmp::Ordering;
+
+use crate::cell::Cell;
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::Result;
+use crate::threads::run_jobs;
+
+/// Smallest number of rows worth sorting on its own thread
+const MIN_ROWS_PER_THREAD: usize = 1 << 14;
+
+/// Direction of a sort key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortOrder {
+    /// Smallest values first
+    #[default]
+    Asc,
+    /// Largest values first
+    Desc,
+}
+
+/// Place of the nulls in a sorted column, whatever its direction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NullOrder {
+    /// Nulls before the values
+    First,
+    /// Nulls after the values
+    #[default]
+    Last,
+}
+
+/// Rank of the values of each type, when a column mixes types
+#[inline(always)]
+fn type_rank(cell: &Cell) -> u8 {
+    match cell {
+        Cell::Null => 0,
+        Cell::Number(_)
+        | Cell::UInt(_)
+        | Cell::Decimal(_)
+        | Cell::Decimal128(_) => 1,
+        Cell::String(_) | Cell::Categorical(_) => 2,
+        Cell::Date(_) => 3,
+        Cell::Time(_) => 4,
+        Cell::Duration(_) => 5,
+        Cell::Bytes(_) => 6,
+        Cell::Bool(_) => 7,
+    }
+}
+
+/// Order of two cells of a column whose categories are coded in
+/// `dictionary`: numbers by value (integers and decimals together),
+/// strings (with categories) and bytes lexicographically, temporal
+/// values chronologically and `false` before `true`. Cells of different
+/// types are ordered by type.
+pub(crate) fn compare_cells(
+    left: &Cell,
+    right: &Cell,
+    dictionary: Option<&[String]>,
+) -> Ordering {
+    if let (Some(a), Some(b)) = (left.text(dictionary), right.text(dictionary))
+    {
+        return a.cmp(b);
+    }
+    match (left, right) {
+        (Cell::Number(a), Cell::Number(b)) => a.cmp(b),
+        (Cell::UInt(a), Cell::UInt(b)) => a.cmp(b),
+        (Cell::Decimal128(a), Cell::Decimal128(b)) => a.cmp(b),
+        // Negative numbers are below any unsigned number
+        (Cell::Number(a), Cell::UInt(b)) => {
+            u64::try_from(*a).map_or(Ordering::Less, |a| a.cmp(b))
+        }
+        (Cell::UInt(a), Cell::Number(b)) => {
+            u64::try_from(*b).map_or(Ordering::Greater, |b| a.cmp(&b))
+        }
+        (
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+            Cell::Number(_)
+            | Cell::UInt(_)
+            | Cell::Decimal(_)
+            | Cell::Decimal128(_),
+        ) => {
+            let (a, b) = (left.as_f64().unwrap(), right.as_f64().unwrap());
+            a.total_cmp(&b)
+        }
+        (Cell::Date(a), Cell::Date(b)) => a.cmp(b),
+        (Cell::Time(a), Cell::Time(b)) => a.cmp(b),
+        (Cell::Duration(a), Cell::Duration(b)) => a.cmp(b),
+        (Cell::Bytes(a), Cell::Bytes(b)) => a.cmp(b),
+        (Cell::Bool(a), Cell::Bool(b)) => a.cmp(b),
+        _ => type_rank(left).cmp(&type_rank(right)),
+    }
+}
+
+/// Sort key resolved against the columns of a Data Frame
+#[derive(Clone, Copy)]
+struct SortKey<'a> {
+    index: usize,
+    order: SortOrder,
+    dictionary: Option<&'a [String]>,
+}
+
+/// Order of `left` and `right` rows on `keys`
+fn compare_rows(
+    left: &[Cell],
+    right: &[Cell],
+    keys: &[SortKey<'_>],
+    nulls: NullOrder,
+) -> Ordering {
+    keys.iter()
+        .map(|key| {
+            let (a, b) = (&left[key.index], &right[key.index]);
+            match (a, b, nulls) {
+                (Cell::Null, Cell::Null, _) => Ordering::Equal,
+                (Cell::Null, _, NullOrder::First)
+                | (_, Cell::Null, NullOrder::Last) => Ordering::Less,
+                (Cell::Null, _, NullOrder::Last)
+                | (_, Cell::Null, NullOrder::First) => Ordering::Greater,
+                _ => match key.order {
+                    SortOrder::Asc => compare_cells(a, b, key.dictionary),
+                    SortOrder::Desc => compare_cells(b, a, key.dictionary),
+                },
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+impl DataFrame {
+    /// New Data Frame with the rows sorted on `keys`, as `(column,
+    /// order)`: rows are ordered on the first key, then on the second
+    /// for equal first keys, and so on. Nulls come last.
+    ///
+    /// The sort is stable (rows with equal keys keep their order), and
+    /// large frames are sorted on several threads.
+    ///
+    /// ```
+    /// use nn::dframe::SortOrder::{Asc, Desc};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(
+    ///     b"year,region\n2023,North\n2024,South\n2023,East\n,West\n2024,East\n",
+    ///     1,
+    /// );
+    /// let sorted = df.sort_by(&[("year", Desc), ("region", Asc)]).unwrap();
+    /// assert_eq!(
+    ///     sorted.column("region").unwrap().as_str(),
+    ///     [Some("East"), Some("South"), Some("East"), Some("North"), Some("West")]
+    /// );
+    /// ```
+    #[inline]
+    pub fn sort_by<C: ColumnRef>(
+        &self,
+        keys: &[(C, SortOrder)],
+    ) -> Result<DataFrame> {
+        self.sort_by_with_nulls(keys, NullOrder::Last)
+    }
+
+    /// New Data Frame with the rows sorted on `keys` like
+    /// [`Self::sort_by`], placing nulls as told by `nulls`
+    pub fn sort_by_with_nulls<C: ColumnRef>(
+        &self,
+        keys: &[(C, SortOrder)],
+        nulls: NullOrder,
+    ) -> Result<DataFrame> {
+        let keys = keys
+            .iter()
+            .map(|(col, order)| {
+                let index = self.column_index(col)?;
+                Ok(SortKey {
+                    index,
+                    order: *order,
+                    dictionary: self.dictionary(index),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let permutation = self.sorted_rows(&keys, nulls);
+        let cells = permutation
+            .iter()
+            .flat_map(|index| {
+                self.row(*index).unwrap_or_default().iter().cloned()
+            })
+            .collect();
+
+        Ok(
+            DataFrame::from_cells(cells, self.header.clone(), &self.dtype)
+                .with_units_of(self)
+                .with_dictionaries_of(self),
+        )
+    }
+
+    /// Indices of the rows in sorted order: runs of rows are sorted on
+    /// separate threads, then merged
+    fn sorted_rows(
+        &self,
+        keys: &[SortKey<'_>],
+        nulls: NullOrder,
+    ) -> Vec<usize> {
+        let len = self.len();
+        let threads = std::thread::available_parallelism()
+            .map_or(1, usize::from)
+            .min(len / MIN_ROWS_PER_THREAD + 1);
+        let run_len = len.div_ceil(threads).max(1);
+        let compare = |a: &usize, b: &usize| {
+            compare_rows(
+                self.row(*a).unwrap_or_default(),
+                self.row(*b).unwrap_or_default(),
+                keys,
+                nulls,
+            )
+        };
+
+        let mut indices: Vec<usize> = (0..len).collect();
+        run_jobs(
+            indices
+                .chunks_mut(run_len)
+                .map(|run| move || run.sort_by(compare)),
+        );
+
+        // Merge neighbouring runs, taking from the left run on ties so
+        // that the sort stays stable
+        let mut run_len = run_len;
+        while run_len < len {
+            let mut merged = Vec::with_capacity(len);
+            indices.chunks(2 * run_len).for_each(|pair| {
+                let (mut left, mut right) =
+                    pair.split_at(run_len.min(pair.len()));
+                while let (Some(a), Some(b)) = (left.first(), right.first()) {
+                    if compare(b, a).is_lt() {
+                        merged.push(*b);
+                        right = &right[1..];
+                    } else {
+                        merged.push(*a);
+                        left = &left[1..];
+                    }
+                }
+                merged.extend_from_slice(left);
+                merged.extend_from_slice(right);
+            });
+            indices = merged;
+            run_len *= 2;
+        }
+
+        indices
+    }
+}
@@ -0,0 +1,329 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{ColId, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+use crate::io::binary::{read_cell, write_cell};
+
+/// Directories created so far by the process, to name the next one
+static SPILL_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+/// Chunk written to disk
+#[derive(Debug)]
+struct SpilledChunk {
+    /// File holding the chunk
+    path: PathBuf,
+    /// Total rows of the chunk
+    rows: usize,
+    /// Types of the columns of the chunk
+    dtype: Vec<CellType>,
+}
+
+/// Data Frame kept on disk in chunks, so that files larger than the
+/// memory can be scanned and aggregated.
+///
+/// Each chunk is written to a file of its own, column after column, so
+/// that reading a single column skips the others. Only one chunk is in
+/// memory at a time while iterating or aggregating. The files are
+/// removed when the frame is dropped.
+///
+/// Chunks are usually written while parsing, with
+/// [`DataFrameChunkIterator::spill`].
+///
+/// ```
+/// use nn::cell::CellType;
+/// use nn::dframe::DiskBackedDataFrame;
+/// use nn::parser::CsvParser;
+///
+/// let chunks = [
+///     CsvParser::parse_buffer(b"item,qty\npen,4\nink,\n", 1),
+///     CsvParser::parse_buffer(b"item,qty\ncap,2.5\n", 1),
+/// ];
+/// let df = DiskBackedDataFrame::from_chunks(chunks).unwrap();
+/// assert_eq!((df.len(), df.chunk_count()), (3, 2));
+///
+/// assert_eq!(df.sum("qty").unwrap(), 6.5);
+/// assert_eq!(df.mean("qty").unwrap(), Some(3.25));
+/// assert_eq!(df.max("qty").unwrap(), Some(4.0));
+/// assert!(df.sum("item").is_err());
+///
+/// let rows: usize = df.chunks().map(|chunk| chunk.unwrap().len()).sum();
+/// assert_eq!(rows, 3);
+///
+/// // Each chunk keeps its own types, unified over the whole frame
+/// let qty: Vec<_> = df.chunks().map(|chunk| chunk.unwrap().dtypes()[1]).collect();
+/// assert_eq!(qty, [CellType::I64, CellType::F64]);
+/// assert_eq!(df.dtypes()[1], CellType::F64);
+/// assert_eq!(df.collect().unwrap().column("item").unwrap().as_str()[2], Some("cap"));
+/// ```
+///
+/// [`DataFrameChunkIterator::spill`]: crate::iter::chunk_iter::DataFrameChunkIterator::spill
+#[derive(Debug)]
+pub struct DiskBackedDataFrame {
+    /// Names of the columns
+    header: Vec<String>,
+    /// Types of the columns, unified over every chunk
+    dtype: Vec<CellType>,
+    /// Directory holding the chunks
+    dir: PathBuf,
+    /// Chunks in order
+    chunks: Vec<SpilledChunk>,
+}
+
+impl DiskBackedDataFrame {
+    /// Write `chunks` in a new directory of the temporary directory of
+    /// the system, see [`Self::from_chunks_in`]
+    pub fn from_chunks<I>(chunks: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = DataFrame>,
+    {
+        Self::from_chunks_in(std::env::temp_dir(), chunks)
+    }
+
+    /// Write `chunks` in a new directory of `parent`, one file per
+    /// chunk.
+    ///
+    /// Every chunk must hold the columns of the first one, in the same
+    /// order. Column types are unified like while parsing.
+    pub fn from_chunks_in<P, I>(parent: P, chunks: I) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = DataFrame>,
+    {
+        let dir = parent.as_ref().join(format!(
+            "nn-spill-{}-{}",
+            std::process::id(),
+            SPILL_DIRS.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)?;
+
+        // Removes the directory if writing fails
+        let mut frame = Self {
+            header: Vec::new(),
+            dtype: Vec::new(),
+            dir,
+            chunks: Vec::new(),
+        };
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            if index == 0 {
+                frame.header = chunk.header().to_vec();
+                frame.dtype = chunk.dtypes().to_vec();
+            }
+            frame.push(index, &chunk)?;
+        }
+
+        Ok(frame)
+    }
+
+    /// Write `chunk` as the chunk `index`, after checking its columns
+    fn push(&mut self, index: usize, chunk: &DataFrame) -> Result<()> {
+        if chunk.hlen() != self.hlen() {
+            return Err(Error::LengthMismatch {
+                expected: self.hlen(),
+                found: chunk.hlen(),
+            });
+        }
+        if let Some(name) = chunk
+            .header()
+            .iter()
+            .zip(&self.header)
+            .find(|(a, b)| a != b)
+        {
+            return Err(Error::ColumnNotFound(name.1.clone()));
+        }
+        self.dtype
+            .iter_mut()
+            .zip(chunk.dtypes())
+            .for_each(|(dtype, found)| *dtype = dtype.infer_type(*found));
+
+        // Rows, then the offset of each column, then the columns
+        let mut columns = vec![Vec::new(); self.hlen()];
+        let dictionaries = chunk.dictionaries();
+        for row in chunk.iter() {
+            for ((column, cell), dictionary) in
+                columns.iter_mut().zip(row).zip(&dictionaries)
+            {
+                write_cell(column, cell, *dictionary)?;
+            }
+        }
+        let mut offset = (8 * (1 + self.hlen())) as u64;
+
+        let path = self.dir.join(format!("{}.chunk", index));
+        let mut file = BufWriter::new(File::create(&path)?);
+        file.write_all(&(chunk.len() as u64).to_le_bytes())?;
+        for column in &columns {
+            file.write_all(&offset.to_le_bytes())?;
+            offset += column.len() as u64;
+        }
+        columns
+            .iter()
+            .try_for_each(|column| file.write_all(column))?;
+        file.flush()?;
+
+        self.chunks.push(SpilledChunk {
+            path,
+            rows: chunk.len(),
+            dtype: chunk.dtypes().to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Names of the columns
+    #[inline(always)]
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    /// Types of the columns, unified over every chunk
+    #[inline(always)]
+    pub fn dtypes(&self) -> &[CellType] {
+        &self.dtype
+    }
+
+    /// Total columns
+    #[inline(always)]
+    pub fn hlen(&self) -> usize {
+        self.header.len()
+    }
+
+    /// Total rows of every chunk
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.rows).sum()
+    }
+
+    /// Returns `true` if no chunk holds a row
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total chunks
+    #[inline(always)]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Cells of column `index` of `chunk`, read from its file
+    fn read_column(chunk: &SpilledChunk, index: usize) -> Result<Vec<Cell>> {
+        let mut file = BufReader::new(File::open(&chunk.path)?);
+        file.seek(SeekFrom::Start(8 * (1 + index) as u64))?;
+        let mut offset = [0; 8];
+        file.read_exact(&mut offset)?;
+        file.seek(SeekFrom::Start(u64::from_le_bytes(offset)))?;
+
+        (0..chunk.rows).map(|_| Ok(read_cell(&mut file)?)).collect()
+    }
+
+    /// Read `chunk` back into a Data Frame, with the column types of
+    /// the chunk rather than the unified ones
+    fn read_chunk(&self, chunk: &SpilledChunk) -> Result<DataFrame> {
+        let columns = (0..self.hlen())
+            .map(|index| Self::read_column(chunk, index))
+            .collect::<Result<Vec<_>>>()?;
+
+        DataFrame::from_stored_columns(
+            columns,
+            chunk.rows,
+            self.header.clone(),
+            &chunk.dtype,
+        )
+    }
+
+    /// Iterator reading the chunks back, one at a time. Each chunk
+    /// keeps the column types it was written with, see
+    /// [`Self::dtypes`] for the types unified over every chunk.
+    #[inline]
+    pub fn chunks(&self) -> impl Iterator<Item = Result<DataFrame>> + '_ {
+        self.chunks.iter().map(|chunk| self.read_chunk(chunk))
+    }
+
+    /// Iterator reading the cells of column `col` back, one chunk at a
+    /// time, without reading the other columns
+    pub fn column_chunks<C: ColumnRef>(
+        &self,
+        col: C,
+    ) -> Result<impl Iterator<Item = Result<Vec<Cell>>> + '_> {
+        let index = col
+            .resolve(&self.header)
+            .ok_or_else(|| Error::ColumnNotFound(col.describe()))?;
+        Ok(self
+            .chunks
+            .iter()
+            .map(move |chunk| Self::read_column(chunk, index)))
+    }
+
+    /// Every chunk read back into a single Data Frame, which must fit
+    /// in memory
+    pub fn collect(&self) -> Result<DataFrame> {
+        let mut frame =
+            DataFrame::from_cells(Vec::new(), self.header.clone(), &self.dtype);
+        for chunk in self.chunks() {
+            frame.extend(chunk?)?;
+        }
+        Ok(frame)
+    }
+
+    /// Fold the numeric values of column `col` with `f`, one chunk at a
+    /// time. Fails unless the column holds only numbers and nulls.
+    fn fold_numbers<C, T, F>(&self, col: C, init: T, mut f: F) -> Result<T>
+    where
+        C: ColumnRef,
+        F: FnMut(T, f64) -> T,
+    {
+        let index = col
+            .resolve(&self.header)
+            .ok_or_else(|| Error::ColumnNotFound(col.describe()))?;
+        let found = self.dtype[index];
+        if !found.is_numeric() && found != CellType::Null {
+            return Err(Error::TypeMismatch {
+                column: self.header[index].clone(),
+                expected: CellType::F64,
+                found,
+            });
+        }
+
+        let mut acc = init;
+        for cells in self.column_chunks(ColId(index))? {
+            acc = cells?.iter().filter_map(Cell::as_f64).fold(acc, &mut f);
+        }
+        Ok(acc)
+    }
+
+    /// Sum of the values of column `col`, ignoring nulls
+    pub fn sum<C: ColumnRef>(&self, col: C) -> Result<f64> {
+        self.fold_numbers(col, 0.0, |sum, value| sum + value)
+    }
+
+    /// Mean of the values of column `col`, ignoring nulls
+    pub fn mean<C: ColumnRef>(&self, col: C) -> Result<Option<f64>> {
+        let (sum, count) =
+            self.fold_numbers(col, (0.0, 0), |(sum, count), value| {
+                (sum + value, count + 1)
+            })?;
+        Ok((count > 0).then(|| sum / count as f64))
+    }
+
+    /// Smallest value of column `col`, ignoring nulls
+    pub fn min<C: ColumnRef>(&self, col: C) -> Result<Option<f64>> {
+        self.fold_numbers(col, None, |min: Option<f64>, value| {
+            Some(min.map_or(value, |min| min.min(value)))
+        })
+    }
+
+    /// Largest value of column `col`, ignoring nulls
+    pub fn max<C: ColumnRef>(&self, col: C) -> Result<Option<f64>> {
+        self.fold_numbers(col, None, |max: Option<f64>, value| {
+            Some(max.map_or(value, |max| max.max(value)))
+        })
+    }
+}
+
+impl Drop for DiskBackedDataFrame {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
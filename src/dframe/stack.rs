@@ -0,0 +1,339 @@
+use crate::cell::{Cell, CellType};
+use crate::dframe::categorical::Merged;
+use crate::dframe::DataFrame;
+use crate::error::{Error, Result};
+#[cfg(feature = "parser")]
+use crate::options::ParserOptions;
+#[cfg(feature = "parser")]
+use crate::parser::CsvParser;
+use crate::warning::ParseWarning;
+
+impl DataFrame {
+    /// New Data Frame holding the rows of `self` followed by the rows
+    /// of `other`, see [`Self::extend`].
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let january = CsvParser::parse_buffer(b"day,sales\n1,10\n2,12\n", 1);
+    /// let february = CsvParser::parse_buffer(b"sales,day\n9.5,1\n", 1);
+    ///
+    /// let both = january.vstack(&february).unwrap();
+    /// assert_eq!(both.len(), 3);
+    /// assert_eq!(both.dtypes(), [CellType::I64, CellType::F64]);
+    /// ```
+    pub fn vstack(&self, other: &DataFrame) -> Result<DataFrame> {
+        let mut stacked = self.slice(0..self.len());
+        stacked.extend(other.slice(0..other.len()))?;
+        Ok(stacked)
+    }
+
+    /// Append the rows of `other` after the rows of `self`.
+    ///
+    /// Columns are matched by name, so `other` must hold the same
+    /// columns, in any order. Column types are unified like while
+    /// parsing (integers promote to decimals, nulls fit any type), but
+    /// columns holding values of two different types other than
+    /// strings (e.g. dates and numbers) are rejected. Integers stacked
+    /// with unsigned numbers turn to unsigned numbers, or to decimals
+    /// if one of them is negative. Nothing is appended on error.
+    pub fn extend(&mut self, mut other: DataFrame) -> Result<()> {
+        let width = self.hlen();
+        if other.hlen() != width {
+            return Err(Error::LengthMismatch {
+                expected: width,
+                found: other.hlen(),
+            });
+        }
+
+        // Column of `other` for each column of `self`
+        let columns = self
+            .header
+            .iter()
+            .map(|name| other.column_index(name))
+            .collect::<Result<Vec<_>>>()?;
+
+        let dtype = self
+            .header
+            .iter()
+            .zip(self.dtype.iter())
+            .zip(&columns)
+            .map(|((name, expected), col)| {
+                let found = other.dtype[*col];
+                let unified = expected.infer_type(found);
+                let mixed = unified == CellType::String
+                    && *expected != CellType::String
+                    && found != CellType::String;
+
+                if mixed {
+                    return Err(Error::TypeMismatch {
+                        column: name.clone(),
+                        expected: *expected,
+                        found,
+                    });
+                }
+                Ok(unified)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Columns where integers meet unsigned numbers
+        let signed: Vec<bool> = self
+            .dtype
+            .iter()
+            .zip(&columns)
+            .map(|(expected, col)| {
+                *expected == CellType::I64 || other.dtype[*col] == CellType::I64
+            })
+            .collect();
+
+        // Categories of `other` are coded in its own dictionaries
+        let dictionaries = columns
+            .iter()
+            .enumerate()
+            .map(|(index, col)| {
+                other
+                    .dictionaries
+                    .get(&other.header[*col])
+                    .map(|theirs| self.merge_dictionary(index, theirs))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (merged, col) in dictionaries.iter().zip(&columns) {
+            match merged {
+                Some(Merged::Recoded(_, codes)) => {
+                    other.recode_categories(*col, codes)
+                }
+                Some(Merged::Decoded) => other.decode_categories(*col),
+                _ => {}
+            }
+        }
+
+        let rows = self.len();
+        self.reserve(other.len());
+
+        let appended = other.len() * width;
+        self.column_data[rows * width..rows * width + appended]
+            .chunks_exact_mut(width.max(1))
+            .zip(other.cells_mut().chunks_exact_mut(width.max(1)))
+            .for_each(|(row, source)| {
+                row.iter_mut().zip(&columns).for_each(|(slot, col)| {
+                    *slot = std::mem::replace(&mut source[*col], Cell::Null)
+                })
+            });
+
+        // Warnings of `other` refer to its own rows and columns
+        let warnings = other.warnings.iter().filter_map(|warning| {
+            let column = if warning.kind.is_row_scoped() {
+                warning.column
+            } else {
+                columns.iter().position(|c| *c == warning.column)?
+            };
+            Some(ParseWarning {
+                row: warning.row + rows,
+                column,
+                ..*warning
+            })
+        });
+        self.warnings.extend(warnings);
+
+        self.rows += other.len();
+        self.dtype.copy_from_slice(&dtype);
+        for (index, merged) in dictionaries.into_iter().enumerate() {
+            if let Some(
+                Merged::Kept(dictionary) | Merged::Recoded(dictionary, _),
+            ) = merged
+            {
+                let name = self.header[index].clone();
+                self.set_dictionary(&name, dictionary);
+            }
+            if self.dtype[index] != CellType::Categorical {
+                self.decode_categories(index);
+            }
+            if signed[index] {
+                self.unify_unsigned_column(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the CSV file `path` and append its rows after the rows of
+    /// `self`, see [`Self::extend`]: daily files can be loaded one after
+    /// another without parsing the previous ones again.
+    ///
+    /// The file must hold the columns of `self`, in any order. Column
+    /// types are promoted as the new rows require (integers to
+    /// decimals, nulls to any type), but a column whose new values do
+    /// not fit its type is rejected, and nothing is appended.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let path = std::env::temp_dir().join("nn_append_csv.csv");
+    /// std::fs::write(&path, "sales,day\n9.5,3\n").unwrap();
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"day,sales\n1,10\n2,12\n", 1);
+    /// df.append_csv(path.to_str().unwrap()).unwrap();
+    /// assert_eq!(df.len(), 3);
+    /// assert_eq!(df.dtypes(), [CellType::I64, CellType::F64]);
+    ///
+    /// std::fs::write(&path, "day,region\n4,north\n").unwrap();
+    /// assert!(df.append_csv(path.to_str().unwrap()).is_err());
+    /// assert_eq!(df.len(), 3);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "parser")]
+    #[inline]
+    pub fn append_csv(&mut self, path: &str) -> Result<()> {
+        self.append_csv_with_options(path, &ParserOptions::default())
+    }
+
+    /// Parse the CSV file `path` as described by `options` and append
+    /// its rows after the rows of `self`, see [`Self::append_csv`]
+    #[cfg(feature = "parser")]
+    pub fn append_csv_with_options(
+        &mut self,
+        path: &str,
+        options: &ParserOptions,
+    ) -> Result<()> {
+        let other = CsvParser::parse_auto_with_options(path, options)?;
+        self.extend(other)
+    }
+
+    /// New Data Frame holding the columns of `self` followed by the
+    /// columns of `other`, row by row.
+    ///
+    /// Both frames must hold the same number of rows, and no column of
+    /// `other` may share its name with a column of `self`.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let parsed = CsvParser::parse_buffer(b"id,price\n1,2.5\n2,4\n", 1);
+    /// let features = CsvParser::parse_buffer(b"expensive\n0\n1\n", 1);
+    ///
+    /// let df = parsed.hstack(&features).unwrap();
+    /// assert_eq!(df.header(), ["id", "price", "expensive"]);
+    /// assert!(parsed.hstack(&parsed).is_err());
+    /// ```
+    pub fn hstack(&self, other: &DataFrame) -> Result<DataFrame> {
+        if self.hlen() > 0 && other.hlen() > 0 && other.len() != self.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.len(),
+                found: other.len(),
+            });
+        }
+        for name in other.header() {
+            self.check_new_column(name)?;
+        }
+
+        let len = if self.hlen() > 0 {
+            self.len()
+        } else {
+            other.len()
+        };
+        let cells = (0..len)
+            .flat_map(|index| {
+                let left = self.row(index).unwrap_or_default();
+                let right = other.row(index).unwrap_or_default();
+                left.iter().chain(right).cloned()
+            })
+            .collect();
+
+        let header = self.header.iter().chain(&other.header).cloned().collect();
+        let dtype: Vec<_> = self
+            .dtype
+            .iter()
+            .chain(other.dtype.iter())
+            .copied()
+            .collect();
+
+        let warnings = self
+            .warnings
+            .iter()
+            .copied()
+            .chain(other.warnings.iter().map(|warning| ParseWarning {
+                column: if warning.kind.is_row_scoped() {
+                    warning.column
+                } else {
+                    warning.column + self.hlen()
+                },
+                ..*warning
+            }))
+            .collect();
+
+        Ok(DataFrame::from_cells(cells, header, &dtype)
+            .with_warnings(warnings)
+            .with_units_of(self)
+            .with_units_of(other)
+            .with_dictionaries_of(self)
+            .with_dictionaries_of(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::{Cell, CellType};
+    use crate::dframe::DataFrame;
+    #[cfg(feature = "parser")]
+    use crate::parser::CsvParser;
+
+    /// Single column frame of `cells`
+    fn frame(cells: Vec<Cell>, dtype: CellType) -> DataFrame {
+        DataFrame::from_cells(cells, vec!["n".to_owned()], &[dtype])
+    }
+
+    #[test]
+    fn integers_stacked_on_unsigned_numbers() {
+        let unsigned =
+            frame(vec![Cell::UInt(u64::MAX), Cell::UInt(1)], CellType::U64);
+
+        let df = unsigned
+            .vstack(&frame(vec![Cell::Number(-5)], CellType::I64))
+            .unwrap();
+        assert_eq!(df.dtypes(), [CellType::F64]);
+        assert!(df.iter().all(|row| matches!(row[0], Cell::Decimal(_))));
+        assert_eq!(
+            df.column("n").unwrap().as_f64(),
+            [Some(u64::MAX as f64), Some(1.0), Some(-5.0)]
+        );
+
+        let df = frame(vec![Cell::Number(5)], CellType::I64)
+            .vstack(&unsigned)
+            .unwrap();
+        assert_eq!(df.dtypes(), [CellType::U64]);
+        assert!(df.iter().all(|row| matches!(row[0], Cell::UInt(_))));
+
+        let mut df = unsigned.slice(0..2);
+        df.push_row(vec![Cell::Number(-5)]).unwrap();
+        assert_eq!(df.dtypes(), [CellType::F64]);
+        let mut df = unsigned.slice(0..2);
+        df.set(1, "n", Cell::Number(-5)).unwrap();
+        assert_eq!(df.dtypes(), [CellType::F64]);
+        assert_eq!(df.column("n").unwrap().as_f64()[1], Some(-5.0));
+    }
+
+    #[test]
+    #[cfg(feature = "parser")]
+    fn ragged_row_warnings_stay_in_the_first_column() {
+        /// Row and column of the warnings of `df`
+        fn positions(df: &DataFrame) -> Vec<(usize, usize)> {
+            let warnings = df.warnings().iter();
+            warnings
+                .map(|warning| (warning.row, warning.column))
+                .collect()
+        }
+
+        let parsed = CsvParser::parse_buffer(b"a,b\n1\n2,\xff\n", 1);
+        assert_eq!(positions(&parsed), [(0, 0), (1, 1)]);
+
+        let ids = CsvParser::parse_buffer(b"id\n1\n2\n", 1);
+        let df = ids.hstack(&parsed).unwrap();
+        assert_eq!(positions(&df), [(0, 0), (1, 2)]);
+
+        let swapped = CsvParser::parse_buffer(b"b,a\n\xff,3\n4\n", 1);
+        let mut df = parsed;
+        df.extend(swapped).unwrap();
+        assert_eq!(positions(&df), [(0, 0), (1, 1), (2, 1), (3, 0)]);
+    }
+}
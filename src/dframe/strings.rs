@@ -0,0 +1,195 @@
+use crate::cell::{Cell, CellType};
+use crate::dframe::{Column, DataFrame};
+use crate::error::{Error, Result};
+
+/// String operations on a string column, created by [`Column::str`].
+///
+/// Transformations return one cell per row, to add as a new column
+/// with [`DataFrame::add_column`] (nulls stay nulls), and tests return
+/// one flag per row, to use as a mask (nulls never match).
+///
+/// ```
+/// use nn::parser::CsvParser;
+///
+/// let mut df = CsvParser::parse_buffer(b"id,name\n1,Ada Lovelace\n2,alan turing\n3,\n", 1);
+/// let names = df.column("name").unwrap().str().unwrap();
+///
+/// assert_eq!(names.starts_with("a"), [false, true, false]);
+/// let last = names.split(" ").get(1);
+/// let upper = names.to_uppercase();
+///
+/// df.add_column("last", last).unwrap();
+/// df.add_column("upper", upper).unwrap();
+/// assert_eq!(
+///     df.column("last").unwrap().as_str(),
+///     [Some("Lovelace"), Some("turing"), None]
+/// );
+/// assert_eq!(df.column("upper").unwrap().as_str()[1], Some("ALAN TURING"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct StrColumn<'a> {
+    /// String column
+    column: Column<'a>,
+}
+
+/// Parts of the cells of a [`StrColumn`] around a delimiter, created by
+/// [`StrColumn::split`]
+#[derive(Clone, Copy)]
+pub struct StrSplit<'a, 'p> {
+    /// Split column
+    column: StrColumn<'a>,
+    /// Delimiter between the parts
+    delimiter: &'p str,
+}
+
+impl<'a> Column<'a> {
+    /// String operations on the column, which must hold strings (or
+    /// categories)
+    #[inline]
+    pub fn str(&self) -> Result<StrColumn<'a>> {
+        if !matches!(
+            self.dtype(),
+            CellType::String | CellType::Categorical | CellType::Null
+        ) {
+            return Err(Error::TypeMismatch {
+                column: self.name().to_owned(),
+                expected: CellType::String,
+                found: self.dtype(),
+            });
+        }
+        Ok(StrColumn { column: *self })
+    }
+}
+
+impl<'a> StrColumn<'a> {
+    /// Strings of the column, `None` for nulls
+    #[inline]
+    fn strings(&self) -> impl Iterator<Item = Option<&'a str>> + 'a {
+        let column = self.column;
+        column.iter().map(move |cell| column.text(cell))
+    }
+
+    /// Cell computed by `f` from each string, nulls stay nulls
+    #[inline]
+    fn map<F>(&self, mut f: F) -> Vec<Cell>
+    where
+        F: FnMut(&'a str) -> Option<String>,
+    {
+        self.strings()
+            .map(|string| {
+                string.and_then(&mut f).map_or(Cell::Null, Cell::String)
+            })
+            .collect()
+    }
+
+    /// Whether `f` holds for each string, `false` for nulls
+    #[inline]
+    fn test<F>(&self, mut f: F) -> Vec<bool>
+    where
+        F: FnMut(&'a str) -> bool,
+    {
+        self.strings()
+            .map(|string| string.is_some_and(&mut f))
+            .collect()
+    }
+
+    /// Strings in lowercase
+    pub fn to_lowercase(&self) -> Vec<Cell> {
+        self.map(|string| Some(string.to_lowercase()))
+    }
+
+    /// Strings in uppercase
+    pub fn to_uppercase(&self) -> Vec<Cell> {
+        self.map(|string| Some(string.to_uppercase()))
+    }
+
+    /// Strings without leading and trailing whitespace
+    pub fn trim(&self) -> Vec<Cell> {
+        self.map(|string| Some(string.trim().to_owned()))
+    }
+
+    /// Strings with every match of `from` replaced by `to`
+    pub fn replace(&self, from: &str, to: &str) -> Vec<Cell> {
+        self.map(|string| Some(string.replace(from, to)))
+    }
+
+    /// Whether each string contains `pattern`
+    pub fn contains(&self, pattern: &str) -> Vec<bool> {
+        self.test(|string| string.contains(pattern))
+    }
+
+    /// Whether each string starts with `prefix`
+    pub fn starts_with(&self, prefix: &str) -> Vec<bool> {
+        self.test(|string| string.starts_with(prefix))
+    }
+
+    /// Whether each string ends with `suffix`
+    pub fn ends_with(&self, suffix: &str) -> Vec<bool> {
+        self.test(|string| string.ends_with(suffix))
+    }
+
+    /// Parts of each string around `delimiter`, see [`StrSplit::get`]
+    #[inline]
+    pub fn split<'p>(&self, delimiter: &'p str) -> StrSplit<'a, 'p> {
+        StrSplit {
+            column: *self,
+            delimiter,
+        }
+    }
+
+    /// Whether each string matches the regular expression `pattern`
+    #[cfg(feature = "regex")]
+    pub fn matches(&self, pattern: &str) -> Result<Vec<bool>> {
+        let regex = regex::Regex::new(pattern)?;
+        Ok(self.test(|string| regex.is_match(string)))
+    }
+
+    /// Group `group` (`0` for the whole match) of the first match of
+    /// the regular expression `pattern` in each string, null where it
+    /// does not match.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"sku\nAB-12\nCD-7\nnone\n", 1);
+    /// let sku = df.column("sku").unwrap().str().unwrap();
+    /// let numbers = sku.extract(r"-(\d+)$", 1).unwrap();
+    /// assert_eq!(numbers[0].to_string(), "12");
+    /// assert!(matches!(numbers[2], nn::cell::Cell::Null));
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn extract(&self, pattern: &str, group: usize) -> Result<Vec<Cell>> {
+        let regex = regex::Regex::new(pattern)?;
+        Ok(self.map(|string| {
+            let found = regex.captures(string)?.get(group)?;
+            Some(found.as_str().to_owned())
+        }))
+    }
+}
+
+impl StrSplit<'_, '_> {
+    /// Part `n` (starting from `0`) of each string, null where there
+    /// are not as many parts
+    pub fn get(&self, n: usize) -> Vec<Cell> {
+        self.column.map(|string| {
+            string.split(self.delimiter).nth(n).map(str::to_owned)
+        })
+    }
+}
+
+impl DataFrame {
+    /// Add column `name` holding `cells`, one per row, after the last
+    /// column. The type of the column is inferred from its cells.
+    pub fn add_column(&mut self, name: &str, cells: Vec<Cell>) -> Result<()> {
+        if self.hlen() > 0 && cells.len() != self.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.len(),
+                found: cells.len(),
+            });
+        }
+        self.check_new_column(name)?;
+
+        self.append_column(name.to_owned(), cells);
+        Ok(())
+    }
+}
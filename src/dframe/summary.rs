@@ -0,0 +1,312 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{infer_dtypes, ColId, ColumnRef, DataFrame};
+use crate::error::Result;
+
+/// Running count, mean, variance, minimum and maximum of values
+/// (Welford's algorithm)
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Moments {
+    /// Total values seen
+    pub(crate) count: usize,
+    /// Mean of the values
+    pub(crate) mean: f64,
+    /// Sum of squared differences from the mean
+    m2: f64,
+    /// Smallest value
+    pub(crate) min: f64,
+    /// Largest value
+    pub(crate) max: f64,
+}
+
+impl Default for Moments {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Moments {
+    /// Account for `value`
+    #[inline]
+    pub(crate) fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Sample standard deviation, `None` with less than two values
+    #[inline]
+    pub(crate) fn std(&self) -> Option<f64> {
+        (self.count > 1).then(|| (self.m2 / (self.count - 1) as f64).sqrt())
+    }
+
+    /// `count`, `mean`, `std`, `min` and `max` cells, nulls when there
+    /// are not enough values
+    pub(crate) fn cells(&self) -> [Cell; 5] {
+        let when_any = |value: f64| {
+            if self.count > 0 {
+                Cell::Decimal(value)
+            } else {
+                Cell::Null
+            }
+        };
+
+        [
+            Cell::Number(self.count as i64),
+            when_any(self.mean),
+            self.std().map_or(Cell::Null, Cell::Decimal),
+            when_any(self.min),
+            when_any(self.max),
+        ]
+    }
+}
+
+/// Distinct values of `cells` and their occurrences, in order of first
+/// appearance
+fn occurrences<'a>(
+    cells: impl Iterator<Item = &'a Cell>,
+) -> Vec<(&'a Cell, usize)> {
+    let mut seen: HashMap<_, usize> = HashMap::new();
+    let mut values: Vec<(&Cell, usize)> = Vec::new();
+
+    cells.for_each(|cell| {
+        let value = *seen.entry(cell.key()).or_insert_with(|| {
+            values.push((cell, 0));
+            values.len() - 1
+        });
+        values[value].1 += 1;
+    });
+    values
+}
+
+impl DataFrame {
+    /// Describe each numeric column within each group of rows sharing
+    /// the same value in column `by`.
+    ///
+    /// The result has one row per group and numeric column, with the
+    /// columns `by`, `column`, `count`, `mean`, `std`, `min` and `max`.
+    /// Groups are listed in order of first appearance and numeric
+    /// columns in the order of the Data Frame. Nulls are not counted,
+    /// and `std` is the sample standard deviation.
+    pub fn summary_by<C: ColumnRef>(&self, by: C) -> Result<DataFrame> {
+        let by_index = self.column_index(by)?;
+        let numeric: Vec<usize> = self
+            .dtypes()
+            .iter()
+            .enumerate()
+            .filter(|(index, t)| *index != by_index && t.is_numeric())
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut groups: HashMap<_, usize> = HashMap::new();
+        let mut summaries: Vec<(&Cell, Vec<Moments>)> = Vec::new();
+
+        self.iter().for_each(|row| {
+            let group =
+                *groups.entry(row[by_index].key()).or_insert_with(|| {
+                    summaries.push((
+                        &row[by_index],
+                        vec![Moments::default(); numeric.len()],
+                    ));
+                    summaries.len() - 1
+                });
+
+            numeric.iter().zip(summaries[group].1.iter_mut()).for_each(
+                |(index, moments)| {
+                    if let Some(value) = row[*index].as_f64() {
+                        moments.push(value);
+                    }
+                },
+            );
+        });
+
+        let mut cells = Vec::with_capacity(summaries.len() * numeric.len() * 7);
+        summaries.iter().for_each(|(key, moments)| {
+            numeric.iter().zip(moments).for_each(|(index, moments)| {
+                cells.push((*key).clone());
+                cells.push(Cell::String(self.header()[*index].clone()));
+                cells.extend(moments.cells());
+            })
+        });
+
+        let by = self.header()[by_index].as_str();
+        let header: Vec<String> =
+            [by, "column", "count", "mean", "std", "min", "max"]
+                .iter()
+                .map(|name| (*name).to_owned())
+                .collect();
+        let dtype = infer_dtypes(&cells, header.len());
+
+        Ok(DataFrame::from_cells(cells, header, &dtype)
+            .with_dictionaries_of(self))
+    }
+
+    /// Summary of every column, one row per column of the Data Frame.
+    ///
+    /// The result has the columns `column`, `count`, `null_count`,
+    /// `mean`, `std`, `min`, `25%`, `50%`, `75%`, `max`, `unique` and
+    /// `top`. Numeric columns fill the statistics from `mean` to `max`,
+    /// other columns fill `unique` (distinct values) and `top` (most
+    /// frequent value, the first one seen on ties); the remaining
+    /// cells are nulls. Nulls are not counted, and `std` is the sample
+    /// standard deviation.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(
+    ///     b"region,sales\nNorth,10\nEast,\nNorth,30\n",
+    ///     1,
+    /// );
+    /// let summary = df.describe();
+    /// assert_eq!(summary.len(), 2);
+    /// assert!(matches!(summary.get(1, "null_count"), Some(Cell::Number(1))));
+    /// assert!(matches!(summary.get(1, "50%"), Some(Cell::Decimal(m)) if *m == 20.0));
+    /// assert!(matches!(summary.get(0, "top"), Some(Cell::String(t)) if t == "North"));
+    /// ```
+    pub fn describe(&self) -> DataFrame {
+        let header: Vec<String> = [
+            "column",
+            "count",
+            "null_count",
+            "mean",
+            "std",
+            "min",
+            "25%",
+            "50%",
+            "75%",
+            "max",
+            "unique",
+            "top",
+        ]
+        .iter()
+        .map(|name| (*name).to_owned())
+        .collect();
+
+        let mut cells = Vec::with_capacity(self.hlen() * header.len());
+        (0..self.hlen()).for_each(|index| {
+            // Every index is a column of the Data Frame
+            let column = self.column(ColId(index)).unwrap();
+            cells.push(Cell::String(column.name().to_owned()));
+
+            match column.moments() {
+                Ok(moments) => {
+                    let [count, mean, std, min, max] = moments.cells();
+                    let quantile = |q| {
+                        column
+                            .quantile(q)
+                            .ok()
+                            .flatten()
+                            .map_or(Cell::Null, Cell::Decimal)
+                    };
+
+                    cells.push(count);
+                    cells.push(Cell::Number(
+                        (self.len() - moments.count) as i64,
+                    ));
+                    cells.extend([mean, std, min]);
+                    cells.extend([
+                        quantile(0.25),
+                        quantile(0.5),
+                        quantile(0.75),
+                    ]);
+                    cells.extend([max, Cell::Null, Cell::Null]);
+                }
+                Err(_) => {
+                    let values = occurrences(
+                        column
+                            .iter()
+                            .filter(|cell| !matches!(cell, Cell::Null)),
+                    );
+
+                    let count: usize = values.iter().map(|(_, n)| n).sum();
+                    // The last maximum in reverse is the first one seen
+                    let top = values
+                        .iter()
+                        .rev()
+                        .max_by_key(|(_, occurrences)| *occurrences)
+                        .map_or(Cell::Null, |(cell, _)| {
+                            Cell::String(column.text(cell).map_or_else(
+                                || cell.to_string(),
+                                str::to_owned,
+                            ))
+                        });
+
+                    cells.push(Cell::Number(count as i64));
+                    cells.push(Cell::Number((self.len() - count) as i64));
+                    cells.extend(std::iter::repeat_n(Cell::Null, 7));
+                    cells.push(Cell::Number(values.len() as i64));
+                    cells.push(top);
+                }
+            }
+        });
+
+        let dtype = infer_dtypes(&cells, header.len());
+        DataFrame::from_cells(cells, header, &dtype)
+    }
+
+    /// Occurrences of each distinct value of column `col`, most frequent
+    /// first (values seen first come first on ties).
+    ///
+    /// The result has two columns: `col`, holding each value, and
+    /// `count`. Nulls are counted like any other value.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"region\nEast\nNorth\n\nNorth\n", 1);
+    /// let counts = df.value_counts("region").unwrap();
+    /// assert_eq!(counts.header(), ["region", "count"]);
+    /// assert!(matches!(counts.get(0, "region"), Some(Cell::String(r)) if r == "North"));
+    /// assert!(matches!(counts.get(0, "count"), Some(Cell::Number(2))));
+    /// assert_eq!(counts.len(), 3);
+    /// assert_eq!(df.nunique("region").unwrap(), 2);
+    /// ```
+    pub fn value_counts<C: ColumnRef>(&self, col: C) -> Result<DataFrame> {
+        let index = self.column_index(col)?;
+
+        let mut values = occurrences(self.iter().map(|row| &row[index]));
+        // Stable, so ties keep their order of first appearance
+        values.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let cells = values
+            .into_iter()
+            .flat_map(|(cell, count)| {
+                [cell.clone(), Cell::Number(count as i64)]
+            })
+            .collect();
+        let header = vec![self.header[index].clone(), "count".to_owned()];
+
+        Ok(DataFrame::from_cells(
+            cells,
+            header,
+            &[self.dtype[index], CellType::I64],
+        )
+        .with_dictionaries_of(self))
+    }
+
+    /// Total distinct values of column `col`, nulls excluded
+    pub fn nunique<C: ColumnRef>(&self, col: C) -> Result<usize> {
+        let index = self.column_index(col)?;
+
+        Ok(self
+            .iter()
+            .map(|row| &row[index])
+            .filter(|cell| !matches!(cell, Cell::Null))
+            .map(Cell::key)
+            .collect::<HashSet<_>>()
+            .len())
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Function splitting a text into tokens
+pub type TokenizeFn = dyn Fn(&str) -> Vec<String> + Send + Sync;
+
+/// How [`DataFrame::tokenize`] splits a text into tokens
+#[derive(Clone)]
+pub enum Tokenizer {
+    /// Runs of characters between whitespaces
+    Whitespace,
+    /// Each character
+    Chars,
+    /// Tokens returned by a function, see [`Tokenizer::custom`]
+    Custom(Arc<TokenizeFn>),
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Whitespace => f.write_str("Whitespace"),
+            Self::Chars => f.write_str("Chars"),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Tokenizer {
+    /// Tokenizer returning the tokens computed by `f`
+    #[inline]
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Tokens of `text`, in order
+    fn tokens(&self, text: &str) -> Vec<String> {
+        match self {
+            Self::Whitespace => {
+                text.split_whitespace().map(str::to_owned).collect()
+            }
+            Self::Chars => text.chars().map(String::from).collect(),
+            Self::Custom(f) => f(text),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Tokens of the string column `col`, one row per token.
+    ///
+    /// The result has the columns `row` (row of the text in the Data
+    /// Frame), `position` (of the token in its text) and `token`, ready
+    /// to be mapped to a vocabulary. Nulls have no token, and columns
+    /// that do not hold strings are rejected.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::dframe::Tokenizer;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id,text\n1,the cat\n2,\n3,a dog sat\n", 1);
+    /// let tokens = df.tokenize("text", &Tokenizer::Whitespace).unwrap();
+    /// assert_eq!(tokens.header(), ["row", "position", "token"]);
+    /// assert_eq!(tokens.len(), 5);
+    /// assert!(matches!(tokens.get(2, "row"), Some(Cell::Number(2))));
+    ///
+    /// let words = Tokenizer::custom(|text| text.split(' ').map(str::to_uppercase).collect());
+    /// let upper = df.tokenize("text", &words).unwrap();
+    /// assert_eq!(upper.column("token").unwrap().as_str()[0], Some("THE"));
+    /// ```
+    pub fn tokenize<C: ColumnRef>(
+        &self,
+        col: C,
+        tokenizer: &Tokenizer,
+    ) -> Result<DataFrame> {
+        let index = self.column_index(col)?;
+        let found = self.dtype[index];
+        if !matches!(found, CellType::String | CellType::Null) {
+            return Err(Error::TypeMismatch {
+                column: self.header[index].clone(),
+                expected: CellType::String,
+                found,
+            });
+        }
+
+        let mut cells = Vec::new();
+        self.iter().enumerate().for_each(|(row, cells_of_row)| {
+            if let Cell::String(text) = &cells_of_row[index] {
+                tokenizer.tokens(text).into_iter().enumerate().for_each(
+                    |(position, token)| {
+                        cells.push(Cell::Number(row as i64));
+                        cells.push(Cell::Number(position as i64));
+                        cells.push(Cell::String(token));
+                    },
+                );
+            }
+        });
+
+        let header = ["row", "position", "token"]
+            .iter()
+            .map(|name| (*name).to_owned())
+            .collect();
+        Ok(DataFrame::from_cells(
+            cells,
+            header,
+            &[CellType::I64, CellType::I64, CellType::String],
+        ))
+    }
+}
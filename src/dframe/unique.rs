@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use crate::cell::{Cell, CellKey};
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::Result;
+
+/// Row kept among rows holding the same values
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Keep {
+    /// Keep the first row seen
+    #[default]
+    First,
+    /// Keep the last row seen
+    Last,
+}
+
+/// Keys of the cells of `row` in `columns`
+#[inline]
+fn row_key<'a>(row: &'a [Cell], columns: &[usize]) -> Vec<CellKey<'a>> {
+    columns.iter().map(|index| row[*index].key()).collect()
+}
+
+impl DataFrame {
+    /// New Data Frame without the rows repeating the values of an
+    /// earlier (or, with [`Keep::Last`], a later) row in the columns of
+    /// `subset`, or in every column if `subset` is `None`. Rows keep
+    /// their order.
+    ///
+    /// Cells are equal when they hold the same value of the same type:
+    /// the integer `1` and the decimal `1.0` differ. Decimals are
+    /// compared by value, except that `0.0` equals `-0.0` and every
+    /// NaN equals every other NaN. Nulls equal each other.
+    ///
+    /// ```
+    /// use nn::dframe::Keep;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(
+    ///     b"id,date,qty\n1,2024-01-01,5\n1,2024-01-01,7\n2,2024-01-01,5\n",
+    ///     1,
+    /// );
+    /// let first = df.drop_duplicates(Some(&["id", "date"]), Keep::First).unwrap();
+    /// assert_eq!(first.column("qty").unwrap().as_i64(), [Some(5), Some(5)]);
+    ///
+    /// let last = df.drop_duplicates(Some(&["id", "date"]), Keep::Last).unwrap();
+    /// assert_eq!(last.column("qty").unwrap().as_i64(), [Some(7), Some(5)]);
+    ///
+    /// let all = df.drop_duplicates(None::<&[&str]>, Keep::First).unwrap();
+    /// assert_eq!(all.len(), 3);
+    /// ```
+    pub fn drop_duplicates<C: ColumnRef>(
+        &self,
+        subset: Option<&[C]>,
+        keep: Keep,
+    ) -> Result<DataFrame> {
+        let columns = match subset {
+            Some(cols) => cols
+                .iter()
+                .map(|col| self.column_index(col))
+                .collect::<Result<Vec<_>>>()?,
+            None => (0..self.hlen()).collect(),
+        };
+
+        let mut seen = HashSet::with_capacity(self.len());
+        let mut first_seen = |row| seen.insert(row_key(row, &columns));
+
+        let mask: Vec<bool> = match keep {
+            Keep::First => self.iter().map(&mut first_seen).collect(),
+            Keep::Last => {
+                let mut mask: Vec<bool> = self
+                    .cells()
+                    .rchunks_exact(self.hlen().max(1))
+                    .map(&mut first_seen)
+                    .collect();
+                mask.reverse();
+                mask
+            }
+        };
+
+        self.filter_mask(&mask)
+    }
+}
@@ -0,0 +1,163 @@
+use crate::cell::{Cell, CellType};
+use crate::dframe::{ColId, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+impl DataFrame {
+    /// Check that `mask` holds a flag for each row
+    #[inline]
+    pub(crate) fn check_mask(&self, mask: &[bool]) -> Result<()> {
+        if mask.len() != self.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.len(),
+                found: mask.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Set the cell of column `col` to `value` in every row where
+    /// `mask` is `true`.
+    ///
+    /// The type of the column is inferred again from its cells, so
+    /// setting a string in a numeric column turns it into a string
+    /// column.
+    pub fn set_where<C: ColumnRef>(
+        &mut self,
+        mask: &[bool],
+        col: C,
+        value: Cell,
+    ) -> Result<()> {
+        self.set_where_expr(mask, col, |_| value.clone())
+    }
+
+    /// Set the cell of column `col` to the value computed by `expr` in
+    /// every row where `mask` is `true`.
+    ///
+    /// `expr` receives the whole row before the update, so the new
+    /// value may depend on other columns.
+    pub fn set_where_expr<C: ColumnRef, F>(
+        &mut self,
+        mask: &[bool],
+        col: C,
+        mut expr: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&[Cell]) -> Cell,
+    {
+        let index = self.column_index(col)?;
+        self.check_mask(mask)?;
+
+        let width = self.hlen();
+        self.cells_mut()
+            .chunks_exact_mut(width)
+            .zip(mask)
+            .filter(|(_, set)| **set)
+            .for_each(|(row, _)| row[index] = expr(row));
+
+        self.refresh_dtype(index);
+        Ok(())
+    }
+
+    /// Set the cell of row `index` in column `col` to `value`, returning
+    /// the previous cell.
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"qty\n1\n2\n", 1);
+    /// df.set(1, "qty", Cell::Decimal(2.5)).unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::F64]);
+    /// assert!(df.set(2, "qty", Cell::Null).is_err());
+    /// ```
+    pub fn set<C: ColumnRef>(
+        &mut self,
+        index: usize,
+        col: C,
+        value: Cell,
+    ) -> Result<Cell> {
+        let col = self.column_index(col)?;
+        if index >= self.len() {
+            return Err(Error::IndexOutOfBounds {
+                index,
+                len: self.len(),
+            });
+        }
+
+        let width = self.hlen();
+        let value_type = value.cell_type();
+        let previous = std::mem::replace(
+            &mut self.cells_mut()[index * width + col],
+            value,
+        );
+
+        // Removing a null or a cell of the same type keeps the type of
+        // the column, otherwise the whole column is read again
+        let previous_type = previous.cell_type();
+        if previous_type == value_type || previous_type == CellType::Null {
+            let dtype = self.dtype[col];
+            self.dtype[col] = dtype.infer_type(value_type);
+            // Integers mixed with unsigned numbers
+            if self.dtype[col] == CellType::U64
+                && (dtype == CellType::I64 || value_type == CellType::I64)
+            {
+                self.unify_unsigned_column(col);
+            }
+        } else {
+            self.refresh_dtype(col);
+        }
+
+        Ok(previous)
+    }
+
+    /// Replace each cell of column `col` by the cell `f` computes from
+    /// it. The type of the column is inferred again from the new cells.
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"name,qty\n ada ,2\nBob,\n", 1);
+    /// df.apply("name", |cell| match cell {
+    ///     Cell::String(name) => Cell::String(name.trim().to_lowercase()),
+    ///     cell => cell.clone(),
+    /// })
+    /// .unwrap();
+    /// assert_eq!(df.column("name").unwrap().as_str(), [Some("ada"), Some("bob")]);
+    ///
+    /// let halved = df
+    ///     .map_column("qty", |cell| match cell.as_f64() {
+    ///         Some(qty) => Cell::Decimal(qty / 2.0),
+    ///         None => Cell::Null,
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(halved.dtypes(), [CellType::String, CellType::F64]);
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::I64]);
+    /// ```
+    pub fn apply<C: ColumnRef, F>(&mut self, col: C, mut f: F) -> Result<()>
+    where
+        F: FnMut(&Cell) -> Cell,
+    {
+        let index = self.column_index(col)?;
+        let width = self.hlen();
+
+        self.cells_mut()
+            .chunks_exact_mut(width)
+            .for_each(|row| row[index] = f(&row[index]));
+
+        self.refresh_dtype(index);
+        Ok(())
+    }
+
+    /// New Data Frame where each cell of column `col` is replaced by
+    /// the cell `f` computes from it, see [`Self::apply`]
+    pub fn map_column<C: ColumnRef, F>(&self, col: C, f: F) -> Result<DataFrame>
+    where
+        F: FnMut(&Cell) -> Cell,
+    {
+        let index = self.column_index(col)?;
+        let mut mapped = self.slice(0..self.len());
+        mapped.apply(ColId(index), f)?;
+        Ok(mapped)
+    }
+}
@@ -0,0 +1,301 @@
+use crate::cell::CellType;
+use crate::dframe::{
+    CastPolicy, DataFrame, FillStrategy, Keep, NullOrder, SortOrder,
+};
+use crate::error::Result;
+
+/// Edit of a Data Frame recorded by a [`VersionedFrame`], which can be
+/// applied again to another Data Frame holding the same columns (e.g.
+/// a fresh parse of the file).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+    /// [`DataFrame::select`]
+    Select(Vec<String>),
+    /// [`DataFrame::drop_columns`]
+    DropColumns(Vec<String>),
+    /// [`DataFrame::rename_column`]
+    RenameColumn {
+        /// Column to rename
+        column: String,
+        /// New name of the column
+        name: String,
+    },
+    /// [`DataFrame::reorder`]
+    Reorder(Vec<String>),
+    /// [`DataFrame::cast`]
+    Cast {
+        /// Column to convert
+        column: String,
+        /// Requested type
+        to: CellType,
+        /// Handling of the cells that do not convert
+        policy: CastPolicy,
+    },
+    /// [`DataFrame::fill_null`]
+    FillNull {
+        /// Column whose nulls are replaced
+        column: String,
+        /// Value replacing the nulls
+        strategy: FillStrategy,
+    },
+    /// [`DataFrame::drop_null_rows`]
+    DropNullRows(Option<Vec<String>>),
+    /// [`DataFrame::drop_duplicates`]
+    DropDuplicates {
+        /// Columns compared, all if `None`
+        subset: Option<Vec<String>>,
+        /// Row kept among the duplicates
+        keep: Keep,
+    },
+    /// [`DataFrame::sort_by_with_nulls`]
+    SortBy {
+        /// Sort keys, as `(column, order)`
+        keys: Vec<(String, SortOrder)>,
+        /// Place of the nulls
+        nulls: NullOrder,
+    },
+    /// [`DataFrame::set_unit`]
+    SetUnit {
+        /// Column of the unit
+        column: String,
+        /// Unit of the column, removed if empty
+        unit: String,
+    },
+}
+
+impl Operation {
+    /// Apply the operation to `df`, which is left unchanged on error
+    pub fn apply(&self, df: &mut DataFrame) -> Result<()> {
+        match self {
+            Self::Select(columns) => *df = df.select(columns)?,
+            Self::DropColumns(columns) => *df = df.drop_columns(columns)?,
+            Self::RenameColumn { column, name } => {
+                df.rename_column(column, name)?
+            }
+            Self::Reorder(columns) => df.reorder(columns)?,
+            Self::Cast { column, to, policy } => {
+                df.cast(column, *to, *policy)?
+            }
+            Self::FillNull { column, strategy } => {
+                df.fill_null(column, strategy.clone())?
+            }
+            Self::DropNullRows(subset) => {
+                *df = df.drop_null_rows(subset.as_deref())?
+            }
+            Self::DropDuplicates { subset, keep } => {
+                *df = df.drop_duplicates(subset.as_deref(), *keep)?
+            }
+            Self::SortBy { keys, nulls } => {
+                *df = df.sort_by_with_nulls(keys, *nulls)?
+            }
+            Self::SetUnit { column, unit } => df.set_unit(column, unit)?,
+        }
+        Ok(())
+    }
+}
+
+/// Operations to apply in order to a Data Frame, e.g. the cleaning
+/// steps recorded by a [`VersionedFrame`] on last month's file, run
+/// again on this month's one.
+///
+/// With the `serde` feature, a pipeline is saved and loaded as a JSON
+/// plan, so it can be kept along with the data and run without writing
+/// code.
+///
+/// ```
+/// use nn::dframe::{Keep, Operation, Pipeline};
+/// use nn::parser::CsvParser;
+///
+/// let pipeline = Pipeline::new(vec![
+///     Operation::DropDuplicates { subset: None, keep: Keep::First },
+///     Operation::Select(vec!["id".to_owned()]),
+/// ]);
+///
+/// let df = CsvParser::parse_buffer(b"id,qty\n1,5\n1,5\n2,3\n", 1);
+/// let df = pipeline.run(df).unwrap();
+/// assert_eq!((df.len(), df.hlen()), (2, 1));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Pipeline {
+    /// Operations, in order
+    operations: Vec<Operation>,
+}
+
+impl Pipeline {
+    /// Pipeline applying `operations`, in order
+    #[inline]
+    pub fn new(operations: Vec<Operation>) -> Self {
+        Self { operations }
+    }
+
+    /// Operations of the pipeline, in order
+    #[inline]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// `df` after applying each operation of the pipeline, in order
+    pub fn run(&self, mut df: DataFrame) -> Result<DataFrame> {
+        for operation in &self.operations {
+            operation.apply(&mut df)?;
+        }
+        Ok(df)
+    }
+
+    /// Pipeline as a JSON plan: an array holding each operation
+    ///
+    /// ```
+    /// use nn::dframe::{Operation, Pipeline};
+    ///
+    /// let pipeline = Pipeline::new(vec![Operation::RenameColumn {
+    ///     column: "qty".to_owned(),
+    ///     name: "quantity".to_owned(),
+    /// }]);
+    ///
+    /// let plan = pipeline.to_json();
+    /// assert_eq!(plan, r#"[{"RenameColumn":{"column":"qty","name":"quantity"}}]"#);
+    /// assert_eq!(Pipeline::from_json(&plan).unwrap().operations().len(), 1);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        // Operations hold no map with non string keys, which is all
+        // that would make serializing fail
+        serde_json::to_string(&self.operations).unwrap()
+    }
+
+    /// Pipeline read from a JSON plan written by [`Self::to_json`]
+    #[cfg(feature = "serde")]
+    pub fn from_json(plan: &str) -> Result<Self> {
+        Ok(Self {
+            operations: serde_json::from_str(plan)?,
+        })
+    }
+}
+
+/// Data Frame recording the operations applied to it, so that they can
+/// be undone, redone, and replayed onto another Data Frame.
+///
+/// Undoing rebuilds the current Data Frame from the original one by
+/// applying the operations left, so only the original and the current
+/// Data Frames are kept in memory.
+///
+/// ```
+/// use nn::cell::CellType;
+/// use nn::dframe::{CastPolicy, Operation, VersionedFrame};
+/// use nn::parser::CsvParser;
+///
+/// let parsed = CsvParser::parse_buffer(b"id,qty\n1,5\n2,x\n", 1);
+/// let mut session = VersionedFrame::new(parsed);
+///
+/// session
+///     .apply(Operation::Cast {
+///         column: "qty".to_owned(),
+///         to: CellType::I64,
+///         policy: CastPolicy::NullOnError,
+///     })
+///     .unwrap();
+/// session.apply(Operation::DropNullRows(None)).unwrap();
+/// assert_eq!(session.len(), 1);
+///
+/// session.undo().unwrap();
+/// assert_eq!(session.len(), 2);
+/// session.redo().unwrap();
+/// assert_eq!(session.len(), 1);
+///
+/// let next_month = CsvParser::parse_buffer(b"id,qty\n3,?\n4,1\n5,2\n", 1);
+/// assert_eq!(session.replay(next_month).unwrap().len(), 2);
+/// ```
+pub struct VersionedFrame {
+    /// Data Frame before any operation
+    original: DataFrame,
+    /// Data Frame after the operations applied
+    current: DataFrame,
+    /// Operations recorded, the ones past `applied` were undone
+    operations: Vec<Operation>,
+    /// Total operations applied to `current`
+    applied: usize,
+}
+
+impl VersionedFrame {
+    /// Start recording the operations applied to `df`
+    pub fn new(df: DataFrame) -> Self {
+        Self {
+            current: df.thaw().into_frame(),
+            original: df,
+            operations: Vec::new(),
+            applied: 0,
+        }
+    }
+
+    /// Apply `operation` and record it, dropping the operations undone
+    /// before. Nothing is recorded if it fails.
+    pub fn apply(&mut self, operation: Operation) -> Result<()> {
+        operation.apply(&mut self.current)?;
+        self.operations.truncate(self.applied);
+        self.operations.push(operation);
+        self.applied += 1;
+        Ok(())
+    }
+
+    /// Undo the last operation applied, returning `false` if there is
+    /// none
+    pub fn undo(&mut self) -> Result<bool> {
+        if self.applied == 0 {
+            return Ok(false);
+        }
+
+        let operations = self.operations[..self.applied - 1].to_vec();
+        self.current =
+            Pipeline::new(operations).run(self.original.thaw().into_frame())?;
+        self.applied -= 1;
+        Ok(true)
+    }
+
+    /// Apply again the last operation undone, returning `false` if
+    /// there is none
+    pub fn redo(&mut self) -> Result<bool> {
+        let Some(operation) = self.operations.get(self.applied) else {
+            return Ok(false);
+        };
+
+        operation.apply(&mut self.current)?;
+        self.applied += 1;
+        Ok(true)
+    }
+
+    /// Operations applied to the current Data Frame, in order
+    #[inline]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations[..self.applied]
+    }
+
+    /// Apply the operations of the current Data Frame, in order, to
+    /// `df`
+    #[inline]
+    pub fn replay(&self, df: DataFrame) -> Result<DataFrame> {
+        self.pipeline().run(df)
+    }
+
+    /// Pipeline applying the operations of the current Data Frame, to
+    /// run them on other Data Frames or export them as a plan
+    #[inline]
+    pub fn pipeline(&self) -> Pipeline {
+        Pipeline::new(self.operations().to_vec())
+    }
+
+    /// Current Data Frame, dropping the recorded operations
+    #[inline]
+    pub fn into_frame(self) -> DataFrame {
+        self.current
+    }
+}
+
+impl std::ops::Deref for VersionedFrame {
+    type Target = DataFrame;
+
+    #[inline(always)]
+    fn deref(&self) -> &DataFrame {
+        &self.current
+    }
+}
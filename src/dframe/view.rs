@@ -0,0 +1,352 @@
+use std::ops::Range;
+
+use crate::cell::{Cell, CellType};
+use crate::dframe::{Column, ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+use crate::iter::dframe_iter::DataFrameIterator;
+use crate::iter::DataFrameColumnIterator;
+
+/// Borrowed view over some columns of a `DataFrame`, created by
+/// [`DataFrame::view`]. Nothing is copied: the view reads the cells of
+/// the frame through the indices of its columns.
+#[derive(Clone)]
+pub struct ColumnsView<'a> {
+    /// Viewed Data Frame
+    df: &'a DataFrame,
+    /// Index of each column of the view in the Data Frame
+    columns: Vec<usize>,
+}
+
+/// Borrowed view over a range of rows of a `DataFrame`, created by
+/// [`DataFrame::view_rows`] and [`DataFrame::page`]. Nothing is copied:
+/// rows are read from the cells of the frame.
+#[derive(Clone)]
+pub struct DataFrameView<'a> {
+    /// Viewed Data Frame
+    df: &'a DataFrame,
+    /// Rows of the Data Frame in the view
+    rows: Range<usize>,
+}
+
+/// Cells of a row within the columns of a [`ColumnsView`]
+#[derive(Clone, Copy)]
+pub struct RowView<'a> {
+    /// Cells of the whole row
+    row: &'a [Cell],
+    /// Index of each column of the view in the row
+    columns: &'a [usize],
+}
+
+impl DataFrame {
+    /// View over the columns `cols`, in the order of `cols`, without
+    /// copying them like [`Self::select`] does.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"a,b,c\n1,x,2.5\n2,y,\n", 1);
+    /// let view = df.view(&["c", "a"]).unwrap();
+    /// assert_eq!(view.header().collect::<Vec<_>>(), ["c", "a"]);
+    ///
+    /// let first = view.iter().next().unwrap();
+    /// assert!(matches!(first[1], Cell::Number(1)));
+    /// assert!(view.column("b").is_none());
+    /// ```
+    pub fn view<C: ColumnRef>(&self, cols: &[C]) -> Result<ColumnsView<'_>> {
+        let mut columns = Vec::with_capacity(cols.len());
+        for col in cols {
+            let index = self.column_index(col)?;
+            if columns.contains(&index) {
+                return Err(Error::DuplicateColumn(col.describe()));
+            }
+            columns.push(index);
+        }
+
+        Ok(ColumnsView { df: self, columns })
+    }
+
+    /// View over the rows in `range`, clamped to the rows of the Data
+    /// Frame, without copying them like [`Self::slice`] does
+    #[inline]
+    pub fn view_rows(&self, range: Range<usize>) -> DataFrameView<'_> {
+        let start = range.start.min(self.len());
+        let end = range.end.clamp(start, self.len());
+
+        DataFrameView {
+            df: self,
+            rows: start..end,
+        }
+    }
+
+    /// View over page `page_no` (starting from `0`) of the rows, cut in
+    /// pages of `page_size` rows. Pages past the last one are empty.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"id\n1\n2\n3\n4\n5\n", 1);
+    /// assert_eq!(df.total_pages(2), 3);
+    ///
+    /// let last = df.page(2, 2);
+    /// assert_eq!((last.offset(), last.len()), (4, 1));
+    /// assert!(matches!(last.get(0, "id"), Some(Cell::Number(5))));
+    /// assert!(df.page(3, 2).is_empty());
+    /// ```
+    #[inline]
+    pub fn page(&self, page_no: usize, page_size: usize) -> DataFrameView<'_> {
+        let start = page_no.saturating_mul(page_size);
+        self.view_rows(start..start.saturating_add(page_size))
+    }
+
+    /// Total pages of `page_size` rows needed to hold every row, `0` if
+    /// `page_size` is `0`
+    #[inline]
+    pub fn total_pages(&self, page_size: usize) -> usize {
+        self.len().checked_div(page_size).map_or(0, |full| {
+            full + usize::from(!self.len().is_multiple_of(page_size))
+        })
+    }
+}
+
+impl<'a> ColumnsView<'a> {
+    /// Index in the Data Frame of column `col` of the view
+    #[inline]
+    fn resolve<C: ColumnRef>(&self, col: C) -> Option<usize> {
+        col.resolve(self.df.header())
+            .filter(|index| self.columns.contains(index))
+    }
+
+    /// Names of the columns of the view
+    #[inline]
+    pub fn header(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let header = self.df.header();
+        self.columns
+            .iter()
+            .map(move |index| header[*index].as_str())
+    }
+
+    /// Types of the columns of the view
+    #[inline]
+    pub fn dtypes(&self) -> impl Iterator<Item = CellType> + '_ {
+        let dtype = self.df.dtypes();
+        self.columns.iter().map(move |index| dtype[*index])
+    }
+
+    /// Total columns of the view
+    #[inline(always)]
+    pub fn hlen(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Total rows of the view
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.df.len()
+    }
+
+    /// Returns `true` if the view holds no rows
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.df.is_empty()
+    }
+
+    /// Cells of row `index` within the view, `None` if out of bounds
+    #[inline]
+    pub fn row(&self, index: usize) -> Option<RowView<'_>> {
+        Some(RowView {
+            row: self.df.row(index)?,
+            columns: &self.columns,
+        })
+    }
+
+    /// Cell of row `index` in column `col`, `None` if the row is out
+    /// of bounds or the column is not in the view
+    #[inline]
+    pub fn get<C: ColumnRef>(&self, index: usize, col: C) -> Option<&'a Cell> {
+        self.df.get_at(index, self.resolve(col)?)
+    }
+
+    /// Iterator over the rows of the view
+    #[inline]
+    pub fn iter(&self) -> ColumnsViewIterator<'_> {
+        ColumnsViewIterator {
+            rows: self.df.iter(),
+            columns: &self.columns,
+        }
+    }
+
+    /// Handle on column `col`, `None` if it is not in the view
+    #[inline]
+    pub fn column<C: ColumnRef>(&self, col: C) -> Option<Column<'a>> {
+        self.df.column(ColumnIndex(self.resolve(col)?))
+    }
+
+    /// Column iterator for column `col`, `None` if it is not in the view
+    #[inline]
+    pub fn iter_col<C: ColumnRef>(
+        &self,
+        col: C,
+    ) -> Option<DataFrameColumnIterator<'a>> {
+        self.df.iter_col(ColumnIndex(self.resolve(col)?))
+    }
+
+    /// New Data Frame holding a copy of the columns of the view
+    #[inline]
+    pub fn to_frame(&self) -> DataFrame {
+        self.df.select_indices(&self.columns)
+    }
+}
+
+impl<'a> DataFrameView<'a> {
+    /// Names of the columns
+    #[inline(always)]
+    pub fn header(&self) -> &'a [String] {
+        self.df.header()
+    }
+
+    /// Types of the columns, inferred from the whole Data Frame
+    #[inline(always)]
+    pub fn dtypes(&self) -> &'a [CellType] {
+        self.df.dtypes()
+    }
+
+    /// Total columns of the view
+    #[inline(always)]
+    pub fn hlen(&self) -> usize {
+        self.df.hlen()
+    }
+
+    /// Total rows of the view
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the view holds no rows
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Row of the Data Frame at the start of the view
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        self.rows.start
+    }
+
+    /// Total rows of the viewed Data Frame
+    #[inline(always)]
+    pub fn total_len(&self) -> usize {
+        self.df.len()
+    }
+
+    /// Cells of row `index` of the view, `None` if out of bounds
+    #[inline]
+    pub fn row(&self, index: usize) -> Option<&'a [Cell]> {
+        if index >= self.len() {
+            return None;
+        }
+        self.df.row(self.rows.start + index)
+    }
+
+    /// Cell of row `index` of the view in column `col`, `None` if out
+    /// of bounds
+    #[inline]
+    pub fn get<C: ColumnRef>(&self, index: usize, col: C) -> Option<&'a Cell> {
+        self.row(index)?.get(col.resolve(self.df.header())?)
+    }
+
+    /// Iterator over the rows of the view
+    #[inline]
+    pub fn iter(&self) -> DataFrameIterator<'a> {
+        let width = self.df.hlen();
+        DataFrameIterator::new(
+            &self.df.cells()[self.rows.start * width..self.rows.end * width],
+            width,
+        )
+    }
+
+    /// New Data Frame holding a copy of the rows of the view
+    #[inline]
+    pub fn to_frame(&self) -> DataFrame {
+        self.df.slice(self.rows.clone())
+    }
+}
+
+/// Column already resolved to its index
+struct ColumnIndex(usize);
+
+impl ColumnRef for ColumnIndex {
+    #[inline(always)]
+    fn resolve(&self, _: &[String]) -> Option<usize> {
+        Some(self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("#{}", self.0)
+    }
+}
+
+impl<'a> RowView<'a> {
+    /// Cell of column `index` of the view
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'a Cell> {
+        self.row.get(*self.columns.get(index)?)
+    }
+
+    /// Total cells of the row
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `true` if the view has no columns
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Cells of the row, in the order of the columns of the view
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &'a Cell> + 'a {
+        let (row, columns) = (self.row, self.columns);
+        columns.iter().map(move |index| &row[*index])
+    }
+}
+
+impl std::ops::Index<usize> for RowView<'_> {
+    type Output = Cell;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Cell {
+        &self.row[self.columns[index]]
+    }
+}
+
+/// Iterator over the rows of a [`ColumnsView`]
+pub struct ColumnsViewIterator<'a> {
+    /// Rows of the Data Frame
+    rows: DataFrameIterator<'a>,
+    /// Index of each column of the view
+    columns: &'a [usize],
+}
+
+impl<'a> Iterator for ColumnsViewIterator<'a> {
+    type Item = RowView<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        Some(RowView {
+            row,
+            columns: self.columns,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
@@ -0,0 +1,180 @@
+use crate::cell::Cell;
+use crate::dframe::{DataFrame, Expr, Series};
+use crate::error::{Error, Result};
+
+/// Value given by a branch of a [`Conditional`]
+#[derive(Clone, Debug)]
+enum Branch {
+    /// Same cell on every row
+    Cell(Cell),
+    /// Cell computed from the row
+    Expr(Expr),
+}
+
+/// Condition waiting for its value, created by [`when`] and
+/// [`Conditional::when`]
+#[derive(Clone, Debug)]
+#[must_use = "a condition does nothing without `then`"]
+pub struct When {
+    /// Branches before the condition
+    branches: Vec<(Expr, Branch)>,
+    /// Condition of the next branch
+    condition: Expr,
+}
+
+/// Column built from conditions on other columns, see [`when`].
+#[derive(Clone, Debug)]
+pub struct Conditional {
+    /// Condition and value of each branch, in order
+    branches: Vec<(Expr, Branch)>,
+    /// Value of the rows matching no condition
+    otherwise: Branch,
+}
+
+/// Start a conditional column: rows passing `condition`, written in the
+/// syntax of [`Expr`], get the value of the following `then`.
+///
+/// Rows take the value of the first condition they pass, and the value
+/// of `otherwise` (null by default) if they pass none.
+///
+/// ```
+/// use nn::cell::{Cell, CellType};
+/// use nn::dframe::when;
+/// use nn::parser::CsvParser;
+///
+/// let mut df = CsvParser::parse_buffer(b"qty,price\n4,2.5\n,3\n-2,1\n30,1\n", 1);
+///
+/// let invalid = when("qty == null || qty < 0")
+///     .unwrap()
+///     .then(Cell::Bool(true))
+///     .otherwise(Cell::Bool(false))
+///     .evaluate(&df, "invalid")
+///     .unwrap();
+/// assert_eq!(invalid.dtype(), CellType::Bool);
+///
+/// let size = when("qty >= 10")
+///     .unwrap()
+///     .then(Cell::String("bulk".into()))
+///     .when("qty > 0")
+///     .unwrap()
+///     .then_expr("qty * price")
+///     .unwrap()
+///     .evaluate(&df, "size")
+///     .unwrap();
+/// assert_eq!(size.cells()[3].to_string(), "bulk");
+/// assert_eq!(size.cells()[0].to_string(), "10.0");
+/// assert!(matches!(size.cells()[1], Cell::Null));
+///
+/// df.add_series(invalid).unwrap();
+/// assert_eq!(df.header(), ["qty", "price", "invalid"]);
+/// ```
+pub fn when(condition: &str) -> Result<When> {
+    Ok(When::new(Vec::new(), Expr::parse(condition)?))
+}
+
+impl When {
+    #[inline(always)]
+    fn new(branches: Vec<(Expr, Branch)>, condition: Expr) -> Self {
+        Self {
+            branches,
+            condition,
+        }
+    }
+
+    /// Give `value` to the rows passing the condition
+    #[inline]
+    pub fn then(self, value: Cell) -> Conditional {
+        self.branch(Branch::Cell(value))
+    }
+
+    /// Give the cell computed by `expr`, written in the syntax of
+    /// [`Expr`], to the rows passing the condition
+    #[inline]
+    pub fn then_expr(self, expr: &str) -> Result<Conditional> {
+        Ok(self.branch(Branch::Expr(Expr::parse(expr)?)))
+    }
+
+    #[inline]
+    fn branch(mut self, value: Branch) -> Conditional {
+        self.branches.push((self.condition, value));
+        Conditional {
+            branches: self.branches,
+            otherwise: Branch::Cell(Cell::Null),
+        }
+    }
+}
+
+impl Conditional {
+    /// Add a branch for the rows passing `condition` and none of the
+    /// conditions before
+    #[inline]
+    pub fn when(self, condition: &str) -> Result<When> {
+        Ok(When::new(self.branches, Expr::parse(condition)?))
+    }
+
+    /// Give `value` to the rows passing no condition
+    #[inline]
+    pub fn otherwise(mut self, value: Cell) -> Self {
+        self.otherwise = Branch::Cell(value);
+        self
+    }
+
+    /// Give the cell computed by `expr` to the rows passing no condition
+    #[inline]
+    pub fn otherwise_expr(mut self, expr: &str) -> Result<Self> {
+        self.otherwise = Branch::Expr(Expr::parse(expr)?);
+        Ok(self)
+    }
+
+    /// Column `name` holding the value of each row of `df`.
+    ///
+    /// Fails if a column is not found or if a condition is not a
+    /// condition.
+    pub fn evaluate(&self, df: &DataFrame, name: &str) -> Result<Series> {
+        let bind = |branch: &Branch| match branch {
+            Branch::Cell(_) => Ok(Vec::new()),
+            Branch::Expr(expr) => expr.bind(df.header()),
+        };
+        let branches = self
+            .branches
+            .iter()
+            .map(|(condition, value)| {
+                if !condition.is_condition() {
+                    return Err(Error::InvalidQuery {
+                        position: 0,
+                        message: "expected a condition".to_owned(),
+                    });
+                }
+                Ok((
+                    condition,
+                    condition.bind(df.header())?,
+                    value,
+                    bind(value)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let otherwise = bind(&self.otherwise)?;
+
+        let dictionaries = df.dictionaries();
+        let value_of =
+            |row: &[Cell], branch: &Branch, indices: &[usize]| match branch {
+                Branch::Cell(cell) => cell.clone(),
+                Branch::Expr(expr) => expr.eval(row, indices, &dictionaries),
+            };
+        let cells = df
+            .iter()
+            .map(|row| {
+                match branches.iter().find(|(condition, indices, _, _)| {
+                    condition.test(row, indices, &dictionaries)
+                }) {
+                    Some((_, _, value, indices)) => {
+                        value_of(row, value, indices)
+                    }
+                    None => value_of(row, &self.otherwise, &otherwise),
+                }
+            })
+            .collect();
+
+        Ok(Series::new(name, cells))
+    }
+}
@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+use crate::cell::Cell;
+use crate::decimal::Decimal128;
+use crate::dframe::sort::compare_cells;
+use crate::dframe::Column;
+use crate::error::Result;
+
+/// Exact decimal value of an integer or exact decimal cell
+#[inline]
+fn exact(cell: &Cell) -> Option<Decimal128> {
+    match cell {
+        Cell::Number(number) => Some((*number).into()),
+        Cell::Decimal128(decimal) => Some(**decimal),
+        _ => None,
+    }
+}
+
+/// Sum of the numbers `left` and `right`, an integer unless one is a
+/// decimal or the sum overflows `i64`. Exact decimals stay exact unless
+/// the sum overflows their mantissa.
+#[inline]
+fn add(left: &Cell, right: &Cell) -> Option<Cell> {
+    Some(match (left, right) {
+        (Cell::Number(a), Cell::Number(b)) => a
+            .checked_add(*b)
+            .map_or(Cell::Decimal(*a as f64 + *b as f64), Cell::Number),
+        (Cell::Decimal128(_), _) | (_, Cell::Decimal128(_)) => {
+            let sum = exact(left)
+                .zip(exact(right))
+                .and_then(|(a, b)| a.checked_add(b));
+            match sum {
+                Some(sum) => Cell::from(sum),
+                None => Cell::Decimal(left.as_f64()? + right.as_f64()?),
+            }
+        }
+        _ => Cell::Decimal(left.as_f64()? + right.as_f64()?),
+    })
+}
+
+/// Difference of the numbers `left` and `right`, an integer unless one
+/// is a decimal or the difference overflows `i64`. Exact decimals stay
+/// exact unless the difference overflows their mantissa.
+#[inline]
+fn sub(left: &Cell, right: &Cell) -> Option<Cell> {
+    Some(match (left, right) {
+        (Cell::Number(a), Cell::Number(b)) => a
+            .checked_sub(*b)
+            .map_or(Cell::Decimal(*a as f64 - *b as f64), Cell::Number),
+        (Cell::Decimal128(_), _) | (_, Cell::Decimal128(_)) => {
+            let difference = exact(left)
+                .zip(exact(right))
+                .and_then(|(a, b)| a.checked_sub(b));
+            match difference {
+                Some(difference) => Cell::from(difference),
+                None => Cell::Decimal(left.as_f64()? - right.as_f64()?),
+            }
+        }
+        _ => Cell::Decimal(left.as_f64()? - right.as_f64()?),
+    })
+}
+
+impl Column<'_> {
+    /// Running value of `f` over the numbers of the column, null on
+    /// the rows holding a null
+    fn cumulative<F>(&self, mut f: F) -> Result<Vec<Cell>>
+    where
+        F: FnMut(&Cell, &Cell) -> Cell,
+    {
+        self.check_numeric()?;
+
+        let mut running: Option<Cell> = None;
+        Ok(self
+            .iter()
+            .map(|cell| {
+                if matches!(cell, Cell::Null) {
+                    return Cell::Null;
+                }
+                let next = match &running {
+                    Some(value) => f(value, cell),
+                    None => cell.clone(),
+                };
+                running = Some(next.clone());
+                next
+            })
+            .collect())
+    }
+
+    /// Value of `f` on each number and the one `periods` rows above,
+    /// null where either is null or there is no row above
+    fn with_previous<F>(&self, periods: usize, mut f: F) -> Result<Vec<Cell>>
+    where
+        F: FnMut(&Cell, &Cell) -> Option<Cell>,
+    {
+        self.check_numeric()?;
+
+        let cells: Vec<&Cell> = self.iter().collect();
+        Ok((0..cells.len())
+            .map(|index| {
+                let previous = cells.get(index.checked_sub(periods)?)?;
+                f(cells[index], previous)
+            })
+            .map(|cell| cell.unwrap_or(Cell::Null))
+            .collect())
+    }
+
+    /// Running sum of the numbers of the column, one cell per row.
+    ///
+    /// Like other cumulative and window functions, nulls give nulls
+    /// (and are skipped by the running value), integers stay integers
+    /// unless they overflow, and columns that are not numeric are
+    /// rejected. The result is aligned with the rows, ready for
+    /// [`crate::dframe::DataFrame::add_column`].
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let mut df = CsvParser::parse_buffer(b"day,sales\n1,10\n2,\n3,15\n4,12\n", 1);
+    /// let sales = df.column("sales").unwrap();
+    ///
+    /// let (total, best) = (sales.cumsum().unwrap(), sales.cummax().unwrap());
+    /// let (change, previous) = (sales.diff(1).unwrap(), sales.shift(1));
+    /// df.add_column("total", total).unwrap();
+    /// df.add_column("best", best).unwrap();
+    /// df.add_column("change", change).unwrap();
+    /// df.add_column("previous", previous).unwrap();
+    ///
+    /// let as_i64 = |col| df.column(col).unwrap().as_i64();
+    /// assert_eq!(as_i64("total"), [Some(10), None, Some(25), Some(37)]);
+    /// assert_eq!(as_i64("best"), [Some(10), None, Some(15), Some(15)]);
+    /// assert_eq!(as_i64("change"), [None, None, None, Some(-3)]);
+    /// assert_eq!(as_i64("previous"), [None, Some(10), None, Some(15)]);
+    /// ```
+    pub fn cumsum(&self) -> Result<Vec<Cell>> {
+        // Both cells are numbers
+        self.cumulative(|sum, cell| add(sum, cell).unwrap())
+    }
+
+    /// Running maximum of the numbers of the column, see
+    /// [`Self::cumsum`]
+    pub fn cummax(&self) -> Result<Vec<Cell>> {
+        self.cumulative(|max, cell| match compare_cells(cell, max, None) {
+            Ordering::Greater => cell.clone(),
+            _ => max.clone(),
+        })
+    }
+
+    /// Running minimum of the numbers of the column, see
+    /// [`Self::cumsum`]
+    pub fn cummin(&self) -> Result<Vec<Cell>> {
+        self.cumulative(|min, cell| match compare_cells(cell, min, None) {
+            Ordering::Less => cell.clone(),
+            _ => min.clone(),
+        })
+    }
+
+    /// Difference between each number and the one `periods` rows above,
+    /// see [`Self::cumsum`]
+    pub fn diff(&self, periods: usize) -> Result<Vec<Cell>> {
+        self.with_previous(periods, sub)
+    }
+
+    /// Relative change between each number and the one `periods` rows
+    /// above (`0.5` for a rise of 50%), as decimals, see
+    /// [`Self::cumsum`]
+    pub fn pct_change(&self, periods: usize) -> Result<Vec<Cell>> {
+        self.with_previous(periods, |cell, previous| {
+            let (value, previous) = (cell.as_f64()?, previous.as_f64()?);
+            Some(Cell::Decimal(value / previous - 1.0))
+        })
+    }
+
+    /// Cells of the column moved down by `periods` rows (or up, if
+    /// negative), with nulls in the rows left empty. Works on columns of
+    /// any type, categories are returned as the strings they stand for.
+    pub fn shift(&self, periods: isize) -> Vec<Cell> {
+        let cells = self.decoded();
+        (0..cells.len())
+            .map(|index| {
+                periods
+                    .checked_neg()
+                    .and_then(|up| index.checked_add_signed(up))
+                    .and_then(|source| cells.get(source))
+                    .map_or(Cell::Null, Cell::clone)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,301 @@
+/// Kind of departure from RFC 4180 found by [`validate`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Quote inside a field that does not start with a quote
+    BareQuote,
+    /// Quoted field still open at the end of the input
+    UnterminatedQuote,
+    /// Characters between the closing quote of a field and the next
+    /// separator or line end
+    TextAfterQuote,
+    /// Record with a different number of fields than the header
+    FieldCount {
+        /// Fields of the header
+        expected: usize,
+        /// Fields of the record
+        found: usize,
+    },
+    /// Carriage return not followed by a line feed, outside of quotes
+    BareCarriageReturn,
+    /// Line end different from the line end of the header (`\r\n` or
+    /// `\n`)
+    MixedLineEnding,
+}
+
+/// Issue found at a precise byte of the input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Offset of the byte in the input
+    pub offset: usize,
+    /// Line of the byte, starting from `1`
+    pub line: usize,
+    /// Column of the byte in its line, in bytes starting from `1`
+    pub column: usize,
+    /// What is wrong
+    pub kind: DiagnosticKind,
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BareQuote => write!(f, "quote in an unquoted field"),
+            Self::UnterminatedQuote => {
+                write!(f, "quoted field is never closed")
+            }
+            Self::TextAfterQuote => {
+                write!(f, "unexpected text after the closing quote")
+            }
+            Self::FieldCount { expected, found } => {
+                write!(f, "expected {} fields, found {}", expected, found)
+            }
+            Self::BareCarriageReturn => {
+                write!(f, "carriage return without line feed")
+            }
+            Self::MixedLineEnding => {
+                write!(f, "line ending differs from the header")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Diagnostic {
+    /// Render the diagnostic with the offending line of `source` (the
+    /// input that was checked) and a caret under the faulty byte.
+    ///
+    /// ```
+    /// use nn::diagnostic::validate;
+    ///
+    /// let source = b"id,name\n1,O\"Brien\n";
+    /// let diagnostic = validate(source)[0];
+    /// let rendered = diagnostic.render(source);
+    /// let lines: Vec<_> = rendered.lines().collect();
+    /// assert_eq!(lines[0], "error: quote in an unquoted field");
+    /// assert_eq!(lines[1], " --> line 2, column 4");
+    /// assert_eq!(lines[3], "2 | 1,O\"Brien");
+    /// assert_eq!(lines[4], "  |    ^");
+    /// ```
+    pub fn render(&self, source: &[u8]) -> String {
+        let offset = self.offset.min(source.len());
+        let start = offset + 1 - self.column.min(offset + 1);
+        let end = source[offset..]
+            .iter()
+            .position(|c| *c == b'\n')
+            .map_or(source.len(), |index| offset + index);
+        let line = String::from_utf8_lossy(&source[start..end]);
+        let line = line.trim_end_matches('\r');
+
+        // Align the caret on the characters before it, not the bytes
+        let before = String::from_utf8_lossy(&source[start..offset]);
+        let padding: String = before
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        format!(
+            "error: {}\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}^\n",
+            self.kind,
+            gutter,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            line,
+            gutter,
+            padding
+        )
+    }
+}
+
+/// Position in the field being scanned
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldState {
+    Start,
+    Unquoted,
+    /// Inside quotes opened at the offset
+    Quoted(usize),
+    AfterQuote,
+}
+
+/// Scanner tracking the position of each byte
+struct Validator<'a> {
+    buffer: &'a [u8],
+    diagnostics: Vec<Diagnostic>,
+    /// Line of the current byte
+    line: usize,
+    /// Offset of the first byte of the line
+    line_start: usize,
+    /// Fields of the header, once read
+    expected: Option<usize>,
+    /// Line end of the header, once read
+    ending: Option<&'static [u8]>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, offset: usize, kind: DiagnosticKind) {
+        // Quoted fields and records may start on a line above the
+        // current one
+        let (line, line_start) = if offset >= self.line_start {
+            (self.line, self.line_start)
+        } else {
+            let above = self.buffer[offset..self.line_start]
+                .iter()
+                .filter(|c| **c == b'\n')
+                .count();
+            let line_start = self.buffer[..offset]
+                .iter()
+                .rposition(|c| *c == b'\n')
+                .map_or(0, |index| index + 1);
+            (self.line - above, line_start)
+        };
+
+        self.diagnostics.push(Diagnostic {
+            offset,
+            line,
+            column: offset - line_start + 1,
+            kind,
+        });
+    }
+
+    /// End of a record of `fields` fields started at `start`
+    fn end_record(&mut self, start: usize, fields: usize) {
+        match self.expected {
+            None => self.expected = Some(fields),
+            Some(expected) if expected != fields => self.report(
+                start,
+                DiagnosticKind::FieldCount {
+                    expected,
+                    found: fields,
+                },
+            ),
+            _ => {}
+        }
+    }
+
+    /// Line end `ending` at `offset`
+    fn end_line(&mut self, offset: usize, ending: &'static [u8]) {
+        match self.ending {
+            None => self.ending = Some(ending),
+            Some(expected) if expected != ending => {
+                self.report(offset, DiagnosticKind::MixedLineEnding)
+            }
+            _ => {}
+        }
+        self.line += 1;
+        self.line_start = offset + ending.len();
+    }
+}
+
+/// Check `buffer` against RFC 4180: fields with quotes are enclosed in
+/// quotes (with inner quotes doubled), every record has as many fields
+/// as the header, and lines end the same way.
+///
+/// Returns the issues in the order of the input, empty for a valid
+/// input. Lines ending with `\n` only are accepted as long as all lines
+/// do.
+///
+/// ```
+/// use nn::diagnostic::{validate, DiagnosticKind};
+///
+/// let diagnostics = validate(b"id,name\r\n1,O\"Brien\r\n2\r\n");
+/// assert_eq!(diagnostics[0].kind, DiagnosticKind::BareQuote);
+/// assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 4));
+/// assert_eq!(
+///     diagnostics[1].kind,
+///     DiagnosticKind::FieldCount { expected: 2, found: 1 }
+/// );
+/// ```
+pub fn validate(buffer: &[u8]) -> Vec<Diagnostic> {
+    let start = if buffer.starts_with("\u{feff}".as_bytes()) {
+        3
+    } else {
+        0
+    };
+    let mut validator = Validator {
+        buffer,
+        diagnostics: Vec::new(),
+        line: 1,
+        line_start: start,
+        expected: None,
+        ending: None,
+    };
+
+    let (mut state, mut fields, mut record_start) =
+        (FieldState::Start, 1, start);
+    let mut offset = start;
+    while offset < buffer.len() {
+        let byte = buffer[offset];
+        match (state, byte) {
+            (FieldState::Quoted(_), b'"')
+                if buffer.get(offset + 1) == Some(&b'"') =>
+            {
+                offset += 1
+            }
+            (FieldState::Quoted(_), b'"') => state = FieldState::AfterQuote,
+            (FieldState::Quoted(_), b'\n') => {
+                validator.line += 1;
+                validator.line_start = offset + 1;
+            }
+            (FieldState::Quoted(_), _) => {}
+
+            (FieldState::Start, b'"') => state = FieldState::Quoted(offset),
+            (_, b',') => {
+                fields += 1;
+                state = FieldState::Start;
+            }
+            (_, b'\r' | b'\n') => {
+                validator.end_record(record_start, fields);
+
+                let ending: &'static [u8] = match (byte, buffer.get(offset + 1))
+                {
+                    (b'\r', Some(b'\n')) => b"\r\n",
+                    (b'\r', _) => {
+                        validator
+                            .report(offset, DiagnosticKind::BareCarriageReturn);
+                        b"\r"
+                    }
+                    _ => b"\n",
+                };
+                validator.end_line(offset, ending);
+                offset += ending.len() - 1;
+
+                (state, fields, record_start) =
+                    (FieldState::Start, 1, offset + 1);
+            }
+            (FieldState::Unquoted, b'"') => {
+                validator.report(offset, DiagnosticKind::BareQuote)
+            }
+            (FieldState::AfterQuote, _) => {
+                validator.report(offset, DiagnosticKind::TextAfterQuote);
+                state = FieldState::Unquoted;
+            }
+            (FieldState::Start, _) => state = FieldState::Unquoted,
+            (FieldState::Unquoted, _) => {}
+        }
+        offset += 1;
+    }
+
+    match state {
+        FieldState::Quoted(open) => {
+            validator.report(open, DiagnosticKind::UnterminatedQuote)
+        }
+        // The last line may not end with a line break
+        _ if record_start < buffer.len() => {
+            validator.end_record(record_start, fields)
+        }
+        _ => {}
+    }
+
+    validator.diagnostics
+}
@@ -0,0 +1,184 @@
+/// Text encoding of binary payloads stored in CSV fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BytesEncoding {
+    /// Standard base64 alphabet (`A-Z a-z 0-9 + /`), padding optional
+    #[default]
+    Base64,
+    /// Two hexadecimal digits per byte, in any case
+    Hex,
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Value of a base64 digit
+#[inline]
+fn base64_value(c: u8) -> Option<u32> {
+    Some(match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    } as u32)
+}
+
+impl BytesEncoding {
+    /// Decode `text`, `None` if it is not valid in this encoding
+    pub fn decode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Base64 => decode_base64(text.as_bytes()),
+            Self::Hex => decode_hex(text.as_bytes()),
+        }
+    }
+
+    /// Encode `bytes` as text
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Base64 => encode_base64(bytes),
+            Self::Hex => bytes
+                .iter()
+                .flat_map(|b| {
+                    [
+                        HEX_DIGITS[(b >> 4) as usize],
+                        HEX_DIGITS[(b & 15) as usize],
+                    ]
+                })
+                .map(char::from)
+                .collect(),
+        }
+    }
+}
+
+/// Decode pairs of hexadecimal digits
+fn decode_hex(text: &[u8]) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+
+    text.chunks_exact(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+/// Decode standard base64, with or without the trailing padding
+fn decode_base64(text: &[u8]) -> Option<Vec<u8>> {
+    let text = match text {
+        [rest @ .., b'=', b'='] | [rest @ .., b'=']
+            if text.len().is_multiple_of(4) =>
+        {
+            rest
+        }
+        _ => text,
+    };
+
+    // A single leftover digit does not hold a whole byte
+    if text.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    for group in text.chunks(4) {
+        let value = group
+            .iter()
+            .try_fold(0, |value, c| Some(value << 6 | base64_value(*c)?))?
+            << (6 * (4 - group.len()));
+
+        bytes.extend_from_slice(&value.to_be_bytes()[1..group.len()]);
+    }
+
+    Some(bytes)
+}
+
+/// Encode as standard base64 with padding
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for group in bytes.chunks(3) {
+        let value = group.iter().enumerate().fold(0, |value, (index, b)| {
+            value | u32::from(*b) << (16 - 8 * index)
+        });
+
+        (0..4).for_each(|index| {
+            if index <= group.len() {
+                let digit = (value >> (18 - 6 * index)) & 63;
+                text.push(BASE64_ALPHABET[digit as usize] as char);
+            } else {
+                text.push('=');
+            }
+        });
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytesEncoding::{Base64, Hex};
+
+    #[test]
+    fn base64_round_trips() {
+        for bytes in
+            [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"]
+        {
+            let text = Base64.encode(bytes);
+            assert_eq!(text.len() % 4, 0);
+            assert_eq!(Base64.decode(&text).as_deref(), Some(bytes));
+        }
+
+        let all: Vec<u8> = (0..=255).collect();
+        assert_eq!(Base64.decode(&Base64.encode(&all)), Some(all));
+    }
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(Base64.encode(b"f"), "Zg==");
+        assert_eq!(Base64.encode(b"fo"), "Zm8=");
+        assert_eq!(Base64.encode(b"foo"), "Zm9v");
+
+        // Padding is optional when decoding
+        assert_eq!(Base64.decode("Zg==").unwrap(), b"f");
+        assert_eq!(Base64.decode("Zg").unwrap(), b"f");
+        assert_eq!(Base64.decode("Zm8").unwrap(), b"fo");
+
+        // Padding only closes a whole group
+        assert_eq!(Base64.decode("Zg="), None);
+        assert_eq!(Base64.decode("Zm8=="), None);
+        assert_eq!(Base64.decode("Zg==Zg=="), None);
+        assert_eq!(Base64.decode("===="), None);
+    }
+
+    #[test]
+    fn invalid_base64() {
+        assert_eq!(Base64.decode("Z"), None);
+        assert_eq!(Base64.decode("Zm9vY"), None);
+        assert_eq!(Base64.decode("Zm9v!"), None);
+        assert_eq!(Base64.decode("Zm 9v"), None);
+        assert_eq!(Base64.decode("Zm-_"), None);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(Hex.encode(b""), "");
+        assert_eq!(Hex.encode(&[0x00, 0x7f, 0xab, 0xff]), "007fabff");
+
+        let all: Vec<u8> = (0..=255).collect();
+        assert_eq!(Hex.decode(&Hex.encode(&all)), Some(all));
+        assert_eq!(Hex.decode("DEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn invalid_hex() {
+        assert_eq!(Hex.decode("abc"), None);
+        assert_eq!(Hex.decode("0g"), None);
+        assert_eq!(Hex.decode("0x12"), None);
+        assert_eq!(Hex.decode("+1"), None);
+        assert_eq!(Hex.decode("é0"), None);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::cell::CellType;
+use crate::decompress::Compression;
+
+/// Errors reported while reading and working on datasets
+#[derive(Debug)]
+pub enum Error {
+    /// Failure while opening, mapping or decompressing a file
+    Io(std::io::Error),
+    /// Input is compressed with a format whose feature is not enabled
+    UnsupportedCompression(Compression),
+    /// Column with the given name does not exist
+    ColumnNotFound(String),
+    /// Column with the given name already exists
+    DuplicateColumn(String),
+    /// Position is past the end of the columns or rows
+    IndexOutOfBounds {
+        /// Position requested
+        index: usize,
+        /// Total columns or rows
+        len: usize,
+    },
+    /// Argument holds a value per row, but its length differs from
+    /// the total rows
+    LengthMismatch {
+        /// Total rows expected
+        expected: usize,
+        /// Length of the argument
+        found: usize,
+    },
+    /// Columns with the same name hold values of incompatible types
+    TypeMismatch {
+        /// Name of the column
+        column: String,
+        /// Type of the column
+        expected: CellType,
+        /// Type of the values added to the column
+        found: CellType,
+    },
+    /// Argument outside of the values accepted by the function, like a
+    /// batch size of zero
+    InvalidArgument(String),
+    /// Cell that cannot be converted to the requested type
+    InvalidCast {
+        /// Name of the column
+        column: String,
+        /// Index of the row
+        row: usize,
+        /// Requested type
+        to: CellType,
+    },
+    /// Column holding more distinct values than the codes of
+    /// categories can tell apart
+    TooManyCategories(String),
+    /// Query expression that cannot be parsed or evaluated
+    InvalidQuery {
+        /// Byte of the expression where the issue is found
+        position: usize,
+        /// What is wrong at the position
+        message: String,
+    },
+    /// Type hints file that cannot be read
+    InvalidTypeHints {
+        /// Line of the issue, starting from `1` (`0` if unknown)
+        line: usize,
+        /// What is wrong on the line
+        message: String,
+    },
+    /// Parse stopped by its [`crate::cancel::CancelToken`]
+    Cancelled {
+        /// Total rows read before stopping
+        rows: usize,
+    },
+    /// Record holding more or fewer fields than the header, rejected by
+    /// the [`RaggedRows`](crate::options::RaggedRows) policy of the
+    /// parse
+    #[cfg(feature = "parser")]
+    RaggedRow {
+        /// Row of the record among the parsed rows
+        row: usize,
+        /// Total fields expected
+        expected: usize,
+        /// Total fields of the record
+        found: usize,
+    },
+    /// Row could not be deserialized into the requested type
+    #[cfg(feature = "serde")]
+    Deserialize {
+        /// Index of the row
+        row: usize,
+        /// Reason reported by the deserializer
+        message: String,
+    },
+    /// JSON plan of a pipeline that cannot be read
+    #[cfg(feature = "serde")]
+    Plan(serde_json::Error),
+    /// Regular expression that does not compile
+    #[cfg(feature = "regex")]
+    Regex(regex::Error),
+    /// Failure while converting from or to arrow
+    #[cfg(feature = "arrow")]
+    Arrow(arrow::error::ArrowError),
+    /// Failure while reading or writing a Parquet file
+    #[cfg(feature = "io-parquet")]
+    Parquet(parquet::errors::ParquetError),
+}
+
+/// Result type used throughout the crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "io error: {}", err),
+            Self::UnsupportedCompression(compression) => write!(
+                f,
+                "{:?} compressed input requires the corresponding feature",
+                compression
+            ),
+            Self::ColumnNotFound(name) => {
+                write!(f, "column not found: {}", name)
+            }
+            Self::DuplicateColumn(name) => {
+                write!(f, "column already exists: {}", name)
+            }
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} out of bounds for length {}", index, len)
+            }
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "length mismatch: expected {} values, found {}",
+                expected, found
+            ),
+            Self::TypeMismatch {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "type mismatch in column {}: expected {}, found {}",
+                column, expected, found
+            ),
+            Self::InvalidArgument(message) => {
+                write!(f, "invalid argument: {}", message)
+            }
+            Self::InvalidCast { column, row, to } => write!(
+                f,
+                "cannot convert row {} of column {} to {}",
+                row, column, to
+            ),
+            Self::TooManyCategories(column) => write!(
+                f,
+                "column {} holds too many distinct values for categories",
+                column
+            ),
+            Self::InvalidQuery { position, message } => {
+                write!(f, "invalid query at byte {}: {}", position, message)
+            }
+            Self::InvalidTypeHints { line, message } => {
+                write!(f, "invalid type hints on line {}: {}", line, message)
+            }
+            Self::Cancelled { rows } => {
+                write!(f, "parse cancelled after {} rows", rows)
+            }
+            #[cfg(feature = "parser")]
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} fields, expected {}",
+                row, found, expected
+            ),
+            #[cfg(feature = "serde")]
+            Self::Deserialize { row, message } => {
+                write!(f, "cannot deserialize row {}: {}", row, message)
+            }
+            #[cfg(feature = "serde")]
+            Self::Plan(err) => write!(f, "invalid plan: {}", err),
+            #[cfg(feature = "regex")]
+            Self::Regex(err) => write!(f, "invalid pattern: {}", err),
+            #[cfg(feature = "arrow")]
+            Self::Arrow(err) => write!(f, "arrow error: {}", err),
+            #[cfg(feature = "io-parquet")]
+            Self::Parquet(err) => write!(f, "parquet error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            #[cfg(feature = "serde")]
+            Self::Plan(err) => Some(err),
+            #[cfg(feature = "regex")]
+            Self::Regex(err) => Some(err),
+            #[cfg(feature = "arrow")]
+            Self::Arrow(err) => Some(err),
+            #[cfg(feature = "io-parquet")]
+            Self::Parquet(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Plan(err)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<regex::Error> for Error {
+    fn from(err: regex::Error) -> Self {
+        Self::Regex(err)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<arrow::error::ArrowError> for Error {
+    fn from(err: arrow::error::ArrowError) -> Self {
+        Self::Arrow(err)
+    }
+}
+
+#[cfg(feature = "io-parquet")]
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Self::Parquet(err)
+    }
+}
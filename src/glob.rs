@@ -0,0 +1,147 @@
+//! Paths of the files matching a pattern of wildcards, read by
+//! [`CsvParser::parse_glob`].
+//!
+//! `*` stands for any run of characters and `?` for a single one,
+//! within a single component of the path: `data/*/2023-??.csv` matches
+//! `data/eu/2023-01.csv` but not `data/eu/old/2023-01.csv`. Hidden
+//! files only match patterns starting with a point.
+//!
+//! [`CsvParser::parse_glob`]: crate::parser::CsvParser::parse_glob
+
+use std::path::{Component, Path, PathBuf};
+
+/// Returns `true` if `component` holds a wildcard
+#[inline]
+fn has_wildcard(component: &str) -> bool {
+    component.contains(['*', '?'])
+}
+
+/// Returns `true` if `name` matches `pattern`
+fn matches(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) =
+        (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern, and of the character of
+    // the name it is tried against
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // Let the last `*` take one more character
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    (p, n) = (star_p + 1, star_n + 1);
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Entries of `dir` whose name matches `pattern`
+fn matching_entries(dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let read = if dir.as_os_str().is_empty() {
+        std::fs::read_dir(".")
+    } else {
+        std::fs::read_dir(dir)
+    };
+    // Directories that cannot be read hold no match
+    let Ok(entries) = read else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !name.starts_with('.') || pattern.starts_with('.'))
+        .filter(|name| matches(pattern, name))
+        .map(|name| dir.join(name))
+        .collect()
+}
+
+/// Files matching `pattern`, sorted by path
+pub(crate) fn expand(pattern: &str) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        match component {
+            Component::Normal(part) => match part.to_str() {
+                Some(part) if has_wildcard(part) => {
+                    paths = paths
+                        .iter()
+                        .flat_map(|dir| matching_entries(dir, part))
+                        .collect();
+                }
+                _ => paths.iter_mut().for_each(|path| path.push(part)),
+            },
+            other => paths.iter_mut().for_each(|path| path.push(other)),
+        }
+    }
+
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_backtrack() {
+        assert!(matches("*a*b", "xaxxb"));
+        assert!(matches("a*b*c", "abcbc"));
+        assert!(matches("a*a*a", "aaa"));
+        assert!(!matches("a*a*a", "aa"));
+        assert!(!matches("*.csv", "a.csv.bak"));
+        assert!(matches("*.csv*", "a.csv.bak"));
+        assert!(matches("2023-??.csv", "2023-01.csv"));
+        assert!(!matches("2023-??.csv", "2023-1.csv"));
+        assert!(matches("?", "é"));
+        assert!(matches("*", ""));
+        assert!(matches("", ""));
+        assert!(!matches("a*", ""));
+        assert!(!matches("*a*a*a*a*a*b", &"a".repeat(64)));
+    }
+
+    #[test]
+    fn hidden_files_need_a_leading_point() {
+        let dir = std::env::temp_dir()
+            .join(format!("nn_glob_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::create_dir_all(dir.join(".cache")).unwrap();
+        for file in ["a.csv", ".hidden.csv", "sub/b.csv", ".cache/c.csv"] {
+            std::fs::write(dir.join(file), b"x\n").unwrap();
+        }
+
+        let found = |pattern: &str| -> Vec<String> {
+            expand(dir.join(pattern).to_str().unwrap())
+                .iter()
+                .map(|path| {
+                    let path = path.strip_prefix(&dir).unwrap();
+                    path.to_str().unwrap().replace('\\', "/")
+                })
+                .collect()
+        };
+        let all = (found("*.csv"), found(".*.csv"), found("*/*.csv"));
+        let nested =
+            (found(".*/*.csv"), found(".c?che/*"), found("?hidden.csv"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(all.0, ["a.csv"]);
+        assert_eq!(all.1, [".hidden.csv"]);
+        assert_eq!(all.2, ["sub/b.csv"]);
+        assert_eq!(nested.0, [".cache/c.csv"]);
+        assert_eq!(nested.1, [".cache/c.csv"]);
+        assert!(nested.2.is_empty());
+    }
+}
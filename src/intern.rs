@@ -0,0 +1,102 @@
+//! Strings interned while parsing, shared by the parses of a pool.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Distinct strings read by the parses sharing the pool, each stored
+/// once and told apart by its code. Clones share the same strings.
+///
+/// Given to the parser with [`ParserOptions::string_pool`] (or created
+/// by [`ParserOptions::intern_strings`]), the pool is looked up as text
+/// fields are read, so that a value repeated over many rows is
+/// allocated once. String columns come out categorical, their
+/// dictionary holding the strings of the pool: files parsed with the
+/// same pool give the same codes to the same strings.
+///
+/// ```
+/// use nn::cell::CellType;
+/// use nn::intern::StringPool;
+/// use nn::options::ParserOptions;
+/// use nn::parser::CsvParser;
+///
+/// let pool = StringPool::new();
+/// let options = ParserOptions::new().string_pool(pool.clone());
+///
+/// let north = CsvParser::parse_buffer_with_options(b"region,units\nNorth,4\nNorth,7\n", 1, &options)
+///     .unwrap();
+/// let both = CsvParser::parse_buffer_with_options(b"region,units\nSouth,2\nNorth,1\n", 1, &options)
+///     .unwrap();
+/// assert_eq!(north.dtypes(), [CellType::Categorical, CellType::I64]);
+/// assert_eq!(both.categories("region").unwrap(), ["North", "South"]);
+/// assert_eq!(pool.len(), 2);
+/// ```
+///
+/// [`ParserOptions::string_pool`]: crate::options::ParserOptions::string_pool
+/// [`ParserOptions::intern_strings`]: crate::options::ParserOptions::intern_strings
+#[derive(Clone, Debug, Default)]
+pub struct StringPool(Arc<RwLock<Pool>>);
+
+/// Strings of a [`StringPool`]
+#[derive(Debug, Default)]
+struct Pool {
+    /// Code of each string
+    codes: HashMap<String, u32>,
+    /// String of each code
+    values: Vec<String>,
+    /// Strings handed over as a dictionary, while none was added since
+    dictionary: Option<Arc<[String]>>,
+}
+
+impl StringPool {
+    /// Pool holding no string yet
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total distinct strings in the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.read().unwrap().values.len()
+    }
+
+    /// Returns `true` if no string was interned yet
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Code of `text`, added to the pool if it is not in yet. `None`
+    /// once the pool holds more strings than a `u32` code can tell
+    /// apart.
+    pub(crate) fn intern(&self, text: &str) -> Option<u32> {
+        if let Some(code) = self.0.read().unwrap().codes.get(text) {
+            return Some(*code);
+        }
+
+        let mut pool = self.0.write().unwrap();
+        // Another thread may have added it in between
+        if let Some(code) = pool.codes.get(text) {
+            return Some(*code);
+        }
+        let code = u32::try_from(pool.values.len()).ok()?;
+        pool.codes.insert(text.to_owned(), code);
+        pool.values.push(text.to_owned());
+        pool.dictionary = None;
+        Some(code)
+    }
+
+    /// Strings of the pool by code, as a dictionary of categories.
+    /// Strings added later only extend it, so earlier dictionaries are
+    /// prefixes of later ones.
+    pub(crate) fn dictionary(&self) -> Arc<[String]> {
+        if let Some(dictionary) = &self.0.read().unwrap().dictionary {
+            return Arc::clone(dictionary);
+        }
+
+        let mut pool = self.0.write().unwrap();
+        let dictionary: Arc<[String]> = pool.values.as_slice().into();
+        pool.dictionary = Some(Arc::clone(&dictionary));
+        dictionary
+    }
+}
@@ -0,0 +1,455 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, DictionaryArray,
+    DurationNanosecondArray, Float64Array, Int64Array, NullArray, StringArray,
+    Time64NanosecondArray, UInt32Array, UInt64Array,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit, UInt32Type};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+use crate::cell::{Cell, CellType};
+use crate::datetime::{Date, Duration, Time};
+use crate::decimal::Decimal128;
+use crate::dframe::DataFrame;
+use crate::error::Result;
+
+/// Arrow type of a column holding `cell_type` values
+pub fn arrow_type(cell_type: CellType) -> DataType {
+    match cell_type {
+        CellType::String => DataType::Utf8,
+        CellType::I64 => DataType::Int64,
+        CellType::U64 => DataType::UInt64,
+        CellType::F64 => DataType::Float64,
+        // Scales vary from cell to cell, decimals are written as text
+        CellType::Decimal128 => DataType::Utf8,
+        CellType::Null => DataType::Null,
+        CellType::Date => DataType::Date32,
+        CellType::Time => DataType::Time64(TimeUnit::Nanosecond),
+        CellType::Duration => DataType::Duration(TimeUnit::Nanosecond),
+        CellType::Bytes => DataType::Binary,
+        CellType::Bool => DataType::Boolean,
+        CellType::Categorical => DataType::Dictionary(
+            Box::new(DataType::UInt32),
+            Box::new(DataType::Utf8),
+        ),
+    }
+}
+
+/// Cell type read from an arrow column of `data_type`, `None` if the
+/// type has no counterpart.
+///
+/// Narrower integers, floats and temporal units are widened to the
+/// type of the cell.
+pub fn cell_type(data_type: &DataType) -> Option<CellType> {
+    Some(match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 => CellType::String,
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32 => CellType::I64,
+        DataType::UInt64 => CellType::U64,
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+            CellType::F64
+        }
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            CellType::Decimal128
+        }
+        DataType::Null => CellType::Null,
+        DataType::Date32 | DataType::Date64 => CellType::Date,
+        DataType::Time32(_) | DataType::Time64(_) => CellType::Time,
+        DataType::Duration(_) => CellType::Duration,
+        DataType::Binary | DataType::LargeBinary => CellType::Bytes,
+        DataType::Boolean => CellType::Bool,
+        DataType::Dictionary(_, values)
+            if matches!(**values, DataType::Utf8 | DataType::LargeUtf8) =>
+        {
+            CellType::Categorical
+        }
+        _ => return None,
+    })
+}
+
+/// Text of `cell` written in a text column, `None` for nulls. Cells of
+/// other types than strings, which the parser keeps in string columns
+/// (`42` among codes like `A1`), are written as they display.
+#[inline]
+fn cell_text(cell: &Cell) -> Option<Cow<'_, str>> {
+    match cell {
+        Cell::Null => None,
+        Cell::String(string) => Some(Cow::Borrowed(string)),
+        cell => Some(Cow::Owned(cell.to_string())),
+    }
+}
+
+/// Returns `true` if a column of `cell_type` written to arrow keeps the
+/// value of `cell`
+#[inline]
+fn fits(cell_type: CellType, cell: &Cell) -> bool {
+    match (cell_type, cell) {
+        // Text columns write any cell as it displays
+        (CellType::String | CellType::Decimal128, _) => true,
+        (_, Cell::Null) => true,
+        (CellType::U64, Cell::Number(number)) => *number >= 0,
+        (CellType::F64, Cell::Number(_) | Cell::UInt(_)) => true,
+        (cell_type, cell) => cell.cell_type() == cell_type,
+    }
+}
+
+/// Downcast `array` to the concrete array `A`. The array is first cast
+/// to the arrow type of the cell, so this cannot fail.
+#[inline]
+fn downcast<A: 'static>(array: &ArrayRef) -> &A {
+    array.as_any().downcast_ref::<A>().unwrap()
+}
+
+impl DataFrame {
+    /// Arrow array of column `index`, with nulls in the validity bitmap.
+    /// Fails if a cell does not fit the arrow type of the column, rather
+    /// than writing it as a null.
+    fn arrow_column(&self, index: usize) -> Result<ArrayRef> {
+        let dtype = self.dtypes()[index];
+        let mismatch = self
+            .iter()
+            .enumerate()
+            .find(|(_, row)| !fits(dtype, &row[index]));
+        if let Some((row, cells)) = mismatch {
+            return Err(ArrowError::CastError(format!(
+                "column {} of type {} holds {} at row {}",
+                self.header()[index],
+                dtype,
+                cells[index].cell_type(),
+                row
+            ))
+            .into());
+        }
+
+        let cells = self.iter().map(|row| &row[index]);
+        Ok(match dtype {
+            CellType::String => {
+                Arc::new(cells.map(cell_text).collect::<StringArray>())
+            }
+            CellType::I64 => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Number(number) => Some(*number),
+                        _ => None,
+                    })
+                    .collect::<Int64Array>(),
+            ),
+            CellType::U64 => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::UInt(number) => Some(*number),
+                        Cell::Number(number) => u64::try_from(*number).ok(),
+                        _ => None,
+                    })
+                    .collect::<UInt64Array>(),
+            ),
+            CellType::F64 => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Decimal(decimal) => Some(*decimal),
+                        Cell::Number(number) => Some(*number as f64),
+                        Cell::UInt(number) => Some(*number as f64),
+                        _ => None,
+                    })
+                    .collect::<Float64Array>(),
+            ),
+            CellType::Decimal128 => {
+                Arc::new(cells.map(cell_text).collect::<StringArray>())
+            }
+            CellType::Null => Arc::new(NullArray::new(self.len())),
+            CellType::Date => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Date(date) => Some(date.days_since_epoch()),
+                        _ => None,
+                    })
+                    .collect::<Date32Array>(),
+            ),
+            CellType::Time => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Time(time) => {
+                            Some(time.nanos_since_midnight() as i64)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Time64NanosecondArray>(),
+            ),
+            CellType::Duration => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Duration(duration) => Some(duration.as_nanos()),
+                        _ => None,
+                    })
+                    .collect::<DurationNanosecondArray>(),
+            ),
+            CellType::Bytes => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Bytes(bytes) => Some(bytes.as_slice()),
+                        _ => None,
+                    })
+                    .collect::<BinaryArray>(),
+            ),
+            CellType::Bool => Arc::new(
+                cells
+                    .map(|cell| match cell {
+                        Cell::Bool(value) => Some(*value),
+                        _ => None,
+                    })
+                    .collect::<BooleanArray>(),
+            ),
+            CellType::Categorical => {
+                let keys = cells
+                    .map(|cell| match cell {
+                        Cell::Categorical(code) => Some(*code),
+                        _ => None,
+                    })
+                    .collect::<UInt32Array>();
+                let values = self
+                    .dictionary(index)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<StringArray>();
+                Arc::new(DictionaryArray::<UInt32Type>::try_new(
+                    keys,
+                    Arc::new(values),
+                )?)
+            }
+        })
+    }
+
+    /// Convert the Data Frame into an arrow `RecordBatch`, with one
+    /// nullable field per column typed by [`arrow_type`].
+    pub fn to_arrow(&self) -> Result<RecordBatch> {
+        let fields: Vec<Field> = self
+            .header()
+            .iter()
+            .zip(self.dtypes())
+            .map(|(name, cell_type)| {
+                Field::new(name, arrow_type(*cell_type), true)
+            })
+            .collect();
+
+        let columns = (0..self.hlen())
+            .map(|index| self.arrow_column(index))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(RecordBatch::try_new(
+            Arc::new(Schema::new(fields)),
+            columns,
+        )?)
+    }
+
+    /// Build a Data Frame from an arrow `RecordBatch`.
+    ///
+    /// Columns are converted as described by [`cell_type`], values
+    /// marked invalid in the validity bitmap become `Cell::Null`.
+    /// Columns of any other arrow type are rejected.
+    pub fn from_arrow(batch: &RecordBatch) -> Result<Self> {
+        let schema = batch.schema();
+        let (rows, width) = (batch.num_rows(), batch.num_columns());
+
+        let dtype = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                cell_type(field.data_type()).ok_or_else(|| {
+                    ArrowError::CastError(format!(
+                        "column {} of type {} is not supported",
+                        field.name(),
+                        field.data_type()
+                    ))
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut cells = vec![Cell::Null; rows * width];
+        let mut dictionaries = Vec::new();
+        for (col, cell_type) in dtype.iter().enumerate() {
+            let array = cast(batch.column(col), &arrow_type(*cell_type))?;
+
+            let value: Box<dyn Fn(usize) -> Cell + '_> = match cell_type {
+                CellType::String => {
+                    let array = downcast::<StringArray>(&array);
+                    Box::new(move |row| {
+                        Cell::String(array.value(row).to_owned())
+                    })
+                }
+                CellType::I64 => {
+                    let array = downcast::<Int64Array>(&array);
+                    Box::new(move |row| Cell::Number(array.value(row)))
+                }
+                CellType::U64 => {
+                    let array = downcast::<UInt64Array>(&array);
+                    Box::new(move |row| Cell::UInt(array.value(row)))
+                }
+                CellType::F64 => {
+                    let array = downcast::<Float64Array>(&array);
+                    Box::new(move |row| Cell::Decimal(array.value(row)))
+                }
+                CellType::Decimal128 => {
+                    let array = downcast::<StringArray>(&array);
+                    Box::new(move |row| {
+                        Decimal128::parse(array.value(row))
+                            .map_or(Cell::Null, Cell::from)
+                    })
+                }
+                CellType::Null => Box::new(move |_| Cell::Null),
+                CellType::Date => {
+                    let array = downcast::<Date32Array>(&array);
+                    Box::new(move |row| {
+                        Cell::Date(Date::from_days_since_epoch(
+                            array.value(row),
+                        ))
+                    })
+                }
+                CellType::Time => {
+                    let array = downcast::<Time64NanosecondArray>(&array);
+                    Box::new(move |row| {
+                        let nanos = array.value(row) as u64;
+                        Cell::Time(Time::from_nanos_since_midnight(nanos))
+                    })
+                }
+                CellType::Duration => {
+                    let array = downcast::<DurationNanosecondArray>(&array);
+                    Box::new(move |row| {
+                        Cell::Duration(Duration::from_nanos(array.value(row)))
+                    })
+                }
+                CellType::Bytes => {
+                    let array = downcast::<BinaryArray>(&array);
+                    Box::new(move |row| Cell::Bytes(array.value(row).to_vec()))
+                }
+                CellType::Bool => {
+                    let array = downcast::<BooleanArray>(&array);
+                    Box::new(move |row| Cell::Bool(array.value(row)))
+                }
+                CellType::Categorical => {
+                    let array = downcast::<DictionaryArray<UInt32Type>>(&array);
+                    let values = downcast::<StringArray>(array.values());
+                    let dictionary: Arc<[String]> = (0..values.len())
+                        .map(|index| values.value(index).to_owned())
+                        .collect();
+                    dictionaries.push((col, dictionary));
+                    Box::new(move |row| {
+                        Cell::Categorical(array.keys().value(row))
+                    })
+                }
+            };
+
+            (0..rows)
+                .filter(|row| array.is_valid(*row))
+                .for_each(|row| {
+                    cells[row * width + col] = value(row);
+                });
+        }
+
+        let header = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+
+        let mut df = Self::from_cells(cells, header, &dtype);
+        for (col, dictionary) in dictionaries {
+            let name = df.header[col].clone();
+            df.set_dictionary(&name, dictionary);
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(all(test, feature = "parser"))]
+mod tests {
+    use super::*;
+    use crate::parser::CsvParser;
+
+    /// Text of each cell of column `index`
+    fn texts(df: &DataFrame, index: usize) -> Vec<String> {
+        let dictionary = df.dictionary(index);
+        df.iter()
+            .map(|row| row[index].decoded(dictionary).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn string_column_keeps_numbers() {
+        let df = CsvParser::parse_buffer(b"code\nA1\n42\n\n7.5\n", 1);
+        assert_eq!(df.dtypes(), [CellType::String]);
+
+        let batch = df.to_arrow().unwrap();
+        let codes = downcast::<StringArray>(batch.column(0));
+        assert_eq!(codes.len(), 4);
+        assert_eq!(codes.value(0), "A1");
+        assert_eq!(codes.value(1), "42");
+        assert!(codes.is_null(2));
+        assert_eq!(codes.value(3), "7.5");
+    }
+
+    #[test]
+    fn categorical_column_keeps_numbers() {
+        let mut df = CsvParser::parse_buffer(b"code\nA1\n42\nA1\n", 1);
+        df.to_categorical("code").unwrap();
+
+        let back = DataFrame::from_arrow(&df.to_arrow().unwrap()).unwrap();
+        assert_eq!(back.dtypes(), [CellType::Categorical]);
+        assert_eq!(texts(&back, 0), ["A1", "42", "A1"]);
+    }
+
+    #[test]
+    fn exact_decimals_are_written_as_text() {
+        let df = DataFrame::from_cells(
+            vec![Cell::from(Decimal128::parse("0.10").unwrap())],
+            vec!["price".to_owned()],
+            &[CellType::Decimal128],
+        );
+        let batch = df.to_arrow().unwrap();
+        assert_eq!(downcast::<StringArray>(batch.column(0)).value(0), "0.10");
+    }
+
+    #[test]
+    fn mismatched_cell_is_an_error() {
+        let df = DataFrame::from_cells(
+            vec![Cell::Number(1), Cell::Bool(true)],
+            vec!["n".to_owned()],
+            &[CellType::I64],
+        );
+        assert!(df.to_arrow().is_err());
+
+        let df = DataFrame::from_cells(
+            vec![Cell::UInt(u64::MAX), Cell::Number(-1)],
+            vec!["n".to_owned()],
+            &[CellType::U64],
+        );
+        assert!(df.to_arrow().is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut df = CsvParser::parse_buffer(
+            b"id,big,price,label,at,ok\n\
+              1,18446744073709551615,2.5,a,12:30:00,\n\
+              -2,3,,\"b, c\",,\n",
+            1,
+        );
+        df.add_column("flag", vec![Cell::Bool(true), Cell::Null])
+            .unwrap();
+        let back = DataFrame::from_arrow(&df.to_arrow().unwrap()).unwrap();
+        assert_eq!(back.header(), df.header());
+        assert_eq!(back.dtypes(), df.dtypes());
+        for index in 0..df.hlen() {
+            assert_eq!(texts(&back, index), texts(&df, index));
+        }
+    }
+}
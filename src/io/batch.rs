@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+use crate::io::buffer::{AlignedBuffer, BufferElement, BufferLayout};
+
+/// SplitMix64 generator, enough to shuffle rows reproducibly
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    #[inline]
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random value in `0..=bound`
+    #[inline]
+    fn below_or_eq(&mut self, bound: usize) -> usize {
+        (self.next() % (bound as u64 + 1)) as usize
+    }
+}
+
+/// Iterator over the mini-batches of a `DataFrame`, created by
+/// [`DataFrame::batches`].
+///
+/// Each batch is a row-major matrix of features (one row per row of
+/// the frame) and the vector of the matching targets. The last batch
+/// holds the remaining rows, and may be smaller.
+pub struct Batches<'a, T: BufferElement> {
+    /// Data Frame read
+    df: &'a DataFrame,
+    /// Index of each feature column
+    features: Vec<usize>,
+    /// Index of the target column
+    target: usize,
+    /// Rows of the Data Frame, in the order they are yielded
+    order: Vec<usize>,
+    /// Rows per batch
+    batch_size: usize,
+    /// Position in `order` of the first row of the next batch
+    position: usize,
+    /// Type of the values
+    element: PhantomData<T>,
+}
+
+impl<T: BufferElement> Batches<'_, T> {
+    /// Rows per batch
+    #[inline(always)]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+impl<T: BufferElement> Iterator for Batches<'_, T> {
+    type Item = (AlignedBuffer<T>, Vec<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rows = self.order.get(self.position..)?;
+        let rows = &rows[..rows.len().min(self.batch_size)];
+        if rows.is_empty() {
+            return None;
+        }
+        self.position += rows.len();
+
+        let mut features = AlignedBuffer::nulls(
+            rows.len(),
+            self.features.len(),
+            BufferLayout::RowMajor,
+        );
+        let mut targets = Vec::with_capacity(rows.len());
+        for (batch_row, row) in rows.iter().enumerate() {
+            let cells = self.df.row(*row)?;
+            self.features.iter().enumerate().for_each(|(col, index)| {
+                if let Some(value) = cells[*index].as_f64() {
+                    let offset = features.offset(batch_row, col);
+                    features[offset] = T::from_f64(value);
+                }
+            });
+            targets
+                .push(cells[self.target].as_f64().map_or(T::NULL, T::from_f64));
+        }
+
+        Some((features, targets))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = (self.order.len() - self.position).div_ceil(self.batch_size);
+        (left, Some(left))
+    }
+}
+
+impl<T: BufferElement> ExactSizeIterator for Batches<'_, T> {}
+
+impl DataFrame {
+    /// Iterator over mini-batches of `batch_size` rows, each yielding
+    /// the values of the numeric columns `feature_cols` as a row-major
+    /// matrix, and the values of the numeric column `target_col`.
+    /// Nulls are written as NaN.
+    ///
+    /// Rows are shuffled with `shuffle_seed` if given (the same seed
+    /// gives the same order), and read in order otherwise.
+    ///
+    /// Fails with [`Error::TypeMismatch`] if a column is not numeric,
+    /// and with [`Error::InvalidArgument`] if `batch_size` is `0`.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"x,y,label\n1,2,0\n3,4,1\n5,6,0\n", 1);
+    ///
+    /// let mut batches = df.batches::<f32, _>(&["x", "y"], "label", 2, None).unwrap();
+    /// assert_eq!(batches.len(), 2);
+    /// let (features, targets) = batches.next().unwrap();
+    /// assert_eq!((features.rows(), features.cols()), (2, 2));
+    /// assert_eq!(features[..], [1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(targets, [0.0, 1.0]);
+    /// assert_eq!(batches.next().unwrap().1, [0.0]);
+    ///
+    /// // Shuffled, reproducibly
+    /// let shuffled = |seed| {
+    ///     df.batches::<f64, _>(&["x"], "label", 3, Some(seed))
+    ///         .unwrap()
+    ///         .flat_map(|(features, _)| features.to_vec())
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(shuffled(7), shuffled(7));
+    /// let mut rows = shuffled(7);
+    /// rows.sort_by(f64::total_cmp);
+    /// assert_eq!(rows, [1.0, 3.0, 5.0]);
+    ///
+    /// assert!(df.batches::<f32, _>(&["x"], "label", 0, None).is_err());
+    /// ```
+    pub fn batches<T: BufferElement, C: ColumnRef>(
+        &self,
+        feature_cols: &[C],
+        target_col: C,
+        batch_size: usize,
+        shuffle_seed: Option<u64>,
+    ) -> Result<Batches<'_, T>> {
+        if batch_size == 0 {
+            return Err(Error::InvalidArgument(
+                "batch size must be non-zero".to_owned(),
+            ));
+        }
+
+        let features = self.numeric_indices(feature_cols)?;
+        let target = self.numeric_indices(&[target_col])?[0];
+
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        if let Some(seed) = shuffle_seed {
+            // Fisher-Yates
+            let mut random = SplitMix64(seed);
+            for index in (1..order.len()).rev() {
+                order.swap(index, random.below_or_eq(index));
+            }
+        }
+
+        Ok(Batches {
+            df: self,
+            features,
+            target,
+            order,
+            batch_size,
+            position: 0,
+            element: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,153 @@
+//! Compact binary encoding of cells: a tag byte followed by the value,
+//! integers in little endian and strings or bytes prefixed by their
+//! length.
+
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+use crate::cell::Cell;
+use crate::datetime::{Date, Duration, Time};
+use crate::decimal::Decimal128;
+
+const NULL: u8 = 0;
+const STRING: u8 = 1;
+const NUMBER: u8 = 2;
+const DECIMAL: u8 = 3;
+const DATE: u8 = 4;
+const TIME: u8 = 5;
+const DURATION: u8 = 6;
+const BYTES: u8 = 7;
+const FALSE: u8 = 8;
+const TRUE: u8 = 9;
+const UINT: u8 = 10;
+const DECIMAL128: u8 = 11;
+
+/// Write `bytes` prefixed by their length
+#[inline]
+pub(crate) fn write_len_prefixed<W: Write>(
+    writer: &mut W,
+    bytes: &[u8],
+) -> Result<()> {
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+/// Write `cell` to `writer`. Categories are written as the strings they
+/// stand for in `dictionary`, the dictionary of the column.
+pub(crate) fn write_cell<W: Write>(
+    writer: &mut W,
+    cell: &Cell,
+    dictionary: Option<&[String]>,
+) -> Result<()> {
+    match cell {
+        Cell::Null => writer.write_all(&[NULL]),
+        Cell::String(string) => {
+            writer.write_all(&[STRING])?;
+            write_len_prefixed(writer, string.as_bytes())
+        }
+        Cell::Categorical(_) => {
+            let text = cell.text(dictionary).unwrap_or_default();
+            writer.write_all(&[STRING])?;
+            write_len_prefixed(writer, text.as_bytes())
+        }
+        Cell::Number(number) => {
+            writer.write_all(&[NUMBER])?;
+            writer.write_all(&number.to_le_bytes())
+        }
+        Cell::UInt(number) => {
+            writer.write_all(&[UINT])?;
+            writer.write_all(&number.to_le_bytes())
+        }
+        Cell::Decimal(decimal) => {
+            writer.write_all(&[DECIMAL])?;
+            writer.write_all(&decimal.to_bits().to_le_bytes())
+        }
+        Cell::Decimal128(decimal) => {
+            writer.write_all(&[DECIMAL128])?;
+            writer.write_all(&decimal.mantissa().to_le_bytes())?;
+            writer.write_all(&[decimal.scale()])
+        }
+        Cell::Date(date) => {
+            writer.write_all(&[DATE])?;
+            writer.write_all(&date.days_since_epoch().to_le_bytes())
+        }
+        Cell::Time(time) => {
+            writer.write_all(&[TIME])?;
+            writer.write_all(&time.nanos_since_midnight().to_le_bytes())
+        }
+        Cell::Duration(duration) => {
+            writer.write_all(&[DURATION])?;
+            writer.write_all(&duration.as_nanos().to_le_bytes())
+        }
+        Cell::Bytes(bytes) => {
+            writer.write_all(&[BYTES])?;
+            write_len_prefixed(writer, bytes)
+        }
+        Cell::Bool(value) => {
+            writer.write_all(&[if *value { TRUE } else { FALSE }])
+        }
+    }
+}
+
+/// Read `N` bytes
+#[inline]
+pub(crate) fn read_array<R: Read, const N: usize>(
+    reader: &mut R,
+) -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read bytes prefixed by their length
+#[inline]
+pub(crate) fn read_len_prefixed<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let len = u64::from_le_bytes(read_array(reader)?);
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// Read a cell written by [`write_cell`]
+pub(crate) fn read_cell<R: Read>(reader: &mut R) -> Result<Cell> {
+    let [tag] = read_array(reader)?;
+    Ok(match tag {
+        NULL => Cell::Null,
+        STRING => Cell::String(
+            String::from_utf8(read_len_prefixed(reader)?)
+                .map_err(|err| Error::new(ErrorKind::InvalidData, err))?,
+        ),
+        NUMBER => Cell::Number(i64::from_le_bytes(read_array(reader)?)),
+        UINT => Cell::UInt(u64::from_le_bytes(read_array(reader)?)),
+        DECIMAL => Cell::Decimal(f64::from_bits(u64::from_le_bytes(
+            read_array(reader)?,
+        ))),
+        DECIMAL128 => {
+            let mantissa = i128::from_le_bytes(read_array(reader)?);
+            let [scale] = read_array(reader)?;
+            Cell::from(Decimal128::new(mantissa, scale).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "invalid decimal scale")
+            })?)
+        }
+        DATE => Cell::Date(Date::from_days_since_epoch(i32::from_le_bytes(
+            read_array(reader)?,
+        ))),
+        TIME => Cell::Time(Time::from_nanos_since_midnight(
+            u64::from_le_bytes(read_array(reader)?),
+        )),
+        DURATION => Cell::Duration(Duration::from_nanos(i64::from_le_bytes(
+            read_array(reader)?,
+        ))),
+        BYTES => Cell::Bytes(read_len_prefixed(reader)?),
+        FALSE => Cell::Bool(false),
+        TRUE => Cell::Bool(true),
+        tag => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown cell tag {}", tag),
+            ))
+        }
+    })
+}
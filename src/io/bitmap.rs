@@ -0,0 +1,115 @@
+/// Packed sequence of bits, one per row: the validity of a column
+/// copied out of a Data Frame, where a set bit marks a value and a
+/// cleared bit a null.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bitmap {
+    /// Bits, the first row in the lowest bit of the first word
+    words: Vec<u64>,
+    /// Total bits
+    len: usize,
+}
+
+impl Bitmap {
+    /// `len` bits, all set to `value`
+    pub fn new(len: usize, value: bool) -> Self {
+        let fill = if value { u64::MAX } else { 0 };
+        let mut bitmap = Self {
+            words: vec![fill; len.div_ceil(64)],
+            len,
+        };
+        bitmap.clear_tail();
+        bitmap
+    }
+
+    /// Clear the bits of the last word past the length, so that whole
+    /// words can be counted
+    #[inline]
+    fn clear_tail(&mut self) {
+        if let (Some(last), tail @ 1..) = (self.words.last_mut(), self.len % 64)
+        {
+            *last &= (1 << tail) - 1;
+        }
+    }
+
+    /// Total bits
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bitmap holds no bits
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bit `index`, `false` if out of bounds
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    /// Set bit `index` to `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit {} out of bounds", index);
+        let (word, bit) = (index / 64, index % 64);
+        self.words[word] =
+            self.words[word] & !(1 << bit) | (value as u64) << bit;
+    }
+
+    /// Append bit `value`
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// Total set bits
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Total cleared bits
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Returns `true` if every bit is set
+    #[inline]
+    pub fn all(&self) -> bool {
+        self.count_ones() == self.len
+    }
+
+    /// Packed words of the bitmap, the bits past the length are cleared
+    #[inline(always)]
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Iterator over the bits
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bitmap = Self::default();
+        iter.into_iter().for_each(|bit| bitmap.push(bit));
+        bitmap
+    }
+}
@@ -0,0 +1,254 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::cell::CellType;
+use crate::dframe::{ColumnRef, DataFrame};
+use crate::error::{Error, Result};
+
+/// Alignment in bytes of the values of an [`AlignedBuffer`]: a cache
+/// line, and the width of the widest SIMD registers
+pub const BUFFER_ALIGN: usize = 64;
+
+/// Storage unit of an [`AlignedBuffer`], giving it its alignment
+#[derive(Clone, Copy)]
+#[repr(C, align(64))]
+struct Block([u8; BUFFER_ALIGN]);
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Type of the values of an [`AlignedBuffer`]: `f32` or `f64`
+pub trait BufferElement: Copy + private::Sealed {
+    /// Value written for nulls
+    const NULL: Self;
+
+    /// `value` converted to the element type, rounded if needed
+    fn from_f64(value: f64) -> Self;
+}
+
+impl BufferElement for f32 {
+    const NULL: Self = f32::NAN;
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl BufferElement for f64 {
+    const NULL: Self = f64::NAN;
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// Order of the values in an [`AlignedBuffer`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BufferLayout {
+    /// Values of each row next to each other
+    #[default]
+    RowMajor,
+    /// Values of each column next to each other
+    ColumnMajor,
+}
+
+/// Contiguous matrix of `f32` or `f64` values, aligned on
+/// [`BUFFER_ALIGN`] bytes, ready to be uploaded to a GPU or handed to
+/// a machine learning framework. Created by
+/// [`DataFrame::export_numeric_buffer`].
+///
+/// The buffer dereferences to the slice of its values.
+#[derive(Clone)]
+pub struct AlignedBuffer<T: BufferElement> {
+    /// Storage of the values
+    blocks: Vec<Block>,
+    /// Total rows of the matrix
+    rows: usize,
+    /// Total columns of the matrix
+    cols: usize,
+    /// Order of the values
+    layout: BufferLayout,
+    /// Type of the values
+    element: PhantomData<T>,
+}
+
+impl<T: BufferElement> AlignedBuffer<T> {
+    /// Buffer of `rows` by `cols` values laid out as told by `layout`,
+    /// all nulls
+    pub(super) fn nulls(
+        rows: usize,
+        cols: usize,
+        layout: BufferLayout,
+    ) -> Self {
+        let bytes = rows * cols * std::mem::size_of::<T>();
+        let mut buffer = Self {
+            blocks: vec![
+                Block([0; BUFFER_ALIGN]);
+                bytes.div_ceil(BUFFER_ALIGN)
+            ],
+            rows,
+            cols,
+            layout,
+            element: PhantomData,
+        };
+        buffer.fill(T::NULL);
+        buffer
+    }
+
+    /// Total rows of the matrix
+    #[inline(always)]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Total columns of the matrix
+    #[inline(always)]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Order of the values
+    #[inline(always)]
+    pub fn layout(&self) -> BufferLayout {
+        self.layout
+    }
+
+    /// Position in the buffer of the value at `row` and `col`
+    #[inline(always)]
+    pub(super) fn offset(&self, row: usize, col: usize) -> usize {
+        match self.layout {
+            BufferLayout::RowMajor => row * self.cols + col,
+            BufferLayout::ColumnMajor => col * self.rows + row,
+        }
+    }
+
+    /// Value at `row` and `col`, `None` if out of bounds
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> Option<T> {
+        (row < self.rows && col < self.cols)
+            .then(|| self[self.offset(row, col)])
+    }
+}
+
+impl<T: BufferElement> Deref for AlignedBuffer<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        // Blocks hold at least `rows * cols` values, and are aligned
+        // for any element type
+        unsafe {
+            std::slice::from_raw_parts(
+                self.blocks.as_ptr().cast::<T>(),
+                self.rows * self.cols,
+            )
+        }
+    }
+}
+
+impl<T: BufferElement> DerefMut for AlignedBuffer<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.blocks.as_mut_ptr().cast::<T>(),
+                self.rows * self.cols,
+            )
+        }
+    }
+}
+
+impl DataFrame {
+    /// Values of the numeric columns `cols` as a contiguous, aligned
+    /// matrix of `f32` or `f64`, laid out as told by `layout`. Nulls
+    /// are written as NaN.
+    ///
+    /// Fails with [`Error::TypeMismatch`] if a column is not numeric.
+    ///
+    /// ```
+    /// use nn::io::buffer::{AlignedBuffer, BufferLayout, BUFFER_ALIGN};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let df = CsvParser::parse_buffer(b"x,y,label\n1,2.5,a\n3,,b\n", 1);
+    /// let buffer: AlignedBuffer<f32> = df
+    ///     .export_numeric_buffer(&["x", "y"], BufferLayout::ColumnMajor)
+    ///     .unwrap();
+    ///
+    /// assert_eq!((buffer.rows(), buffer.cols()), (2, 2));
+    /// assert_eq!(buffer[..3], [1.0, 3.0, 2.5]);
+    /// assert!(buffer[3].is_nan());
+    /// assert_eq!(buffer.as_ptr() as usize % BUFFER_ALIGN, 0);
+    ///
+    /// assert!(df
+    ///     .export_numeric_buffer::<f64, _>(&["label"], BufferLayout::RowMajor)
+    ///     .is_err());
+    /// ```
+    pub fn export_numeric_buffer<T: BufferElement, C: ColumnRef>(
+        &self,
+        cols: &[C],
+        layout: BufferLayout,
+    ) -> Result<AlignedBuffer<T>> {
+        let indices = self.numeric_indices(cols)?;
+
+        let mut buffer =
+            AlignedBuffer::nulls(self.len(), indices.len(), layout);
+        self.iter().enumerate().for_each(|(row, cells)| {
+            indices.iter().enumerate().for_each(|(col, index)| {
+                if let Some(value) = cells[*index].as_f64() {
+                    let offset = buffer.offset(row, col);
+                    buffer[offset] = T::from_f64(value);
+                }
+            })
+        });
+
+        Ok(buffer)
+    }
+
+    /// Index of each of the columns `cols`, checked to be numeric
+    pub(super) fn numeric_indices<C: ColumnRef>(
+        &self,
+        cols: &[C],
+    ) -> Result<Vec<usize>> {
+        cols.iter()
+            .map(|col| {
+                let index = self.column_index(col)?;
+                let found = self.dtypes()[index];
+                if !found.is_numeric() && found != CellType::Null {
+                    return Err(Error::TypeMismatch {
+                        column: self.header()[index].clone(),
+                        expected: CellType::F64,
+                        found,
+                    });
+                }
+                Ok(index)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlignedBuffer, BufferLayout};
+    use crate::cell::{Cell, CellType};
+    use crate::decimal::Decimal128;
+    use crate::dframe::DataFrame;
+
+    #[test]
+    fn exact_decimal_columns_are_numeric() {
+        let df = DataFrame::from_cells(
+            vec![Cell::from(Decimal128::parse("0.10").unwrap()), Cell::Null],
+            vec!["price".to_owned()],
+            &[CellType::Decimal128],
+        );
+        let buffer: AlignedBuffer<f64> = df
+            .export_numeric_buffer(&["price"], BufferLayout::RowMajor)
+            .unwrap();
+        assert_eq!(buffer[0], 0.1);
+        assert!(buffer[1].is_nan());
+    }
+}
@@ -0,0 +1,386 @@
+use std::io::Write;
+
+use crate::cell::Cell;
+use crate::dframe::DataFrame;
+use crate::encoding::BytesEncoding;
+use crate::error::Result;
+use crate::io::float::RoundTripF64;
+
+/// When fields are enclosed in quotes while writing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// Quote every non-null field
+    Always,
+    /// Quote fields only when needed: containing the delimiter,
+    /// quotes, line breaks or surrounding whitespace
+    #[default]
+    Minimal,
+    /// Never quote fields, even if the output cannot be read back
+    Never,
+}
+
+/// Options controlling how a `DataFrame` is written as CSV.
+///
+/// ```
+/// use nn::io::csv::{CsvWriterOptions, QuoteStyle};
+///
+/// let options = CsvWriterOptions::new()
+///     .delimiter(b';')
+///     .quote_style(QuoteStyle::Always)
+///     .null_value("NA")
+///     .line_terminator("\r\n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct CsvWriterOptions {
+    /// Field separator
+    pub(crate) delimiter: u8,
+    /// Quoting policy of fields
+    pub(crate) quote_style: QuoteStyle,
+    /// Text written for `Cell::Null`
+    pub(crate) null_value: String,
+    /// Text written after each record
+    pub(crate) line_terminator: String,
+    /// Write the header as the first record
+    pub(crate) header: bool,
+    /// Text encoding of `Cell::Bytes`
+    pub(crate) bytes_encoding: BytesEncoding,
+    /// Start the output with the UTF-8 byte order mark
+    pub(crate) bom: bool,
+}
+
+impl Default for CsvWriterOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote_style: QuoteStyle::default(),
+            null_value: String::new(),
+            line_terminator: "\n".to_owned(),
+            header: true,
+            bytes_encoding: BytesEncoding::default(),
+            bom: false,
+        }
+    }
+}
+
+impl CsvWriterOptions {
+    /// Default options: comma separated, minimal quoting, empty nulls
+    /// and `\n` terminated records
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preset matching what Excel expects from a CSV file: comma
+    /// separated, `\r\n` terminated records, starting with a byte order
+    /// mark so that non ASCII text is read as UTF-8
+    #[inline]
+    pub fn excel() -> Self {
+        Self::new().line_terminator("\r\n").bom(true)
+    }
+
+    /// Preset for tab separated values as pasted into spreadsheets:
+    /// tab separated, `\r\n` terminated records
+    #[inline]
+    pub fn tsv() -> Self {
+        Self::new().delimiter(b'\t').line_terminator("\r\n")
+    }
+
+    /// Separate fields with `delimiter`
+    #[inline]
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Quote fields as described by `quote_style`
+    #[inline]
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Write `Cell::Null` as `null_value`
+    #[inline]
+    pub fn null_value(mut self, null_value: &str) -> Self {
+        self.null_value = null_value.to_owned();
+        self
+    }
+
+    /// End each record with `line_terminator`
+    #[inline]
+    pub fn line_terminator(mut self, line_terminator: &str) -> Self {
+        self.line_terminator = line_terminator.to_owned();
+        self
+    }
+
+    /// Write (default) or omit the header record
+    #[inline]
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Write `Cell::Bytes` encoded with `bytes_encoding` (base64 by
+    /// default)
+    #[inline]
+    pub fn bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// Start (or not, by default) the output with the UTF-8 byte order
+    /// mark
+    #[inline]
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Returns `true` if `field` should be quoted
+    #[inline]
+    fn needs_quotes(&self, field: &str) -> bool {
+        match self.quote_style {
+            QuoteStyle::Always => true,
+            QuoteStyle::Never => false,
+            QuoteStyle::Minimal => {
+                field.bytes().any(|c| {
+                    matches!(c, b'"' | b'\r' | b'\n') || c == self.delimiter
+                }) || field.starts_with(char::is_whitespace)
+                    || field.ends_with(char::is_whitespace)
+            }
+        }
+    }
+
+    /// Write text `field`, quoting and escaping it if needed
+    fn write_field<W: Write>(&self, writer: &mut W, field: &str) -> Result<()> {
+        if self.needs_quotes(field) {
+            writer.write_all(b"\"")?;
+            writer.write_all(field.replace('"', "\"\"").as_bytes())?;
+            writer.write_all(b"\"")?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Write a single `cell`, whose categories are read from
+    /// `dictionary`
+    fn write_cell<W: Write>(
+        &self,
+        writer: &mut W,
+        cell: &Cell,
+        dictionary: Option<&[String]>,
+    ) -> Result<()> {
+        let quote = self.quote_style == QuoteStyle::Always;
+
+        match cell {
+            Cell::Null => writer.write_all(self.null_value.as_bytes())?,
+            Cell::String(string) => self.write_field(writer, string)?,
+            Cell::Categorical(_) => self.write_field(
+                writer,
+                cell.text(dictionary).unwrap_or_default(),
+            )?,
+            Cell::Number(number) if quote => write!(writer, "\"{}\"", number)?,
+            Cell::Number(number) => write!(writer, "{}", number)?,
+            Cell::UInt(number) if quote => write!(writer, "\"{}\"", number)?,
+            Cell::UInt(number) => write!(writer, "{}", number)?,
+            Cell::Decimal(decimal) if quote => {
+                write!(writer, "\"{}\"", RoundTripF64(*decimal))?
+            }
+            Cell::Decimal(decimal) => {
+                write!(writer, "{}", RoundTripF64(*decimal))?
+            }
+            Cell::Decimal128(decimal) if quote => {
+                write!(writer, "\"{}\"", decimal)?
+            }
+            Cell::Decimal128(decimal) => write!(writer, "{}", decimal)?,
+            Cell::Date(date) if quote => write!(writer, "\"{}\"", date)?,
+            Cell::Date(date) => write!(writer, "{}", date)?,
+            Cell::Time(time) if quote => write!(writer, "\"{}\"", time)?,
+            Cell::Time(time) => write!(writer, "{}", time)?,
+            Cell::Duration(duration) if quote => {
+                write!(writer, "\"{}\"", duration)?
+            }
+            Cell::Duration(duration) => write!(writer, "{}", duration)?,
+            Cell::Bool(value) if quote => write!(writer, "\"{}\"", value)?,
+            Cell::Bool(value) => write!(writer, "{}", value)?,
+            Cell::Bytes(bytes) => {
+                let text = self.bytes_encoding.encode(bytes);
+                if quote {
+                    write!(writer, "\"{}\"", text)?
+                } else {
+                    writer.write_all(text.as_bytes())?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the header names as a record
+    fn write_header<W: Write>(
+        &self,
+        writer: &mut W,
+        header: &[String],
+    ) -> Result<()> {
+        header.iter().enumerate().try_for_each(|(index, name)| {
+            if index > 0 {
+                writer.write_all(&[self.delimiter])?;
+            }
+            self.write_field(writer, name)
+        })?;
+        writer.write_all(self.line_terminator.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write the cells of `row` as a record, with the `dictionaries`
+    /// of its columns
+    fn write_row<W: Write>(
+        &self,
+        writer: &mut W,
+        row: &[Cell],
+        dictionaries: &[Option<&[String]>],
+    ) -> Result<()> {
+        row.iter().zip(dictionaries).enumerate().try_for_each(
+            |(index, (cell, dictionary))| {
+                if index > 0 {
+                    writer.write_all(&[self.delimiter])?;
+                }
+                self.write_cell(writer, cell, *dictionary)
+            },
+        )?;
+        writer.write_all(self.line_terminator.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl DataFrame {
+    /// Write the Data Frame as CSV into `writer`
+    pub fn write_csv<W: Write>(
+        &self,
+        writer: W,
+        options: &CsvWriterOptions,
+    ) -> Result<()> {
+        let mut writer = std::io::BufWriter::new(writer);
+
+        if options.bom {
+            writer.write_all("\u{feff}".as_bytes())?;
+        }
+        if options.header && self.hlen() > 0 {
+            options.write_header(&mut writer, self.header())?;
+        }
+
+        let dictionaries = self.dictionaries();
+        self.iter().try_for_each(|row| {
+            options.write_row(&mut writer, row, &dictionaries)
+        })?;
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Data Frame as tab separated values ready to be pasted into a
+    /// spreadsheet, written with [`CsvWriterOptions::tsv`]
+    pub fn to_clipboard_tsv(&self) -> String {
+        let mut buffer = Vec::new();
+        // Writing into memory does not fail
+        self.write_csv(&mut buffer, &CsvWriterOptions::tsv())
+            .unwrap();
+        String::from_utf8(buffer).expect("writer emits UTF-8")
+    }
+
+    /// Write the Data Frame as CSV into file `file_name`, replacing the
+    /// file if it exists
+    pub fn to_csv(
+        &self,
+        file_name: &str,
+        options: &CsvWriterOptions,
+    ) -> Result<()> {
+        self.write_csv(std::fs::File::create(file_name)?, options)
+    }
+}
+
+#[cfg(all(test, feature = "parser"))]
+mod tests {
+    use super::{CsvWriterOptions, QuoteStyle};
+    use crate::cell::{Cell, CellType};
+    use crate::datetime::Date;
+    use crate::dframe::DataFrame;
+    use crate::options::ParserOptions;
+    use crate::parser::CsvParser;
+
+    #[test]
+    fn written_frames_parse_back() {
+        let texts = [
+            "say \"hi\", ok",
+            "1\"2",
+            "he said \"hi\"",
+            "  pad ",
+            "two\nlines\r\n",
+            "\"",
+            ".5 m",
+            "plain",
+        ];
+        let cells: Vec<Cell> = texts
+            .iter()
+            .enumerate()
+            .flat_map(|(row, text)| {
+                let day = Date::from_ymd(2024, 1, row as u32 + 1).unwrap();
+                [
+                    Cell::String((*text).to_owned()),
+                    Cell::Number(row as i64 - 4),
+                    match row % 3 {
+                        0 => Cell::Null,
+                        _ => Cell::Decimal(row as f64 / 4.0),
+                    },
+                    Cell::Date(day),
+                ]
+            })
+            .collect();
+        let header = ["text", "count", "ratio", "day"].map(str::to_owned);
+        let dtype = [
+            CellType::String,
+            CellType::I64,
+            CellType::F64,
+            CellType::Date,
+        ];
+        let df = DataFrame::from_cells(cells, header.to_vec(), &dtype);
+
+        let options = ParserOptions::new().with_type("day", CellType::Date);
+        for style in [QuoteStyle::Minimal, QuoteStyle::Always] {
+            let mut written = Vec::new();
+            df.write_csv(
+                &mut written,
+                &CsvWriterOptions::new().quote_style(style),
+            )
+            .unwrap();
+            let back =
+                CsvParser::parse_buffer_with_options(&written, 1, &options)
+                    .unwrap();
+
+            assert_eq!(back.header(), header);
+            assert_eq!(back.dtypes(), dtype);
+            let rows: Vec<String> =
+                back.iter().map(|row| format!("{:?}", row)).collect();
+            let expected: Vec<String> =
+                df.iter().map(|row| format!("{:?}", row)).collect();
+            assert_eq!(rows, expected, "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn quoted_header_names_parse_back() {
+        let df = DataFrame::from_cells(
+            vec![Cell::Number(1), Cell::Number(2)],
+            vec!["say \"hi\"".to_owned(), "a,b".to_owned()],
+            &[CellType::I64, CellType::I64],
+        );
+        let mut written = Vec::new();
+        df.write_csv(&mut written, &CsvWriterOptions::new())
+            .unwrap();
+        assert_eq!(written, b"\"say \"\"hi\"\"\",\"a,b\"\n1,2\n");
+
+        let back = CsvParser::parse_buffer(&written, 1);
+        assert_eq!(back.header(), df.header());
+        assert_eq!(back.len(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::cell::Cell;
+use crate::dframe::{ColId, Column, ColumnRef, DataFrame};
+use crate::error::Result;
+use crate::io::bitmap::Bitmap;
+
+/// Values summed, or compared, together so that the loops vectorize
+const LANES: usize = 8;
+
+/// Sum of `values`, in `LANES` independent accumulators
+#[inline]
+pub(crate) fn dense_sum(values: &[f64]) -> f64 {
+    let mut lanes = [0.0; LANES];
+    let chunks = values.chunks_exact(LANES);
+    let rest: f64 = chunks.remainder().iter().sum();
+    chunks.for_each(|chunk| {
+        lanes
+            .iter_mut()
+            .zip(chunk)
+            .for_each(|(lane, value)| *lane += value)
+    });
+    lanes.iter().sum::<f64>() + rest
+}
+
+/// `values` whose bit is set in `validity` reduced with `f`, `None` if
+/// no bit is set. Fully valid words are reduced in `LANES` without
+/// testing their bits.
+#[inline]
+pub(crate) fn dense_reduce(
+    values: &[f64],
+    validity: &Bitmap,
+    f: fn(f64, f64) -> f64,
+) -> Option<f64> {
+    let mut result: Option<f64> = None;
+    let mut push = |value: f64| {
+        result = Some(result.map_or(value, |current| f(current, value)))
+    };
+
+    for (word, values) in validity.words().iter().zip(values.chunks(64)) {
+        if *word == u64::MAX {
+            let mut lanes = [values[0]; LANES];
+            values.chunks_exact(LANES).for_each(|chunk| {
+                lanes
+                    .iter_mut()
+                    .zip(chunk)
+                    .for_each(|(lane, value)| *lane = f(*lane, *value))
+            });
+            lanes.into_iter().for_each(&mut push);
+            continue;
+        }
+
+        // Visit the set bits only
+        let mut bits = *word;
+        while bits != 0 {
+            push(values[bits.trailing_zeros() as usize]);
+            bits &= bits - 1;
+        }
+    }
+    result
+}
+
+/// Values of a numeric column copied into a dense buffer, with a
+/// validity bitmap marking the nulls (which hold `0.0` in the buffer).
+/// Created by [`Column::export_dense`].
+///
+/// The Data Frame keeps its cells: the buffer is a copy taken when
+/// exported, and later updates of the frame do not reach it. Its
+/// aggregations read the buffer without matching on cells, and test
+/// the validity a word of 64 rows at a time, for callers running many
+/// of them over the same column.
+///
+/// ```
+/// use nn::parser::CsvParser;
+///
+/// let df = CsvParser::parse_buffer(b"id,price\n1,2\n2,\n3,4.5\n", 1);
+/// let price = df.column("price").unwrap().export_dense().unwrap();
+///
+/// assert_eq!(price.values(), [2.0, 0.0, 4.5]);
+/// assert!(price.is_null(1));
+/// assert_eq!((price.null_count(), price.sum()), (1, 6.5));
+/// assert_eq!(price.mean(), Some(3.25));
+/// assert_eq!((price.min(), price.max()), (Some(2.0), Some(4.5)));
+/// assert_eq!(df.export_validity("price").unwrap().count_ones(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DenseF64 {
+    /// Value of each row, `0.0` for nulls
+    values: Vec<f64>,
+    /// Rows holding a value
+    validity: Bitmap,
+}
+
+impl DenseF64 {
+    /// Value of each row, `0.0` for nulls
+    #[inline(always)]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Rows holding a value
+    #[inline(always)]
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+
+    /// Total rows
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the buffer holds no rows
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if row `index` holds a null (or is out of bounds)
+    #[inline(always)]
+    pub fn is_null(&self, index: usize) -> bool {
+        !self.validity.get(index)
+    }
+
+    /// Total nulls
+    #[inline]
+    pub fn null_count(&self) -> usize {
+        self.validity.count_zeros()
+    }
+
+    /// Value of row `index`, `None` for nulls and out of bounds rows
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<f64> {
+        self.validity.get(index).then(|| self.values[index])
+    }
+
+    /// Sum of the values (`0` for a column of nulls)
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        // Nulls hold zeros, so every value is summed
+        dense_sum(&self.values)
+    }
+
+    /// Mean of the values, `None` for a column of nulls
+    #[inline]
+    pub fn mean(&self) -> Option<f64> {
+        let count = self.validity.count_ones();
+        (count > 0).then(|| self.sum() / count as f64)
+    }
+
+    /// Smallest value, `None` for a column of nulls
+    #[inline]
+    pub fn min(&self) -> Option<f64> {
+        dense_reduce(&self.values, &self.validity, f64::min)
+    }
+
+    /// Largest value, `None` for a column of nulls
+    #[inline]
+    pub fn max(&self) -> Option<f64> {
+        dense_reduce(&self.values, &self.validity, f64::max)
+    }
+}
+
+impl Column<'_> {
+    /// Rows of the column holding a value, copied into a [`Bitmap`]
+    pub fn export_validity(&self) -> Bitmap {
+        self.iter()
+            .map(|cell| !matches!(cell, Cell::Null))
+            .collect()
+    }
+
+    /// Values copied into a dense buffer with their validity, see
+    /// [`DenseF64`]. Fails unless the column holds only numbers and
+    /// nulls.
+    pub fn export_dense(&self) -> Result<DenseF64> {
+        self.check_numeric()?;
+        Ok(DenseF64 {
+            values: self.iter().map(|c| c.as_f64().unwrap_or(0.0)).collect(),
+            validity: self.export_validity(),
+        })
+    }
+}
+
+impl DataFrame {
+    /// Rows of column `col` holding a value, copied into a [`Bitmap`],
+    /// see [`Column::export_validity`]
+    pub fn export_validity<C: ColumnRef>(&self, col: C) -> Result<Bitmap> {
+        let index = self.column_index(col)?;
+        // Every index is a column of the Data Frame
+        Ok(self.column(ColId(index)).unwrap().export_validity())
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::{self, Display, Write};
+
+/// Decimal exponents written in plain notation, values outside are
+/// written in scientific notation (`1.5e-9`, `2e21`).
+const PLAIN_EXPONENTS: std::ops::Range<i32> = -7..21;
+
+/// Formats an `f64` with the fewest digits that parse back to the exact
+/// same value, independent of `Display` formatting choices.
+///
+/// - Digits are the shortest round-trip digits (as produced by ryu).
+/// - Values with decimal exponent in `-7..21` are written in plain
+///   notation, always with a fractional part (`3.0`), so that written
+///   decimals are read back as decimals and not integers.
+/// - Other values use scientific notation (`1.5e-9`).
+/// - `NaN`, `inf` and `-inf` are written as is.
+///
+/// ```
+/// use nn::io::float::RoundTripF64;
+///
+/// assert_eq!(RoundTripF64(3.0).to_string(), "3.0");
+/// assert_eq!(RoundTripF64(0.1 + 0.2).to_string(), "0.30000000000000004");
+/// assert_eq!(RoundTripF64(-1.5e-9).to_string(), "-1.5e-9");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundTripF64(pub f64);
+
+impl Display for RoundTripF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0;
+
+        if value.is_nan() {
+            return f.write_str("NaN");
+        }
+        if value.is_infinite() {
+            return f.write_str(if value > 0.0 { "inf" } else { "-inf" });
+        }
+
+        // Shortest round trip digits with exponent, e.g. `-1.2345e3`
+        let mut scientific = String::with_capacity(32);
+        write!(scientific, "{:e}", value)?;
+
+        let (mantissa, exponent) = scientific.split_once('e').unwrap();
+        let exponent: i32 = exponent.parse().unwrap();
+        let (sign, mantissa) = match mantissa.strip_prefix('-') {
+            Some(mantissa) => ("-", mantissa),
+            None => ("", mantissa),
+        };
+        let (lead, fraction) =
+            mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        f.write_str(sign)?;
+
+        if !PLAIN_EXPONENTS.contains(&exponent) {
+            f.write_str(lead)?;
+            if !fraction.is_empty() {
+                f.write_char('.')?;
+                f.write_str(fraction)?;
+            }
+            return write!(f, "e{}", exponent);
+        }
+
+        if exponent < 0 {
+            // 0.000ddd
+            f.write_str("0.")?;
+            (0..-exponent - 1).try_for_each(|_| f.write_char('0'))?;
+            f.write_str(lead)?;
+            return f.write_str(fraction);
+        }
+
+        // Digits before the point: lead digit and `exponent` digits
+        // taken from the fraction, padded with zeros.
+        let integer_len = exponent as usize;
+        f.write_str(lead)?;
+        if fraction.len() <= integer_len {
+            f.write_str(fraction)?;
+            (fraction.len()..integer_len)
+                .try_for_each(|_| f.write_char('0'))?;
+            f.write_str(".0")
+        } else {
+            f.write_str(&fraction[..integer_len])?;
+            f.write_char('.')?;
+            f.write_str(&fraction[integer_len..])
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::io::Write;
+
+use crate::cell::Cell;
+use crate::dframe::DataFrame;
+use crate::encoding::BytesEncoding;
+use crate::error::Result;
+use crate::io::float::RoundTripF64;
+
+/// Layout of the JSON document written for a `DataFrame`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Array of one object per row: `[{"col":val,...},...]`
+    #[default]
+    Records,
+    /// Object of one array per column: `{"col":[...],...}`
+    Columns,
+}
+
+/// Write `string` as a JSON string literal, escaping quotes,
+/// backslashes and control characters
+pub(crate) fn write_json_string<W: Write>(
+    writer: &mut W,
+    string: &str,
+) -> Result<()> {
+    writer.write_all(b"\"")?;
+
+    let mut start = 0;
+    for (index, c) in string.char_indices() {
+        let escaped = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            c if (c as u32) < 0x20 => "",
+            _ => continue,
+        };
+
+        writer.write_all(&string.as_bytes()[start..index])?;
+        if escaped.is_empty() {
+            write!(writer, "\\u{:04x}", c as u32)?;
+        } else {
+            writer.write_all(escaped.as_bytes())?;
+        }
+        start = index + c.len_utf8();
+    }
+
+    writer.write_all(&string.as_bytes()[start..])?;
+    writer.write_all(b"\"")?;
+    Ok(())
+}
+
+/// Write `cell` as a JSON value. Non finite decimals are not
+/// representable in JSON and are written as `null`, bytes are written
+/// as base64 strings, and categories coded in `dictionary` as the
+/// strings they stand for.
+pub(crate) fn write_json_value<W: Write>(
+    writer: &mut W,
+    cell: &Cell,
+    dictionary: Option<&[String]>,
+) -> Result<()> {
+    match cell {
+        Cell::Null => writer.write_all(b"null")?,
+        Cell::String(string) => write_json_string(writer, string)?,
+        Cell::Number(number) => write!(writer, "{}", number)?,
+        Cell::UInt(number) => write!(writer, "{}", number)?,
+        Cell::Decimal128(decimal) => write!(writer, "{}", decimal)?,
+        Cell::Decimal(decimal) if decimal.is_finite() => {
+            write!(writer, "{}", RoundTripF64(*decimal))?
+        }
+        Cell::Decimal(_) => writer.write_all(b"null")?,
+        Cell::Date(date) => write!(writer, "\"{}\"", date)?,
+        Cell::Time(time) => write!(writer, "\"{}\"", time)?,
+        Cell::Duration(duration) => write!(writer, "\"{}\"", duration)?,
+        Cell::Bytes(bytes) => {
+            write!(writer, "\"{}\"", BytesEncoding::Base64.encode(bytes))?
+        }
+        Cell::Bool(value) => write!(writer, "{}", value)?,
+        Cell::Categorical(code) => match cell.text(dictionary) {
+            Some(text) => write_json_string(writer, text)?,
+            None => write!(writer, "{}", code)?,
+        },
+    }
+    Ok(())
+}
+
+impl DataFrame {
+    /// Write the Data Frame as JSON into `writer`, laid out as
+    /// described by `orientation`
+    pub fn write_json<W: Write>(
+        &self,
+        writer: W,
+        orientation: Orientation,
+    ) -> Result<()> {
+        let mut writer = std::io::BufWriter::new(writer);
+
+        match orientation {
+            Orientation::Records => {
+                let dictionaries = self.dictionaries();
+                writer.write_all(b"[")?;
+                self.iter().enumerate().try_for_each(|(index, row)| {
+                    if index > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    self.write_json_object(&mut writer, row, &dictionaries)
+                })?;
+                writer.write_all(b"]")?;
+            }
+            Orientation::Columns => {
+                writer.write_all(b"{")?;
+                self.header().iter().enumerate().try_for_each(
+                    |(index, name)| {
+                        if index > 0 {
+                            writer.write_all(b",")?;
+                        }
+                        write_json_string(&mut writer, name)?;
+                        writer.write_all(b":")?;
+                        self.write_json_array(&mut writer, index)
+                    },
+                )?;
+                writer.write_all(b"}")?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Data Frame as a JSON string, laid out as described by
+    /// `orientation`
+    pub fn to_json(&self, orientation: Orientation) -> String {
+        let mut buffer = Vec::new();
+        // Writing into memory does not fail
+        self.write_json(&mut buffer, orientation).unwrap();
+        String::from_utf8(buffer).expect("writer emits UTF-8")
+    }
+
+    /// Write the cells of column `index` as a JSON array
+    fn write_json_array<W: Write>(
+        &self,
+        writer: &mut W,
+        index: usize,
+    ) -> Result<()> {
+        let dictionary = self.dictionary(index);
+        writer.write_all(b"[")?;
+        self.iter().enumerate().try_for_each(|(row_index, row)| {
+            if row_index > 0 {
+                writer.write_all(b",")?;
+            }
+            write_json_value(writer, &row[index], dictionary)
+        })?;
+        writer.write_all(b"]")?;
+        Ok(())
+    }
+
+    /// Write `row` as a JSON object keyed by the header, with the
+    /// dictionary of each column
+    fn write_json_object<W: Write>(
+        &self,
+        writer: &mut W,
+        row: &[Cell],
+        dictionaries: &[Option<&[String]>],
+    ) -> Result<()> {
+        writer.write_all(b"{")?;
+        self.header().iter().zip(row).enumerate().try_for_each(
+            |(index, (name, cell))| {
+                if index > 0 {
+                    writer.write_all(b",")?;
+                }
+                write_json_string(writer, name)?;
+                writer.write_all(b":")?;
+                write_json_value(writer, cell, dictionaries[index])
+            },
+        )?;
+        writer.write_all(b"}")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_json_string, write_json_value, Orientation};
+    use crate::cell::{Cell, CellType};
+    use crate::dframe::DataFrame;
+
+    fn json_string(string: &str) -> String {
+        let mut buffer = Vec::new();
+        write_json_string(&mut buffer, string).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn json_value(cell: &Cell) -> String {
+        let mut buffer = Vec::new();
+        write_json_value(&mut buffer, cell, None).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn escapes_quotes_backslashes_and_whitespace() {
+        assert_eq!(json_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(json_string("a\nb\rc\td"), r#""a\nb\rc\td""#);
+    }
+
+    #[test]
+    fn escapes_other_control_characters_as_unicode() {
+        assert_eq!(json_string("\u{0}"), r#""\u0000""#);
+        assert_eq!(json_string("a\u{1}b\u{1f}"), r#""a\u0001b\u001f""#);
+        // DEL and non ASCII text are written as they are
+        assert_eq!(json_string("\u{7f}é✓"), "\"\u{7f}é✓\"");
+    }
+
+    #[test]
+    fn non_finite_decimals_are_null() {
+        assert_eq!(json_value(&Cell::Decimal(f64::NAN)), "null");
+        assert_eq!(json_value(&Cell::Decimal(f64::INFINITY)), "null");
+        assert_eq!(json_value(&Cell::Decimal(f64::NEG_INFINITY)), "null");
+        assert_eq!(json_value(&Cell::Decimal(0.1)), "0.1");
+        assert_eq!(json_value(&Cell::Null), "null");
+    }
+
+    #[test]
+    fn empty_frames_in_both_orientations() {
+        let header_only = DataFrame::from_cells(
+            Vec::new(),
+            vec!["a".to_owned(), "b".to_owned()],
+            &[CellType::Null, CellType::Null],
+        );
+        assert_eq!(header_only.to_json(Orientation::Records), "[]");
+        assert_eq!(
+            header_only.to_json(Orientation::Columns),
+            r#"{"a":[],"b":[]}"#
+        );
+
+        let no_columns = DataFrame::from_cells(Vec::new(), Vec::new(), &[]);
+        assert_eq!(no_columns.to_json(Orientation::Records), "[]");
+        assert_eq!(no_columns.to_json(Orientation::Columns), "{}");
+    }
+
+    #[test]
+    fn rows_in_both_orientations() {
+        let df = DataFrame::from_cells(
+            vec![
+                Cell::Number(1),
+                Cell::String("x\ny".to_owned()),
+                Cell::Null,
+                Cell::Null,
+            ],
+            vec!["n".to_owned(), "s\"".to_owned()],
+            &[CellType::I64, CellType::String],
+        );
+        assert_eq!(
+            df.to_json(Orientation::Records),
+            r#"[{"n":1,"s\"":"x\ny"},{"n":null,"s\"":null}]"#
+        );
+        assert_eq!(
+            df.to_json(Orientation::Columns),
+            r#"{"n":[1,null],"s\"":["x\ny",null]}"#
+        );
+    }
+}
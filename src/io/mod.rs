@@ -0,0 +1,21 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "dataframe")]
+pub mod batch;
+#[cfg(feature = "dataframe")]
+pub(crate) mod binary;
+#[cfg(feature = "dataframe")]
+pub mod bitmap;
+#[cfg(feature = "dataframe")]
+pub mod buffer;
+#[cfg(feature = "dataframe")]
+pub mod csv;
+#[cfg(feature = "dataframe")]
+pub mod dense;
+pub mod float;
+#[cfg(feature = "io-json")]
+pub mod json;
+#[cfg(feature = "parser")]
+pub(crate) mod number;
+#[cfg(feature = "io-parquet")]
+pub mod parquet;
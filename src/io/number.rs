@@ -0,0 +1,226 @@
+//! Integers and decimals read straight from the bytes of a field,
+//! accepting exactly what `str::parse` accepts.
+//!
+//! Integers are accumulated digit by digit with overflow checks.
+//! Decimals whose digits fit in 53 bits and whose exponent is small
+//! enough are computed exactly from a single multiplication or division
+//! by a power of ten (Clinger's fast path), others are handed over to
+//! `str::parse`.
+
+/// Powers of ten represented exactly as an `f64`
+const POWERS_OF_TEN: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13,
+    1e14, 1e15, 1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+/// Largest integer represented exactly as an `f64`
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+/// Sign of a number and the bytes following it
+#[inline(always)]
+fn split_sign(bytes: &[u8]) -> (bool, &[u8]) {
+    match bytes.first() {
+        Some(b'-') => (true, &bytes[1..]),
+        Some(b'+') => (false, &bytes[1..]),
+        _ => (false, bytes),
+    }
+}
+
+/// Integer written in `bytes`, `None` if it is not one or does not fit
+/// in an `i64`
+#[inline]
+pub(crate) fn parse_i64(bytes: &[u8]) -> Option<i64> {
+    let (negative, digits) = split_sign(bytes);
+    if digits.is_empty() {
+        return None;
+    }
+
+    // Accumulated as a negative number, which reaches `i64::MIN`
+    let mut value: i64 = 0;
+    for byte in digits {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_sub(digit as i64)?;
+    }
+
+    if negative {
+        Some(value)
+    } else {
+        value.checked_neg()
+    }
+}
+
+/// Unsigned integer written in `bytes`, `None` if it is not one or does
+/// not fit in a `u64`
+#[inline]
+pub(crate) fn parse_u64(bytes: &[u8]) -> Option<u64> {
+    let digits = bytes.strip_prefix(b"+").unwrap_or(bytes);
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    for byte in digits {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add(digit as u64)?;
+    }
+    Some(value)
+}
+
+/// Digits of `bytes` accumulated into `mantissa` (while it stays below
+/// `u64::MAX / 10`), returns the total digits read and whether some
+/// were dropped
+#[inline(always)]
+fn read_digits(bytes: &[u8], mantissa: &mut u64) -> (usize, bool) {
+    let mut dropped = false;
+    let count = bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .inspect(|byte| match mantissa.checked_mul(10) {
+            Some(shifted) if *mantissa < u64::MAX / 10 => {
+                *mantissa = shifted + (**byte - b'0') as u64
+            }
+            _ => dropped = true,
+        })
+        .count();
+    (count, dropped)
+}
+
+/// Decimal written in `bytes`, `None` if it is not one
+#[inline]
+pub(crate) fn parse_f64(bytes: &[u8]) -> Option<f64> {
+    let fallback = || std::str::from_utf8(bytes).ok()?.parse().ok();
+    let (negative, rest) = split_sign(bytes);
+
+    let mut mantissa = 0;
+    let (integer_len, dropped) = read_digits(rest, &mut mantissa);
+    let mut rest = &rest[integer_len..];
+
+    let mut fraction_len = 0;
+    let mut dropped_fraction = false;
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        (fraction_len, dropped_fraction) = read_digits(fraction, &mut mantissa);
+        rest = &fraction[fraction_len..];
+    }
+    if integer_len + fraction_len == 0 || dropped || dropped_fraction {
+        // `inf`, `NaN`, or too many digits
+        return fallback();
+    }
+
+    let mut exponent = -(fraction_len as i64);
+    if let Some(written) = rest.strip_prefix(b"e").or(rest.strip_prefix(b"E")) {
+        let (negative_exponent, digits) = split_sign(written);
+        if digits.is_empty() || digits.len() > 4 {
+            return fallback();
+        }
+        let mut value = 0;
+        for byte in digits {
+            if !byte.is_ascii_digit() {
+                return None;
+            }
+            value = value * 10 + (byte - b'0') as i64;
+        }
+        exponent += if negative_exponent { -value } else { value };
+        rest = &[];
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+
+    if mantissa > MAX_EXACT_INTEGER || exponent.unsigned_abs() > 22 {
+        return fallback();
+    }
+
+    let value = mantissa as f64;
+    let value = match exponent {
+        0.. => value * POWERS_OF_TEN[exponent as usize],
+        _ => value / POWERS_OF_TEN[exponent.unsigned_abs() as usize],
+    };
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fields around the limits of the three parsers, and ones that are
+    /// not numbers
+    const FIELDS: [&str; 43] = [
+        "0",
+        "-0",
+        "+0",
+        "",
+        "-",
+        "+",
+        "+-1",
+        "1-",
+        " 1",
+        "9223372036854775807",
+        "9223372036854775808",
+        "-9223372036854775808",
+        "-9223372036854775809",
+        "+9223372036854775807",
+        "18446744073709551615",
+        "18446744073709551616",
+        "-18446744073709551615",
+        "000000000000000000000000001",
+        "9007199254740992",
+        "9007199254740993",
+        "-9007199254740993",
+        "1844674407370955161",
+        "18446744073709551617e-3",
+        "0.1",
+        "0.3",
+        "1.7976931348623157e308",
+        "1.7976931348623159e308",
+        "4.9e-324",
+        "2.4703282292062328e-324",
+        "1e22",
+        "1e23",
+        "1e-22",
+        "1e-23",
+        "123456789012345678901234567890",
+        "0.000000000000000000000000001",
+        "1e99999",
+        "1.",
+        ".5",
+        ".",
+        "1e",
+        "1e+",
+        "1.5e3x",
+        "inf",
+    ];
+
+    #[test]
+    fn integers_match_str_parse() {
+        for field in FIELDS {
+            let bytes = field.as_bytes();
+            assert_eq!(parse_i64(bytes), field.parse().ok(), "{}", field);
+            assert_eq!(parse_u64(bytes), field.parse().ok(), "{}", field);
+        }
+    }
+
+    #[test]
+    fn decimals_match_str_parse() {
+        let extra = ["NaN", "-infinity", "1E5", "-1.5e-3", "2.5E+2"];
+        for field in FIELDS.iter().chain(extra.iter()) {
+            let expected = field.parse::<f64>().ok().map(f64::to_bits);
+            let found = parse_f64(field.as_bytes()).map(f64::to_bits);
+            assert_eq!(found, expected, "{}", field);
+        }
+    }
+
+    #[test]
+    fn decimals_round_at_the_exact_limit() {
+        // 2^53 + 1 is not an `f64`, so the fast path must not take it
+        assert_eq!(parse_f64(b"9007199254740993"), Some(9007199254740992.0));
+        assert_eq!(parse_f64(b"0.1"), Some(0.1));
+        assert_eq!(parse_f64(b"-0"), Some(-0.0));
+        assert!(parse_f64(b"-0").unwrap().is_sign_negative());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs::File;
+use std::io::Write;
+
+use arrow::compute::concat_batches;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::dframe::DataFrame;
+use crate::error::Result;
+
+/// Reader of Parquet files into a `DataFrame`.
+///
+/// Columns are converted through arrow, as described by
+/// [`crate::io::arrow::cell_type`].
+pub struct ParquetReader;
+
+impl ParquetReader {
+    /// Read the whole Parquet file `file_name`
+    pub fn read(file_name: &str) -> Result<DataFrame> {
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(file_name)?)?;
+        let schema = builder.schema().clone();
+
+        let batches = builder
+            .build()?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        DataFrame::from_arrow(&concat_batches(&schema, &batches)?)
+    }
+}
+
+impl DataFrame {
+    /// Write the Data Frame as a single Parquet row group into
+    /// `writer`, compressed with snappy
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<()> {
+        let batch = self.to_arrow()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+
+        let mut writer =
+            ArrowWriter::try_new(writer, batch.schema(), Some(properties))?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    /// Write the Data Frame as Parquet into file `file_name`, replacing
+    /// the file if it exists
+    pub fn to_parquet(&self, file_name: &str) -> Result<()> {
+        self.write_parquet(File::create(file_name)?)
+    }
+}
+
+#[cfg(all(test, feature = "parser"))]
+mod tests {
+    use super::*;
+    use crate::cell::{Cell, CellType};
+    use crate::parser::CsvParser;
+
+    #[test]
+    fn round_trip() {
+        let mut df = CsvParser::parse_buffer(
+            b"id,big,price,label,at,region\n\
+              1,18446744073709551615,2.5,a,12:30:00,north\n\
+              -2,3,,\"b, c\",,south\n\
+              3,4,0.5,,08:00:00,north\n",
+            1,
+        );
+        df.to_categorical("region").unwrap();
+        df.add_column(
+            "flag",
+            vec![Cell::Bool(true), Cell::Null, Cell::Bool(false)],
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir()
+            .join(format!("nn_parquet_{}.parquet", std::process::id()));
+        let file_name = path.to_str().unwrap();
+        df.to_parquet(file_name).unwrap();
+        let back = ParquetReader::read(file_name);
+        std::fs::remove_file(&path).unwrap();
+
+        let back = back.unwrap();
+        assert_eq!(back.header(), df.header());
+        assert_eq!(back.dtypes(), df.dtypes());
+        assert_eq!(back.dtypes()[5], CellType::Categorical);
+        for index in 0..df.hlen() {
+            let texts = |df: &DataFrame| -> Vec<String> {
+                let dictionary = df.dictionary(index);
+                df.iter()
+                    .map(|row| row[index].decoded(dictionary).to_string())
+                    .collect()
+            };
+            assert_eq!(texts(&back), texts(&df), "{}", df.header()[index]);
+        }
+    }
+}
@@ -0,0 +1,638 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use vector::Vector;
+
+use crate::cell::{Cell, CellType};
+use crate::decompress::decompress;
+use crate::dframe::{DataFrame, DiskBackedDataFrame};
+use crate::error::{Error, Result};
+use crate::options::ParsePlan;
+use crate::parser::CsvParser;
+use crate::scan::nth_record_end;
+use crate::warning::ParseWarning;
+
+/// Bytes of the file being read in chunks: either the memory
+/// mapped file, or the decompressed content of the file.
+enum ChunkSource {
+    Mapped(memmap2::Mmap),
+    Decoded(Vec<u8>),
+}
+
+impl ChunkSource {
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmaped) => mmaped,
+            Self::Decoded(decoded) => decoded,
+        }
+    }
+}
+
+/// Chunk of a file read by [`DataFrameChunkIterator`]
+pub struct Chunk {
+    /// Position of the chunk in the file, starting from `0`
+    pub ordinal: usize,
+    /// Bytes of the file holding the rows of the chunk (of the
+    /// decompressed content, for compressed files)
+    pub byte_range: Range<usize>,
+    /// Rows of the chunk
+    pub frame: DataFrame,
+}
+
+/// Order in which chunks parsed on several threads are delivered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkOrder {
+    /// In the order of the file
+    #[default]
+    Ordered,
+    /// As soon as each chunk is parsed, for throughput
+    AsCompleted,
+}
+
+/// File content and schema shared by every chunk
+struct ChunkReader {
+    /// Actual file content
+    source: ChunkSource,
+    /// Header of the file
+    header: Vec<String>,
+    /// Column types unified over the whole file
+    dtype: Vec<CellType>,
+    /// Type the integers of each column holding unsigned numbers turn
+    /// to, decided over the whole file (see
+    /// [`DataFrame::unify_unsigned`])
+    unsigned: Vec<Option<CellType>>,
+    /// Offset of the records in `source`
+    body_start: usize,
+    /// Length of the records section
+    body_len: usize,
+    /// Byte ending a record
+    terminator: u8,
+}
+
+/// Chunk parsed on a worker thread, before it is turned into a
+/// `DataFrame` on the consumer thread
+#[cfg(feature = "threads")]
+struct RawChunk {
+    ordinal: usize,
+    byte_range: Range<usize>,
+    cells: Vec<Cell>,
+    warnings: Vec<ParseWarning>,
+}
+
+impl ChunkReader {
+    /// Range of the next `chunk_rows` rows starting at `offset` (relative
+    /// to the records), `None` past the last row
+    fn next_range(
+        &self,
+        offset: usize,
+        chunk_rows: usize,
+    ) -> Option<Range<usize>> {
+        if offset >= self.body_len || self.header.is_empty() {
+            return None;
+        }
+
+        let remaining = &self.body()[offset..];
+
+        // Seek the end of the `chunk_rows`-th record, which may span
+        // several lines inside quotes
+        let chunk_len =
+            nth_record_end(remaining, self.terminator, chunk_rows - 1)
+                .unwrap_or(remaining.len());
+
+        Some(offset..offset + chunk_len)
+    }
+
+    #[inline(always)]
+    fn body(&self) -> &[u8] {
+        &self.source.as_slice()
+            [self.body_start..self.body_start + self.body_len]
+    }
+
+    /// Parse the records in `range` (relative to the records)
+    fn parse(&self, range: Range<usize>) -> (Vector<Cell>, Vec<ParseWarning>) {
+        let plan = ParsePlan {
+            terminator: self.terminator,
+            ..ParsePlan::identity(self.header.len())
+        };
+        let (cells, _, warnings) =
+            CsvParser::parse_body(&self.body()[range], &plan, 1);
+        (cells, warnings)
+    }
+
+    /// Data Frame of the `cells` of a chunk, with the shared schema
+    fn frame(
+        &self,
+        cells: Vector<Cell>,
+        warnings: Vec<ParseWarning>,
+    ) -> DataFrame {
+        let mut dtype = Vector::zeroed(self.dtype.len());
+        dtype.copy_from_slice(&self.dtype);
+
+        self.unify(DataFrame::new(cells, self.header.clone(), dtype), warnings)
+    }
+
+    /// Chunk `df` with its `warnings`, its unsigned columns converted
+    /// as decided over the whole file
+    fn unify(
+        &self,
+        mut df: DataFrame,
+        warnings: Vec<ParseWarning>,
+    ) -> DataFrame {
+        df = df.with_warnings(warnings);
+        df.unify_unsigned_to(&self.unsigned);
+        df
+    }
+
+    /// Range of the chunk in the file, from a range of the records
+    #[inline(always)]
+    fn file_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.body_start + range.start..self.body_start + range.end
+    }
+}
+
+/// Custom iterator for reading a file in chunks of `DataFrame`:
+/// `DataFrameChunkIterator`
+///
+/// Each chunk holds at most `chunk_rows` rows and shares the same
+/// header and column types, inferred from the whole file before the
+/// first chunk is read.
+pub struct DataFrameChunkIterator {
+    /// File content and schema
+    reader: Arc<ChunkReader>,
+    /// Offset of the next chunk, relative to the records
+    offset: usize,
+    /// Ordinal of the next chunk
+    ordinal: usize,
+    /// Total rows per chunk
+    chunk_rows: usize,
+}
+
+impl DataFrameChunkIterator {
+    /// Create chunk iterator over the file `file_name`
+    #[inline]
+    pub fn new(file_name: &str, chunk_rows: usize) -> Result<Self> {
+        Self::with_terminator(file_name, chunk_rows, b'\n')
+    }
+
+    /// Create chunk iterator over the file `file_name`, whose records
+    /// end with `terminator` (see [`ParserOptions::record_terminator`]).
+    /// Fails with [`Error::InvalidArgument`] if `chunk_rows` is zero.
+    ///
+    /// [`ParserOptions::record_terminator`]: crate::options::ParserOptions::record_terminator
+    pub fn with_terminator(
+        file_name: &str,
+        chunk_rows: usize,
+        terminator: u8,
+    ) -> Result<Self> {
+        if chunk_rows == 0 {
+            return Err(Error::InvalidArgument(
+                "chunk_rows should be greater than zero".to_owned(),
+            ));
+        }
+
+        let fd = std::fs::OpenOptions::new().read(true).open(file_name)?;
+        let mmaped = unsafe { memmap2::MmapOptions::new().map(&fd)? };
+
+        let source = match decompress(&mmaped)? {
+            Some(decoded) => ChunkSource::Decoded(decoded),
+            None => ChunkSource::Mapped(mmaped),
+        };
+
+        let buffer = source.as_slice();
+        let (header, body) = CsvParser::split_header(buffer, terminator);
+        let body_start = body.as_ptr() as usize - buffer.as_ptr() as usize;
+
+        // Types are inferred over the whole file, so that every chunk
+        // reports the same schema.
+        let mut dtype = vec![CellType::Null; header.len()];
+        let mut negative = vec![false; header.len()];
+        if !header.is_empty() {
+            CsvParser::new(body)
+                .with_terminator(terminator)
+                .infer_types_on_buffer(&mut dtype, &mut negative);
+        }
+        // Unsigned columns with a negative integer anywhere in the file
+        // are decimals in every chunk
+        let unsigned = dtype
+            .iter_mut()
+            .zip(negative)
+            .map(|(dtype, negative)| {
+                if *dtype != CellType::U64 {
+                    return None;
+                }
+                if negative {
+                    *dtype = CellType::F64;
+                }
+                Some(*dtype)
+            })
+            .collect();
+
+        Ok(Self {
+            reader: Arc::new(ChunkReader {
+                body_len: body.len(),
+                source,
+                header,
+                dtype,
+                unsigned,
+                body_start,
+                terminator,
+            }),
+            offset: 0,
+            ordinal: 0,
+            chunk_rows,
+        })
+    }
+
+    /// Header shared by all the chunks
+    #[inline(always)]
+    pub fn header(&self) -> &[String] {
+        &self.reader.header
+    }
+
+    /// Column types shared by all the chunks
+    #[inline(always)]
+    pub fn dtypes(&self) -> &[CellType] {
+        &self.reader.dtype
+    }
+
+    /// Next chunk, tagged with its ordinal and byte range
+    pub fn next_chunk(&mut self) -> Option<Chunk> {
+        let range = self.reader.next_range(self.offset, self.chunk_rows)?;
+        self.offset = range.end + 1;
+
+        let (cells, warnings) = self.reader.parse(range.clone());
+        let ordinal = self.ordinal;
+        self.ordinal += 1;
+
+        Some(Chunk {
+            ordinal,
+            byte_range: self.reader.file_range(&range),
+            frame: self.reader.frame(cells, warnings),
+        })
+    }
+
+    /// Iterator of the remaining chunks, tagged with their ordinal and
+    /// byte range
+    #[inline]
+    pub fn tagged(mut self) -> impl Iterator<Item = Chunk> {
+        std::iter::from_fn(move || self.next_chunk())
+    }
+
+    /// Write the remaining chunks to disk as they are parsed, so that
+    /// files larger than the memory can be scanned: only one chunk is
+    /// in memory at a time. See [`DiskBackedDataFrame`].
+    #[inline]
+    pub fn spill(self) -> Result<DiskBackedDataFrame> {
+        DiskBackedDataFrame::from_chunks(self)
+    }
+
+    /// Parse the remaining chunks on `threads` threads, delivering them
+    /// in `order`.
+    ///
+    /// At most a few chunks per thread are parsed ahead of the consumer;
+    /// with [`ChunkOrder::Ordered`] chunks completed early are held
+    /// until the chunks before them are delivered, and no chunk further
+    /// than that many chunks past the next one to deliver is started, so
+    /// that a slow chunk does not let the held chunks grow.
+    #[cfg(feature = "threads")]
+    pub fn parallel(self, threads: usize, order: ChunkOrder) -> ParallelChunks {
+        ParallelChunks::new(self, threads.max(1), order)
+    }
+}
+
+impl Iterator for DataFrameChunkIterator {
+    type Item = DataFrame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().map(|chunk| chunk.frame)
+    }
+}
+
+/// Next chunk to claim by the workers of [`ParallelChunks`]
+#[cfg(feature = "threads")]
+struct ChunkCursor {
+    /// Offset of the next chunk, relative to the records
+    offset: usize,
+    /// Ordinal of the next chunk
+    ordinal: usize,
+    /// Ordinal of the next chunk to deliver to the consumer
+    delivered: usize,
+    /// Whether the consumer was dropped
+    closed: bool,
+}
+
+/// Chunks of a file parsed on several threads, see
+/// [`DataFrameChunkIterator::parallel`]
+#[cfg(feature = "threads")]
+pub struct ParallelChunks {
+    /// File content and schema
+    reader: Arc<ChunkReader>,
+    /// Cursor shared with the workers, and its wake up signal
+    cursor: Arc<(std::sync::Mutex<ChunkCursor>, std::sync::Condvar)>,
+    /// Chunks sent by the workers
+    receiver: std::sync::mpsc::Receiver<RawChunk>,
+    /// Chunks received before the chunks preceding them
+    pending: std::collections::BTreeMap<usize, RawChunk>,
+    /// Ordinal of the next chunk to deliver, in ordered mode
+    next_ordinal: usize,
+    /// Delivery order
+    order: ChunkOrder,
+}
+
+#[cfg(feature = "threads")]
+impl ParallelChunks {
+    fn new(
+        chunks: DataFrameChunkIterator,
+        threads: usize,
+        order: ChunkOrder,
+    ) -> Self {
+        let DataFrameChunkIterator {
+            reader,
+            offset,
+            ordinal,
+            chunk_rows,
+        } = chunks;
+
+        // Workers claim the next chunk under the lock, and parse it
+        // outside of it. In order, they wait for the consumer before
+        // claiming a chunk too far ahead of the next one to deliver.
+        let cursor = Arc::new((
+            std::sync::Mutex::new(ChunkCursor {
+                offset,
+                ordinal,
+                delivered: ordinal,
+                closed: false,
+            }),
+            std::sync::Condvar::new(),
+        ));
+        let ahead = match order {
+            ChunkOrder::Ordered => threads * 2,
+            ChunkOrder::AsCompleted => usize::MAX,
+        };
+        let (sender, receiver) = std::sync::mpsc::sync_channel(threads * 2);
+
+        for _ in 0..threads {
+            let (reader, cursor, sender) =
+                (Arc::clone(&reader), Arc::clone(&cursor), sender.clone());
+
+            std::thread::spawn(move || loop {
+                let (ordinal, range) = {
+                    let (lock, wake_up) = &*cursor;
+                    let mut cursor = wake_up
+                        .wait_while(lock.lock().unwrap(), |cursor| {
+                            !cursor.closed
+                                && cursor.ordinal - cursor.delivered >= ahead
+                        })
+                        .unwrap();
+                    if cursor.closed {
+                        break;
+                    }
+                    let Some(range) =
+                        reader.next_range(cursor.offset, chunk_rows)
+                    else {
+                        break;
+                    };
+                    cursor.offset = range.end + 1;
+                    cursor.ordinal += 1;
+                    (cursor.ordinal - 1, range)
+                };
+
+                let (mut cells, warnings) = reader.parse(range.clone());
+                let chunk = RawChunk {
+                    ordinal,
+                    byte_range: range,
+                    cells: cells
+                        .iter_mut()
+                        .map(|cell| std::mem::replace(cell, Cell::Null))
+                        .collect(),
+                    warnings,
+                };
+
+                // The consumer was dropped, no need to parse further
+                if sender.send(chunk).is_err() {
+                    break;
+                }
+            });
+        }
+
+        Self {
+            reader,
+            cursor,
+            receiver,
+            pending: std::collections::BTreeMap::new(),
+            next_ordinal: ordinal,
+            order,
+        }
+    }
+
+    /// Next raw chunk in the delivery order
+    fn next_raw(&mut self) -> Option<RawChunk> {
+        if self.order == ChunkOrder::AsCompleted {
+            return self.receiver.recv().ok();
+        }
+
+        loop {
+            if let Some(chunk) = self.pending.remove(&self.next_ordinal) {
+                self.next_ordinal += 1;
+                let (lock, wake_up) = &*self.cursor;
+                lock.lock().unwrap().delivered = self.next_ordinal;
+                wake_up.notify_all();
+                return Some(chunk);
+            }
+            let chunk = self.receiver.recv().ok()?;
+            self.pending.insert(chunk.ordinal, chunk);
+        }
+    }
+}
+
+#[cfg(feature = "threads")]
+impl Drop for ParallelChunks {
+    /// Stop the workers waiting for the consumer
+    fn drop(&mut self) {
+        let (lock, wake_up) = &*self.cursor;
+        if let Ok(mut cursor) = lock.lock() {
+            cursor.closed = true;
+        }
+        wake_up.notify_all();
+    }
+}
+
+#[cfg(feature = "threads")]
+impl Iterator for ParallelChunks {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.next_raw()?;
+        let frame = DataFrame::from_cells(
+            chunk.cells,
+            self.reader.header.clone(),
+            &self.reader.dtype,
+        );
+
+        Some(Chunk {
+            ordinal: chunk.ordinal,
+            byte_range: self.reader.file_range(&chunk.byte_range),
+            frame: self.reader.unify(frame, chunk.warnings),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chunks of `csv` written to a file of its own, read back
+    fn read_chunks(
+        name: &str,
+        csv: &[u8],
+        chunk_rows: usize,
+        terminator: u8,
+    ) -> Vec<DataFrame> {
+        let path = std::env::temp_dir().join(format!(
+            "nn_chunk_iter_{}_{}.csv",
+            name,
+            std::process::id()
+        ));
+        std::fs::write(&path, csv).unwrap();
+        let chunks = DataFrameChunkIterator::with_terminator(
+            path.to_str().unwrap(),
+            chunk_rows,
+            terminator,
+        )
+        .unwrap()
+        .collect();
+        std::fs::remove_file(path).unwrap();
+        chunks
+    }
+
+    #[test]
+    fn zero_chunk_rows_is_an_error() {
+        let chunks = DataFrameChunkIterator::new("missing.csv", 0);
+        assert!(matches!(chunks, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn quoted_line_end_at_chunk_boundary() {
+        let csv = b"id,note\n1,\"first\nline\"\n2,plain\n3,\"a,\nb\"\n";
+        let chunks = read_chunks("quoted", csv, 1, b'\n');
+        assert_eq!(chunks.len(), 3);
+
+        let notes: Vec<_> = chunks
+            .iter()
+            .map(|chunk| {
+                assert_eq!(chunk.len(), 1);
+                chunk.column("note").unwrap().as_str()[0].map(str::to_owned)
+            })
+            .collect();
+        assert_eq!(
+            notes,
+            [
+                Some("first\nline".to_owned()),
+                Some("plain".to_owned()),
+                Some("a,\nb".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn unsigned_columns_share_the_schema() {
+        let csv = b"id\n18446744073709551615\n1\n-1\n";
+        let chunks = read_chunks("unsigned", csv, 1, b'\n');
+        for chunk in &chunks {
+            assert_eq!(chunk.dtypes(), [CellType::F64]);
+        }
+        let values: Vec<_> = chunks
+            .iter()
+            .map(|chunk| chunk.column("id").unwrap().as_f64()[0])
+            .collect();
+        assert_eq!(
+            values,
+            [Some(18446744073709551615.0), Some(1.0), Some(-1.0)]
+        );
+        assert_eq!(chunks[0].warnings().len(), 1);
+
+        let chunks = read_chunks(
+            "unsigned_only",
+            b"id\n18446744073709551615\n1\n",
+            1,
+            b'\n',
+        );
+        for chunk in &chunks {
+            assert_eq!(chunk.dtypes(), [CellType::U64]);
+        }
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn parallel_chunks_match_sequential_ones() {
+        let csv = b"id,name\n18446744073709551615,a\n1,b\n-1,c\n";
+        let path = std::env::temp_dir()
+            .join(format!("nn_chunk_iter_parallel_{}.csv", std::process::id()));
+        std::fs::write(&path, csv).unwrap();
+        let file_name = path.to_str().unwrap();
+
+        let sequential: Vec<_> =
+            DataFrameChunkIterator::new(file_name, 1).unwrap().collect();
+        let parallel: Vec<_> = DataFrameChunkIterator::new(file_name, 1)
+            .unwrap()
+            .parallel(2, ChunkOrder::Ordered)
+            .map(|chunk| chunk.frame)
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(sequential.len(), parallel.len());
+        for (sequential, parallel) in sequential.iter().zip(&parallel) {
+            assert_eq!(sequential.dtypes(), parallel.dtypes());
+            assert_eq!(
+                sequential.column("id").unwrap().as_f64(),
+                parallel.column("id").unwrap().as_f64()
+            );
+            assert_eq!(sequential.warnings().len(), parallel.warnings().len());
+        }
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn ordered_parallel_chunks_hold_a_bounded_backlog() {
+        let csv: Vec<u8> = (0..64).fold(b"id\n".to_vec(), |mut csv, id| {
+            csv.extend(format!("{}\n", id).bytes());
+            csv
+        });
+        let path = std::env::temp_dir()
+            .join(format!("nn_chunk_iter_backlog_{}.csv", std::process::id()));
+        std::fs::write(&path, csv).unwrap();
+
+        let mut chunks = DataFrameChunkIterator::new(path.to_str().unwrap(), 1)
+            .unwrap()
+            .parallel(2, ChunkOrder::Ordered);
+        let mut ordinals = Vec::new();
+        for _ in 0..32 {
+            let chunk = chunks.next().unwrap();
+            assert!(chunks.pending.len() <= 4);
+            ordinals.push(chunk.ordinal);
+        }
+        // Dropping the chunks early stops the waiting workers
+        drop(chunks);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(ordinals, (0..32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn custom_terminator() {
+        let csv = b"id,note\r1,\"a\rb\"\r2,c\r3,d\r";
+        let chunks = read_chunks("terminator", csv, 2, b'\r');
+        assert_eq!(
+            chunks.iter().map(DataFrame::len).collect::<Vec<_>>(),
+            [2, 1]
+        );
+        assert_eq!(chunks[0].header(), ["id", "note"]);
+        assert_eq!(
+            chunks[0].column("id").unwrap().as_i64(),
+            [Some(1), Some(2)]
+        );
+        assert_eq!(chunks[1].column("note").unwrap().as_str(), [Some("d")]);
+    }
+}
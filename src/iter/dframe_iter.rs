@@ -39,19 +39,18 @@ impl<'a> Iterator for DataFrameIterator<'a> {
 
     #[inline(always)]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remainder_size =
-            (self.data_frame.len() - self.index) / self.col_size;
+        let remainder_size = (self.data_frame.len() - self.index)
+            .checked_div(self.col_size)
+            .unwrap_or(0);
         (remainder_size, Some(remainder_size))
     }
 
     #[inline(always)]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        if n >= self.data_frame.len() / self.col_size {
-            None
-        } else {
-            let start = n * self.col_size;
-            let cell_slice = &self.data_frame[start..start + self.col_size];
-            Some(cell_slice)
-        }
+        self.index = n
+            .saturating_mul(self.col_size)
+            .saturating_add(self.index)
+            .min(self.data_frame.len());
+        self.next()
     }
 }
@@ -1,4 +1,8 @@
+#[cfg(feature = "parser")]
+pub mod chunk_iter;
 pub mod dframe_iter;
+#[cfg(feature = "serde")]
+pub mod typed_iter;
 use crate::cell::*;
 
 /// Custom iterator for column type `DataFrame`:
@@ -40,19 +44,22 @@ impl<'a> Iterator for DataFrameColumnIterator<'a> {
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remainder_size =
-            (self.data_frame.len() - self.index) / self.col_size;
+        // The index steps past the end after the last cell of any
+        // column but the first
+        let remainder_size = self
+            .data_frame
+            .len()
+            .saturating_sub(self.index)
+            .div_ceil(self.col_size);
         (remainder_size, Some(remainder_size))
     }
 
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        if n >= self.data_frame.len() / self.col_size {
-            None
-        } else {
-            let start = n * self.col_size + self.index;
-            let cell_slice = &self.data_frame[start];
-            Some(cell_slice)
-        }
+        self.index = n
+            .saturating_mul(self.col_size)
+            .saturating_add(self.index)
+            .min(self.data_frame.len());
+        self.next()
     }
 }
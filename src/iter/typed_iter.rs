@@ -0,0 +1,246 @@
+use std::marker::PhantomData;
+
+use serde::de::value::{Error as DeError, MapDeserializer, SeqDeserializer};
+use serde::de::{Deserialize, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::cell::Cell;
+use crate::dframe::DataFrame;
+use crate::error::{Error, Result};
+use crate::iter::dframe_iter::DataFrameIterator;
+
+/// Deserializer of a single cell.
+///
+/// Nulls are `None` (or unit), strings can be read as booleans
+/// (`true`/`false`) and enum unit variants, dates, times and durations
+/// are read as their text or as the types of [`crate::datetime`].
+/// Categories, coded in the dictionary of their column, are read as
+/// the strings they stand for.
+#[derive(Clone, Copy)]
+struct CellDeserializer<'a>(&'a Cell, Option<&'a [String]>);
+
+impl<'de> IntoDeserializer<'de, DeError> for CellDeserializer<'de> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'a> CellDeserializer<'a> {
+    /// Text of the cell, for strings and categories
+    #[inline(always)]
+    fn text(self) -> Option<&'a str> {
+        self.0.text(self.1)
+    }
+}
+
+impl<'de> Deserializer<'de> for CellDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        if let (Cell::Categorical(_), Some(text)) = (self.0, self.text()) {
+            return visitor.visit_borrowed_str(text);
+        }
+        match self.0 {
+            Cell::Null => visitor.visit_unit(),
+            Cell::String(string) => visitor.visit_borrowed_str(string),
+            Cell::Number(number) => visitor.visit_i64(*number),
+            Cell::UInt(number) => visitor.visit_u64(*number),
+            Cell::Decimal128(decimal) => visitor.visit_f64(decimal.to_f64()),
+            Cell::Decimal(decimal) => visitor.visit_f64(*decimal),
+            Cell::Date(date) => visitor.visit_string(date.to_string()),
+            Cell::Time(time) => visitor.visit_string(time.to_string()),
+            Cell::Duration(duration) => {
+                visitor.visit_string(duration.to_string())
+            }
+            Cell::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cell::Bool(value) => visitor.visit_bool(*value),
+            Cell::Categorical(code) => visitor.visit_u32(*code),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        match self.0 {
+            Cell::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        match self.text() {
+            Some(text) if text.eq_ignore_ascii_case("true") => {
+                visitor.visit_bool(true)
+            }
+            Some(text) if text.eq_ignore_ascii_case("false") => {
+                visitor.visit_bool(false)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        match (name, self.0) {
+            ("Date", Cell::Date(date)) => visitor.visit_newtype_struct(
+                date.days_since_epoch().into_deserializer(),
+            ),
+            ("Time", Cell::Time(time)) => visitor.visit_newtype_struct(
+                time.nanos_since_midnight().into_deserializer(),
+            ),
+            ("Duration", Cell::Duration(duration)) => visitor
+                .visit_newtype_struct(duration.as_nanos().into_deserializer()),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        match self.text() {
+            Some(text) => visitor.visit_enum(text.into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Deserializer of a row: a map keyed by the header for structs and
+/// maps, or a sequence of cells for tuples
+struct RowDeserializer<'a> {
+    df: &'a DataFrame,
+    row: &'a [Cell],
+}
+
+impl<'a> RowDeserializer<'a> {
+    /// Deserializer of each cell of the row
+    #[inline]
+    fn cells(&self) -> impl Iterator<Item = CellDeserializer<'a>> + 'a {
+        let df = self.df;
+        self.row.iter().enumerate().map(move |(index, cell)| {
+            CellDeserializer(cell, df.dictionary(index))
+        })
+    }
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let mut map = MapDeserializer::<_, DeError>::new(
+            self.df
+                .header()
+                .iter()
+                .map(String::as_str)
+                .zip(self.cells()),
+        );
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let mut seq = SeqDeserializer::<_, DeError>::new(self.cells());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct map struct
+        enum identifier ignored_any
+    }
+}
+
+/// Iterator over the rows of a `DataFrame` deserialized into `T`,
+/// created by [`crate::dframe::DataFrame::iter_as`]
+pub struct DataFrameTypedIterator<'a, T> {
+    /// Data frame read, whose header is matched with the fields of `T`
+    df: &'a DataFrame,
+    /// Rows of the data frame
+    rows: DataFrameIterator<'a>,
+    /// Index of the next row
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> DataFrameTypedIterator<'a, T> {
+    #[inline(always)]
+    pub(crate) fn new(df: &'a DataFrame, rows: DataFrameIterator<'a>) -> Self {
+        Self {
+            df,
+            rows,
+            index: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Iterator for DataFrameTypedIterator<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        let index = self.index;
+        self.index += 1;
+
+        Some(
+            T::deserialize(RowDeserializer { df: self.df, row }).map_err(
+                |err| Error::Deserialize {
+                    row: index,
+                    message: err.to_string(),
+                },
+            ),
+        )
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
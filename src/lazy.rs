@@ -0,0 +1,261 @@
+//! Lazy queries over CSV files.
+//!
+//! A [`LazyFrame`] records the steps of a query without reading the
+//! file. When collected, the plan is optimized before parsing:
+//! - only the columns needed by the query are read (projection
+//!   pushdown),
+//! - conditions on a single column run while the records are scanned,
+//!   so that rejected rows are never stored (predicate pushdown).
+//!
+//! ```no_run
+//! use nn::lazy::scan_csv;
+//!
+//! let df = scan_csv("sales.csv")
+//!     .filter("region == \"North\"")?
+//!     .select(&["item", "price"])
+//!     .filter("price > 100")?
+//!     .collect()?;
+//! # Ok::<(), nn::error::Error>(())
+//! ```
+
+use crate::dframe::{DataFrame, Expr};
+use crate::error::{Error, Result};
+use crate::options::ParserOptions;
+use crate::parser::CsvParser;
+
+/// Step of the logical plan of a [`LazyFrame`]
+#[derive(Clone, Debug)]
+enum Step {
+    /// Keep the columns, in this order
+    Select(Vec<String>),
+    /// Keep the rows passing the condition
+    Filter(Expr),
+}
+
+/// Query over a CSV file, read when [`collect`](Self::collect)ed.
+/// Created by [`scan_csv`].
+#[derive(Clone, Debug)]
+pub struct LazyFrame {
+    /// Path of the file
+    path: String,
+    /// Options the file is read with
+    options: ParserOptions,
+    /// Threads parsing the file
+    threads: usize,
+    /// Steps applied to the rows of the file, in order
+    steps: Vec<Step>,
+}
+
+/// Query over the CSV file at `path`, read with the default options
+#[inline]
+pub fn scan_csv(path: &str) -> LazyFrame {
+    LazyFrame::scan_csv(path, ParserOptions::default())
+}
+
+/// Plan of a [`LazyFrame`] once optimized
+struct Optimized<'a> {
+    /// Columns read from the file, all if `None`
+    columns: Option<Vec<String>>,
+    /// Column and conditions run while scanning
+    pushed: Option<(String, Vec<&'a Expr>)>,
+    /// Steps run on the parsed Data Frame
+    steps: Vec<&'a Step>,
+}
+
+impl LazyFrame {
+    /// Query over the CSV file at `path`, read with `options`. The
+    /// columns and filter of `options` are kept as they are, so nothing
+    /// is pushed into them.
+    #[inline]
+    pub fn scan_csv(path: &str, options: ParserOptions) -> Self {
+        Self {
+            path: path.to_owned(),
+            options,
+            threads: 1,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Parse the file on `threads` threads
+    #[inline]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Keep the columns `columns`, in this order
+    #[inline]
+    pub fn select(mut self, columns: &[&str]) -> Self {
+        self.steps.push(Step::Select(
+            columns.iter().map(|c| (*c).to_owned()).collect(),
+        ));
+        self
+    }
+
+    /// Keep the rows passing the condition `expr`, written in the
+    /// syntax of [`Expr`]
+    #[inline]
+    pub fn filter(self, expr: &str) -> Result<Self> {
+        Ok(self.filter_expr(Expr::parse(expr)?))
+    }
+
+    /// Keep the rows passing the condition `expr`
+    #[inline]
+    pub fn filter_expr(mut self, expr: Expr) -> Self {
+        self.steps.push(Step::Filter(expr));
+        self
+    }
+
+    /// Plan with the columns and conditions pushed into the parser
+    fn optimize(&self) -> Optimized<'_> {
+        // Columns needed before the first projection
+        let columns = self.options.use_columns.is_none().then(|| {
+            let mut columns: Vec<String> = Vec::new();
+            for step in &self.steps {
+                let used = match step {
+                    Step::Select(selected) => selected,
+                    Step::Filter(expr) => expr.columns(),
+                };
+                for name in used {
+                    if !columns.contains(name) {
+                        columns.push(name.clone());
+                    }
+                }
+                if let Step::Select(_) = step {
+                    return Some(columns);
+                }
+            }
+            None
+        });
+
+        // Conditions on the same single column
+        let mut pushed: Option<(String, Vec<&Expr>)> = None;
+        let mut steps = Vec::with_capacity(self.steps.len());
+        // Columns kept by the projections so far, filters on other
+        // columns stay after them to fail as expected
+        let mut selected: Vec<&Vec<String>> = Vec::new();
+        for step in &self.steps {
+            let single = match step {
+                Step::Select(columns) => {
+                    selected.push(columns);
+                    None
+                }
+                Step::Filter(expr) if self.options.filter.is_none() => {
+                    match expr.columns() {
+                        [column]
+                            if selected.iter().all(|s| s.contains(column)) =>
+                        {
+                            Some((column, expr))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            match (single, &mut pushed) {
+                (Some((column, expr)), None) => {
+                    pushed = Some((column.clone(), vec![expr]))
+                }
+                (Some((column, expr)), Some((key, exprs))) if key == column => {
+                    exprs.push(expr)
+                }
+                _ => steps.push(step),
+            }
+        }
+
+        Optimized {
+            columns: columns.flatten(),
+            pushed,
+            steps,
+        }
+    }
+
+    /// Description of the optimized plan, one step per line
+    ///
+    /// ```
+    /// use nn::lazy::scan_csv;
+    ///
+    /// let plan = scan_csv("sales.csv")
+    ///     .filter("price > 100")
+    ///     .unwrap()
+    ///     .select(&["item"])
+    ///     .explain();
+    /// assert_eq!(
+    ///     plan,
+    ///     "scan sales.csv\n  columns: price, item\n  filter on price: 1 condition\nselect item\n"
+    /// );
+    /// ```
+    pub fn explain(&self) -> String {
+        let plan = self.optimize();
+
+        let mut text = format!("scan {}\n", self.path);
+        if let Some(columns) = &plan.columns {
+            text += &format!("  columns: {}\n", columns.join(", "));
+        }
+        if let Some((column, exprs)) = &plan.pushed {
+            let plural = if exprs.len() == 1 { "" } else { "s" };
+            text += &format!(
+                "  filter on {}: {} condition{}\n",
+                column,
+                exprs.len(),
+                plural
+            );
+        }
+        for step in plan.steps {
+            text += &match step {
+                Step::Select(columns) => {
+                    format!("select {}\n", columns.join(", "))
+                }
+                Step::Filter(expr) => {
+                    format!("filter on {}\n", expr.columns().join(", "))
+                }
+            };
+        }
+        text
+    }
+
+    /// Read the file and run the query.
+    ///
+    /// Fails if the file cannot be read, if a column is not found or if
+    /// a filter is not a condition.
+    pub fn collect(self) -> Result<DataFrame> {
+        if let Some(Step::Filter(expr)) = self
+            .steps
+            .iter()
+            .find(|step| matches!(step, Step::Filter(e) if !e.is_condition()))
+        {
+            return Err(Error::InvalidQuery {
+                position: 0,
+                message: format!(
+                    "expected a condition on {}",
+                    expr.columns().join(", ")
+                ),
+            });
+        }
+
+        let plan = self.optimize();
+        let mut options = self.options.clone();
+        if let Some(columns) = &plan.columns {
+            let columns: Vec<&str> =
+                columns.iter().map(String::as_str).collect();
+            options = options.use_columns(&columns);
+        }
+        if let Some((column, exprs)) = &plan.pushed {
+            let exprs: Vec<Expr> = exprs.iter().map(|e| (*e).clone()).collect();
+            options = options.filter(column, move |cell| {
+                let row = std::slice::from_ref(cell);
+                exprs.iter().all(|expr| expr.test(row, &[0], &[]))
+            });
+        }
+
+        let mut df =
+            CsvParser::parse_with_options(&self.path, self.threads, &options)?;
+        for step in plan.steps {
+            df = match step {
+                Step::Select(columns) => df.select(columns)?,
+                Step::Filter(expr) => df.filter_expr(expr)?,
+            };
+        }
+        Ok(df)
+    }
+}
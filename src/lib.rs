@@ -0,0 +1,90 @@
+//! Workspace for parsing, modifying and working on datasets for
+//! neural network.
+//!
+//! ## Features
+//! - `parser` (default): CSV parser producing a `DataFrame`
+//! - `dataframe` (default): `DataFrame` and its iterators
+//! - `threads` (default): parse on multiple threads
+//! - `io-json`, `io-parquet`: JSON and Parquet support
+//! - `regex`: column selection by regular expression
+//! - `arrow`: conversion from and to arrow `RecordBatch`
+//! - `serde`: serialization of cells and `DataFrame`, rows as JSON values
+//! - `gzip`, `zstd`: transparent decompression of the input
+//!
+//! The tokenizer ([`parse_state`]) has no dependency and is always
+//! available.
+//!
+//! The commonly used types are available from [`prelude`]:
+//!
+//! ```no_run
+//! # #[cfg(feature = "parser")] {
+//! use nn::prelude::*;
+//!
+//! fn main() -> Result<()> {
+//!     let df = CsvParser::parse_multi_threaded("sample.csv", 4)?;
+//!     println!("{:?} {:?}", df.header(), df.dtypes());
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+#[cfg(feature = "parser")]
+pub mod cancel;
+pub mod cell;
+pub mod datetime;
+pub mod decimal;
+pub mod decompress;
+#[cfg(feature = "dataframe")]
+pub mod dframe;
+#[cfg(feature = "parser")]
+pub mod diagnostic;
+pub mod encoding;
+pub mod error;
+#[cfg(feature = "parser")]
+mod glob;
+#[cfg(feature = "parser")]
+pub mod intern;
+pub mod io;
+#[cfg(feature = "dataframe")]
+pub mod iter;
+#[cfg(feature = "parser")]
+pub mod lazy;
+#[cfg(feature = "parser")]
+pub mod options;
+pub mod parse_state;
+#[cfg(feature = "parser")]
+pub mod parser;
+#[cfg(feature = "parser")]
+mod scan;
+#[cfg(feature = "parser")]
+pub mod sidecar;
+#[cfg(feature = "dataframe")]
+pub mod threads;
+pub mod warning;
+
+/// Re-exports of the commonly used types
+pub mod prelude {
+    pub use crate::cell::{Cell, CellType};
+    pub use crate::datetime::{Date, DateOrder, Duration, Time};
+    pub use crate::decimal::Decimal128;
+    #[cfg(feature = "dataframe")]
+    pub use crate::dframe::{
+        Agg, Column, DataFrame, FillStrategy, JoinType, Keep, NullOrder,
+        NullPolicy, SortOrder,
+    };
+    pub use crate::encoding::BytesEncoding;
+    pub use crate::error::{Error, Result};
+    #[cfg(feature = "dataframe")]
+    pub use crate::io::csv::{CsvWriterOptions, QuoteStyle};
+    #[cfg(feature = "io-json")]
+    pub use crate::io::json::Orientation;
+    #[cfg(feature = "io-parquet")]
+    pub use crate::io::parquet::ParquetReader;
+    #[cfg(feature = "parser")]
+    pub use crate::iter::chunk_iter::{Chunk, ChunkOrder};
+    #[cfg(feature = "parser")]
+    pub use crate::options::{DecimalMark, ParserOptions, RaggedRows};
+    #[cfg(feature = "parser")]
+    pub use crate::parser::CsvParser;
+    pub use crate::warning::{ParseWarning, WarningKind};
+}
@@ -0,0 +1,1551 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cancel::CancelToken;
+use crate::cell::{Cell, CellType};
+use crate::datetime::{Date, DateOrder};
+use crate::decimal::Decimal128;
+use crate::encoding::BytesEncoding;
+use crate::error::{Error, Result};
+use crate::intern::StringPool;
+use crate::io::number::{parse_f64, parse_i64};
+use crate::parser::CsvParser;
+use crate::scan::{nth_record_end, QUOTE, SEPARATOR};
+#[cfg(feature = "threads")]
+use crate::threads::ThreadPool;
+use crate::warning::WarningKind;
+
+/// Options controlling which records are read by the parser and
+/// how they are read.
+///
+/// Options are built by chaining:
+///
+/// ```
+/// use nn::options::ParserOptions;
+///
+/// let options = ParserOptions::new()
+///     .skip_records(100)
+///     .nrows(20)
+///     .use_columns(&["region", "sales"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParserOptions {
+    /// Total data rows to read after the skipped ones, all if `None`
+    pub(crate) nrows: Option<usize>,
+    /// Total data rows to skip right after the header
+    pub(crate) skip_records: usize,
+    /// Names of the columns to read, all if `None`
+    pub(crate) use_columns: Option<Vec<String>>,
+    /// Rows to keep, decided on the cell of a key column
+    pub(crate) filter: Option<RowFilter>,
+    /// Explicit date format of some columns, as `(column, format)`
+    pub(crate) date_formats: Vec<(String, String)>,
+    /// Order used to infer dates in other columns, no inference if
+    /// `None`
+    pub(crate) date_order: Option<DateOrder>,
+    /// Whether times of day and durations are inferred in text fields
+    pub(crate) temporal: bool,
+    /// Columns holding encoded binary payloads, as `(column, encoding)`
+    pub(crate) bytes_columns: Vec<(String, BytesEncoding)>,
+    /// Integer columns whose invalid fields are read as nulls
+    pub(crate) checked_integers: Vec<String>,
+    /// Columns read as exact decimals
+    pub(crate) exact_decimals: Vec<String>,
+    /// Columns holding formatted numbers, as `(column, decimal mark)`
+    pub(crate) number_formats: Vec<(String, DecimalMark)>,
+    /// Functions reading the cells of some columns
+    pub(crate) converters: Vec<Converter>,
+    /// Whether the line after the header holds the unit of each column
+    pub(crate) units_row: bool,
+    /// Total lines to drop at the end of the file
+    pub(crate) skip_footer: usize,
+    /// Line of the file holding the header, lines above it are dropped
+    pub(crate) header_row: usize,
+    /// Total lines making up the header, and the separator joining the
+    /// names of each level
+    pub(crate) header_levels: Option<(usize, String)>,
+    /// Predicate telling the footer lines at the end of the file
+    pub(crate) footer: Option<FooterFilter>,
+    /// Type some columns are converted to, as `(column, type)`
+    pub(crate) column_types: Vec<(String, CellType)>,
+    /// Fields read as nulls in every column
+    pub(crate) null_values: Vec<String>,
+    /// Whether the type hints next to the parsed file are ignored
+    pub(crate) skip_sidecar: bool,
+    /// Column holding the path of the parsed file, not added if `None`
+    pub(crate) source_column: Option<String>,
+    /// Pool the text fields are interned in, not interned if `None`
+    pub(crate) string_pool: Option<StringPool>,
+    /// Whether the records are parsed without counting the lines first
+    pub(crate) single_pass: bool,
+    /// Whether fields are read as UTF-8 without validating them
+    pub(crate) unsafe_fast: bool,
+    /// How records with more or fewer fields than the header are read
+    pub(crate) ragged_rows: RaggedRows,
+    /// Byte ending a record, `\n` if `None`
+    pub(crate) terminator: Option<u8>,
+    /// Whether integers written with leading zeros are read as strings
+    pub(crate) leading_zeros: bool,
+    /// Token stopping the parse once cancelled
+    pub(crate) cancel: Option<CancelToken>,
+    /// Threads running the jobs of the parse, fresh threads if `None`
+    #[cfg(feature = "threads")]
+    pub(crate) thread_pool: Option<Arc<ThreadPool>>,
+}
+
+/// How the parser reads records holding more or fewer fields than the
+/// header, see [`ParserOptions::ragged_rows`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// Pad short records with nulls, recording a
+    /// [`WarningKind::RaggedRow`] warning, and fail the parse with
+    /// [`Error::RaggedRow`] on the first long record
+    Pad,
+    /// Pad short records with nulls and drop the extra fields of long
+    /// records, recording a [`WarningKind::RaggedRow`] warning
+    #[default]
+    Truncate,
+    /// Fail the parse with [`Error::RaggedRow`] on the first ragged
+    /// record
+    Error,
+}
+
+impl RaggedRows {
+    /// Whether a record of `fields` fields, with `expected` columns in
+    /// the header, fails the parse
+    #[inline(always)]
+    pub(crate) fn rejects(self, fields: usize, expected: usize) -> bool {
+        match self {
+            Self::Pad => fields > expected,
+            Self::Truncate => false,
+            Self::Error => fields != expected,
+        }
+    }
+}
+
+/// Character separating the integer part of formatted numbers from
+/// their fraction, see [`ParserOptions::formatted_numbers`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecimalMark {
+    /// `1,234.50`: commas group the digits
+    #[default]
+    Point,
+    /// `1.234,50` or `1 234,50`: points group the digits
+    Comma,
+}
+
+impl DecimalMark {
+    /// The decimal mark itself
+    #[inline(always)]
+    fn mark(self) -> char {
+        match self {
+            Self::Point => '.',
+            Self::Comma => ',',
+        }
+    }
+
+    /// Whether `c` groups the digits of the integer part
+    #[inline]
+    fn is_grouping(self, c: char) -> bool {
+        matches!(c, ' ' | '\'' | '\u{a0}' | '\u{202f}')
+            || c == match self {
+                Self::Point => ',',
+                Self::Comma => '.',
+            }
+    }
+
+    /// Number written in `text` with grouped digits, a currency symbol,
+    /// or a sign in accounting parentheses (`(12.00)`), as plain digits
+    /// with a point: `-1234.50` for `"-$1,234.50"`. `None` if `text`
+    /// holds anything else.
+    fn normalize(self, text: &str) -> Option<String> {
+        let text = text.trim();
+        let (text, parens) = match text.strip_prefix('(') {
+            Some(text) => (text.strip_suffix(')')?, true),
+            None => (text, false),
+        };
+
+        // Sign, currency symbol and spaces around the digits
+        let start =
+            text.find(|c: char| c.is_ascii_digit() || c == self.mark())?;
+        let end = text.rfind(|c: char| c.is_ascii_digit())? + 1;
+        let mut signs = 0;
+        let mut negative = parens;
+        for c in text[..start].chars().chain(text[end..].chars()) {
+            match c {
+                '-' | '+' => {
+                    signs += 1;
+                    negative ^= c == '-';
+                }
+                c if is_currency(c) || c.is_whitespace() => {}
+                _ => return None,
+            }
+        }
+        if signs > 1 || (parens && signs > 0) {
+            return None;
+        }
+
+        let mut number = String::with_capacity(end - start + 1);
+        if negative {
+            number.push('-');
+        }
+        let mut fraction = false;
+        for c in text[start..end].chars() {
+            match c {
+                '0'..='9' => number.push(c),
+                c if c == self.mark() && !fraction => {
+                    number.push('.');
+                    fraction = true;
+                }
+                c if self.is_grouping(c) && !fraction => {}
+                _ => return None,
+            }
+        }
+        Some(number)
+    }
+}
+
+/// Whether `c` is a currency symbol
+#[inline]
+fn is_currency(c: char) -> bool {
+    matches!(
+        c,
+        '$' | '¢'
+            | '£'
+            | '¤'
+            | '¥'
+            | '֏'
+            | '؋'
+            | '৳'
+            | '฿'
+            | '₡'
+            | '₦'
+            | '₨'
+            | '₩'
+            | '₪'
+            | '₫'
+            | '€'
+            | '₭'
+            | '₮'
+            | '₱'
+            | '₲'
+            | '₴'
+            | '₵'
+            | '₸'
+            | '₹'
+            | '₺'
+            | '₼'
+            | '₽'
+            | '₾'
+            | '₿'
+    )
+}
+
+/// Predicate over the cell of a column, deciding whether the row
+/// is kept
+pub type CellPredicate = dyn Fn(&Cell) -> bool + Send + Sync;
+
+/// Predicate registered on a key column with [`ParserOptions::filter`]
+#[derive(Clone)]
+pub(crate) struct RowFilter {
+    /// Name of the key column
+    column: String,
+    /// Predicate the key cell should pass
+    predicate: Arc<CellPredicate>,
+}
+
+/// Function reading a cell from the raw bytes of a field
+pub type CellConverter = dyn Fn(&[u8]) -> Cell + Send + Sync;
+
+/// Column of the file a [`CellConverter`] is registered on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ConverterColumn {
+    /// Column with this name
+    Name(String),
+    /// Column at this position, starting from `0`
+    Index(usize),
+}
+
+/// Converter registered with [`ParserOptions::converter`] or
+/// [`ParserOptions::converter_at`]
+#[derive(Clone)]
+pub(crate) struct Converter {
+    /// Column read by the converter
+    column: ConverterColumn,
+    /// Function reading each field of the column
+    convert: Arc<CellConverter>,
+}
+
+impl std::fmt::Debug for Converter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Converter")
+            .field("column", &self.column)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Predicate over a raw line of the file, telling whether it is part
+/// of the footer
+pub type LinePredicate = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Predicate registered with [`ParserOptions::footer`]
+#[derive(Clone)]
+pub(crate) struct FooterFilter(Arc<LinePredicate>);
+
+impl std::fmt::Debug for FooterFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FooterFilter").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for RowFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowFilter")
+            .field("column", &self.column)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How the cells of a column are read, before the usual inference
+#[derive(Clone, Copy)]
+pub(crate) enum CellRule<'p> {
+    /// Dates with an explicit `strftime` like format
+    DateFormat(&'p str),
+    /// Numeric dates resolved with the order preset
+    DateOrder(DateOrder),
+    /// Binary payloads written in the encoding
+    Bytes(BytesEncoding),
+    /// Integers within `i64` range, any other field is a null
+    CheckedInteger,
+    /// Exact decimals, any other field is a null
+    ExactDecimal,
+    /// Numbers with grouped digits or currency symbols, using the
+    /// decimal mark
+    Formatted(DecimalMark),
+    /// Cells read by a converter of the caller from the raw bytes
+    Custom(&'p CellConverter),
+}
+
+impl CellRule<'_> {
+    /// Cell read from the field `bytes`, decoded as `text`, by the
+    /// rule, `None` if `text` does not follow it
+    #[inline]
+    pub(crate) fn convert(self, bytes: &[u8], text: &str) -> Option<Cell> {
+        match self {
+            Self::Custom(convert) => Some(convert(bytes)),
+            Self::DateFormat(format) => {
+                Date::parse_with_format(text, format).map(Cell::Date)
+            }
+            Self::DateOrder(order) => {
+                Date::parse_with_order(text, order).map(Cell::Date)
+            }
+            Self::Bytes(encoding) => encoding.decode(text).map(Cell::Bytes),
+            Self::CheckedInteger => Some(
+                parse_i64(text.as_bytes()).map_or(Cell::Null, Cell::Number),
+            ),
+            Self::ExactDecimal => {
+                Some(Decimal128::parse(text).map_or(Cell::Null, Cell::from))
+            }
+            Self::Formatted(mark) => {
+                let number = mark.normalize(text)?;
+                match parse_i64(number.as_bytes()) {
+                    Some(number) => Some(Cell::Number(number)),
+                    None => parse_f64(number.as_bytes()).map(Cell::Decimal),
+                }
+            }
+        }
+    }
+
+    /// Why `text` was not read as an integer by
+    /// [`CellRule::CheckedInteger`]
+    #[inline]
+    pub(crate) fn integer_issue(text: &[u8]) -> WarningKind {
+        let digits = match text.first() {
+            Some(b'-' | b'+') => &text[1..],
+            _ => text,
+        };
+        if !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) {
+            WarningKind::IntegerOverflow
+        } else {
+            WarningKind::InvalidInteger
+        }
+    }
+}
+
+/// Layout of the parsed records, resolved from [`ParserOptions`]
+/// against the header of the file.
+pub(crate) struct ParsePlan<'p> {
+    /// Column of each field in the result, `None` if not read
+    pub(crate) columns: Vec<Option<usize>>,
+    /// Total columns in the result
+    pub(crate) width: usize,
+    /// Key column of the filter and the predicate rows should pass
+    pub(crate) filter: Option<(usize, &'p CellPredicate)>,
+    /// Rule of each field, `None` if read with the usual inference
+    pub(crate) rules: Vec<Option<CellRule<'p>>>,
+    /// Fields read as nulls
+    pub(crate) null_values: &'p [String],
+    /// Whether the records are parsed without counting the lines first
+    pub(crate) single_pass: bool,
+    /// Whether fields are read as UTF-8 without validating them
+    pub(crate) unsafe_fast: bool,
+    /// Byte ending a record
+    pub(crate) terminator: u8,
+    /// Whether integers written with leading zeros are read as strings
+    pub(crate) leading_zeros: bool,
+    /// Whether times of day and durations are inferred in text fields
+    pub(crate) temporal: bool,
+    /// How records with more or fewer fields than the header are read
+    pub(crate) ragged_rows: RaggedRows,
+    /// Token stopping the parse once cancelled
+    pub(crate) cancel: Option<&'p CancelToken>,
+    /// Pool the text fields are interned in
+    pub(crate) pool: Option<&'p StringPool>,
+}
+
+impl ParsePlan<'_> {
+    /// Plan that reads all `width` columns of every record
+    #[inline]
+    pub(crate) fn identity(width: usize) -> Self {
+        Self {
+            columns: (0..width).map(Some).collect(),
+            width,
+            filter: None,
+            rules: vec![None; width],
+            null_values: &[],
+            single_pass: false,
+            unsafe_fast: false,
+            terminator: b'\n',
+            leading_zeros: false,
+            temporal: false,
+            ragged_rows: RaggedRows::Truncate,
+            cancel: None,
+            pool: None,
+        }
+    }
+}
+
+/// Names of the fields of a header `line`, keeping empty fields
+fn header_fields(line: &[u8]) -> Vec<String> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let (mut fields, mut field, mut quoted) = (Vec::new(), Vec::new(), false);
+
+    let mut bytes = line.iter().peekable();
+    while let Some(byte) = bytes.next() {
+        match (byte, quoted) {
+            (b'"', true) if bytes.peek() == Some(&&b'"') => {
+                field.push(b'"');
+                bytes.next();
+            }
+            (b'"', _) => quoted = !quoted,
+            (b',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(*byte),
+        }
+    }
+    fields.push(field);
+
+    fields
+        .into_iter()
+        .map(|field| String::from_utf8_lossy(&field).trim().to_owned())
+        .collect()
+}
+
+impl ParserOptions {
+    /// Default options: read every record
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop after reading `nrows` data rows
+    #[inline]
+    pub fn nrows(mut self, nrows: usize) -> Self {
+        self.nrows = Some(nrows);
+        self
+    }
+
+    /// Skip the first `skip_records` data rows (the header is
+    /// still read)
+    #[inline]
+    pub fn skip_records(mut self, skip_records: usize) -> Self {
+        self.skip_records = skip_records;
+        self
+    }
+
+    /// Read only the columns named in `columns`, skipping the other
+    /// fields while scanning. Columns keep the order of the file.
+    #[inline]
+    pub fn use_columns(mut self, columns: &[&str]) -> Self {
+        self.use_columns =
+            Some(columns.iter().map(|c| (*c).to_owned()).collect());
+        self
+    }
+
+    /// Keep only the rows whose cell in `column` passes `predicate`.
+    ///
+    /// The predicate runs while the records are scanned, so rejected
+    /// rows are never stored in the result. The key column does not
+    /// need to be one of [`Self::use_columns`]. Rows are counted for
+    /// [`Self::nrows`] before being filtered.
+    #[inline]
+    pub fn filter<F>(mut self, column: &str, predicate: F) -> Self
+    where
+        F: Fn(&Cell) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(RowFilter {
+            column: column.to_owned(),
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Read the cells of `column` with `convert`, called with the raw
+    /// bytes of each field instead of the usual inference. Meant for
+    /// formats of a domain, like coordinates or codes of an enum, that
+    /// would otherwise be read as strings and converted afterwards.
+    ///
+    /// The bytes are those between the quotes of quoted fields, with
+    /// the surrounding spaces trimmed. Empty fields and the markers of
+    /// [`Self::null_values`] are read as nulls without calling
+    /// `convert`. A converter replaces any other rule of the column,
+    /// like [`Self::with_date_format`].
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// // Latitudes written as `48.85N` or `33.87S`
+    /// let options = ParserOptions::new().converter("lat", |bytes| {
+    ///     let text = std::str::from_utf8(bytes).unwrap_or_default();
+    ///     let value = |digits: &str| digits.parse::<f64>().ok();
+    ///     match (text.strip_suffix('N'), text.strip_suffix('S')) {
+    ///         (Some(north), _) => value(north).map_or(Cell::Null, Cell::Decimal),
+    ///         (_, Some(south)) => value(south).map_or(Cell::Null, |v| Cell::Decimal(-v)),
+    ///         _ => Cell::Null,
+    ///     }
+    /// });
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"city,lat\nParis,48.85N\nSydney,33.87S\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::F64]);
+    /// assert_eq!(df.column("lat").unwrap().as_f64(), [Some(48.85), Some(-33.87)]);
+    /// ```
+    #[inline]
+    pub fn converter<F>(self, column: &str, convert: F) -> Self
+    where
+        F: Fn(&[u8]) -> Cell + Send + Sync + 'static,
+    {
+        self.push_converter(ConverterColumn::Name(column.to_owned()), convert)
+    }
+
+    /// Read the cells of the column at `index` (starting from `0`, in
+    /// the order of the file) with `convert`, see [`Self::converter`].
+    /// Useful for files whose header repeats or lacks names.
+    #[inline]
+    pub fn converter_at<F>(self, index: usize, convert: F) -> Self
+    where
+        F: Fn(&[u8]) -> Cell + Send + Sync + 'static,
+    {
+        self.push_converter(ConverterColumn::Index(index), convert)
+    }
+
+    /// Register `convert` on `column`, replacing its previous converter
+    fn push_converter<F>(mut self, column: ConverterColumn, convert: F) -> Self
+    where
+        F: Fn(&[u8]) -> Cell + Send + Sync + 'static,
+    {
+        self.converters
+            .retain(|converter| converter.column != column);
+        self.converters.push(Converter {
+            column,
+            convert: Arc::new(convert),
+        });
+        self
+    }
+
+    /// Read the cells of `column` as dates written with `format`, using
+    /// the directives of [`Date::parse_with_format`]. Cells that do not
+    /// match are kept as they are, turning the column into strings.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    ///
+    /// let options = ParserOptions::new().with_date_format("dob", "%d/%m/%Y");
+    /// ```
+    #[inline]
+    pub fn with_date_format(mut self, column: &str, format: &str) -> Self {
+        self.date_formats.retain(|(name, _)| name != column);
+        self.date_formats
+            .push((column.to_owned(), format.to_owned()));
+        self
+    }
+
+    /// Convert column `column` to type `dtype` once parsed, reading the
+    /// fields that do not convert as nulls (see [`DataFrame::cast`]).
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().with_type("zip", CellType::String);
+    /// let df = CsvParser::parse_buffer_with_options(b"zip\n75001\n", 1, &options)
+    ///     .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String]);
+    /// ```
+    ///
+    /// [`DataFrame::cast`]: crate::dframe::DataFrame::cast
+    #[inline]
+    pub fn with_type(mut self, column: &str, dtype: CellType) -> Self {
+        self.column_types.retain(|(name, _)| name != column);
+        self.column_types.push((column.to_owned(), dtype));
+        self
+    }
+
+    /// Read the fields equal to one of `markers` (like `NA` or `-`) as
+    /// nulls, in every column.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().null_values(&["NA", "-"]);
+    /// let df = CsvParser::parse_buffer_with_options(b"qty\n4\nNA\n-\n", 1, &options)
+    ///     .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::I64]);
+    /// assert_eq!(df.column("qty").unwrap().as_i64(), [Some(4), None, None]);
+    /// ```
+    #[inline]
+    pub fn null_values(mut self, markers: &[&str]) -> Self {
+        for marker in markers {
+            if !self.null_values.iter().any(|known| known == marker) {
+                self.null_values.push((*marker).to_owned());
+            }
+        }
+        self
+    }
+
+    /// Ignore the type hints file next to the parsed file, see
+    /// [`crate::sidecar`]
+    #[inline]
+    pub fn skip_sidecar(mut self) -> Self {
+        self.skip_sidecar = true;
+        self
+    }
+
+    /// Parse the records in a single pass over the file: each thread
+    /// lays its rows out in a buffer growing with the rows, and the
+    /// buffers are stitched together at the end.
+    ///
+    /// By default a first pass counts the lines of the slice of each
+    /// thread, so that rows are written in place in a buffer allocated
+    /// once. Skipping it saves a full scan of the file, at the cost of
+    /// moving every cell once more.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"id,name\n1,ada\n2,bob\n3,cy\n";
+    /// let options = ParserOptions::new().single_pass();
+    /// let df = CsvParser::parse_buffer_with_options(csv, 2, &options).unwrap();
+    /// assert_eq!(df.len(), 3);
+    /// assert_eq!(df.column("name").unwrap().as_str()[2], Some("cy"));
+    /// ```
+    #[inline]
+    pub fn single_pass(mut self) -> Self {
+        self.single_pass = true;
+        self
+    }
+
+    /// Read the fields as UTF-8 without validating them.
+    ///
+    /// By default each field is checked, and invalid sequences are
+    /// replaced with `U+FFFD`, recording a
+    /// [`WarningKind::InvalidUtf8`] warning. Skipping the check saves
+    /// a pass over the bytes of each field, but is only sound for
+    /// buffers known to hold valid UTF-8: other buffers produce
+    /// strings breaking the invariant of `str`, which is undefined
+    /// behaviour.
+    ///
+    /// # Safety
+    ///
+    /// The buffer parsed with these options must be valid UTF-8.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"id,name\n1,ada\n";
+    /// let options = unsafe { ParserOptions::new().unsafe_fast() };
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(df.column("name").unwrap().as_str()[0], Some("ada"));
+    /// ```
+    ///
+    /// [`WarningKind::InvalidUtf8`]: crate::warning::WarningKind::InvalidUtf8
+    #[inline]
+    pub unsafe fn unsafe_fast(mut self) -> Self {
+        self.unsafe_fast = true;
+        self
+    }
+
+    /// Read records with more or fewer fields than the header as told
+    /// by `policy`. Each record is checked on its own, so a ragged
+    /// record never shifts the fields of the next ones, and the parse
+    /// stops at the first record the policy fails on.
+    ///
+    /// Records are padded or truncated by default, see
+    /// [`RaggedRows::Truncate`]. Blank lines are skipped either way.
+    ///
+    /// ```
+    /// use nn::cell::Cell;
+    /// use nn::error::Error;
+    /// use nn::options::{ParserOptions, RaggedRows};
+    /// use nn::parser::CsvParser;
+    /// use nn::warning::WarningKind;
+    ///
+    /// // Short records are padded and long ones truncated by default
+    /// let csv = b"id,name,age\n1,ada\n2,bob,40,extra\n3,cy,25\n";
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &ParserOptions::new()).unwrap();
+    /// assert!(matches!(df.get(0, "age"), Some(Cell::Null)));
+    /// assert!(matches!(df.get(1, "age"), Some(Cell::Number(40))));
+    /// assert_eq!(df.warnings()[0].kind, WarningKind::RaggedRow { fields: 2 });
+    ///
+    /// let options = ParserOptions::new().ragged_rows(RaggedRows::Error);
+    /// assert!(matches!(
+    ///     CsvParser::parse_buffer_with_options(csv, 1, &options),
+    ///     Err(Error::RaggedRow { row: 0, expected: 3, found: 2 })
+    /// ));
+    ///
+    /// // Short records are padded, long ones fail the parse
+    /// let options = ParserOptions::new().ragged_rows(RaggedRows::Pad);
+    /// let short = b"id,name\n1\n2,bob\n";
+    /// let df = CsvParser::parse_buffer_with_options(short, 1, &options).unwrap();
+    /// assert_eq!(df.column("name").unwrap().as_str(), [None, Some("bob")]);
+    /// assert!(matches!(
+    ///     CsvParser::parse_buffer_with_options(csv, 1, &options),
+    ///     Err(Error::RaggedRow { row: 1, expected: 3, found: 4 })
+    /// ));
+    /// ```
+    #[inline]
+    pub fn ragged_rows(mut self, policy: RaggedRows) -> Self {
+        self.ragged_rows = policy;
+        self
+    }
+
+    /// End records with `terminator` instead of `\n`, such as `\r` for
+    /// files written by classic Mac OS. Line ends that are not the
+    /// terminator are read as spaces, or kept as they are in quoted
+    /// fields.
+    ///
+    /// The parse fails with [`Error::InvalidArgument`] if `terminator`
+    /// is the separator `,` or the quote `"`.
+    ///
+    /// ```
+    /// use nn::error::Error;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"id,name\r1,ada\r2,bob\r";
+    /// let options = ParserOptions::new().record_terminator(b'\r');
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(df.len(), 2);
+    /// assert_eq!(df.column("name").unwrap().as_str()[1], Some("bob"));
+    ///
+    /// let csv = b"id,name;1,ada;2,bob";
+    /// let options = ParserOptions::new().record_terminator(b';');
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(df.header(), ["id", "name"]);
+    /// assert_eq!(df.len(), 2);
+    ///
+    /// let options = ParserOptions::new().record_terminator(b',');
+    /// assert!(matches!(
+    ///     CsvParser::parse_buffer_with_options(csv, 1, &options),
+    ///     Err(Error::InvalidArgument(_))
+    /// ));
+    /// ```
+    #[inline]
+    pub fn record_terminator(mut self, terminator: u8) -> Self {
+        self.terminator = Some(terminator);
+        self
+    }
+
+    /// Byte ending a record
+    #[inline(always)]
+    pub(crate) fn terminator(&self) -> u8 {
+        self.terminator.unwrap_or(b'\n')
+    }
+
+    /// Stop the parse once `token` is cancelled, failing with
+    /// [`Error::Cancelled`], see [`CancelToken`]
+    #[inline]
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Run the jobs of the parse on the threads of `pool` instead of
+    /// fresh threads, see [`ThreadPool`]. The total jobs is still told
+    /// by the thread count given to the parser.
+    #[cfg(feature = "threads")]
+    #[inline]
+    pub fn thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(pool);
+        self
+    }
+
+    /// Add a column `name`, after the others, holding the path of the
+    /// parsed file on every row. Meant for [`CsvParser::parse_glob`],
+    /// to tell which file each row comes from.
+    ///
+    /// Only files get the column: buffers parsed with
+    /// [`CsvParser::parse_buffer_with_options`] have no path.
+    #[inline]
+    pub fn source_column(mut self, name: &str) -> Self {
+        self.source_column = Some(name.to_owned());
+        self
+    }
+
+    /// Intern the text fields in a new [`StringPool`] as they are read,
+    /// so that repeated values are allocated once. String columns
+    /// become categorical.
+    ///
+    /// The pool is shared by every parse made with these options (and
+    /// their clones), e.g. by the files of
+    /// [`CsvParser::parse_glob_with_options`].
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().intern_strings();
+    /// let df = CsvParser::parse_buffer_with_options(b"from,to,km\nLyon,Paris,465\nParis,Lyon,465\n", 1, &options)
+    ///     .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::Categorical, CellType::Categorical, CellType::I64]);
+    ///
+    /// // Equal strings share their code, in every column
+    /// assert!(matches!(df.get(0, "from"), Some(Cell::Categorical(0))));
+    /// assert!(matches!(df.get(1, "to"), Some(Cell::Categorical(0))));
+    /// assert_eq!(df.categories("to").unwrap(), ["Lyon", "Paris"]);
+    /// assert_eq!(df.query("from == 'Paris'").unwrap().len(), 1);
+    /// ```
+    ///
+    /// [`CsvParser::parse_glob_with_options`]: crate::parser::CsvParser::parse_glob_with_options
+    #[inline]
+    pub fn intern_strings(self) -> Self {
+        self.string_pool(StringPool::new())
+    }
+
+    /// Intern the text fields in `pool` as they are read, see
+    /// [`Self::intern_strings`]
+    #[inline]
+    pub fn string_pool(mut self, pool: StringPool) -> Self {
+        self.string_pool = Some(pool);
+        self
+    }
+
+    /// Infer dates in the columns without an explicit format, resolving
+    /// numeric dates like `05/01/2024` with `order`.
+    ///
+    /// Dates are not inferred by default. Columns whose cells are all
+    /// dates or nulls get the [`CellType::Date`] type.
+    ///
+    /// [`CellType::Date`]: crate::cell::CellType::Date
+    #[inline]
+    pub fn date_order(mut self, order: DateOrder) -> Self {
+        self.date_order = Some(order);
+        self
+    }
+
+    /// Infer times of day (`HH:MM[:SS[.fff]]`) and ISO 8601 durations
+    /// (like `PT1H30M` or `-P2D`) in text fields, see [`Time::parse`]
+    /// and [`Duration::parse`].
+    ///
+    /// Off by default, so that codes written like times or durations
+    /// (`P1D`, `-P2W`) stay strings. Columns whose cells are all times
+    /// (or all durations) or nulls get the [`CellType::Time`] (or
+    /// [`CellType::Duration`]) type.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"start,length,code\n09:30,PT1H30M,P1D\n13:05:10,PT45M,-P2W\n";
+    /// let df = CsvParser::parse_buffer(csv, 1);
+    /// assert_eq!(df.dtypes(), [CellType::String; 3]);
+    ///
+    /// let options = ParserOptions::new().infer_temporal();
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(
+    ///     df.dtypes(),
+    ///     [CellType::Time, CellType::Duration, CellType::Duration]
+    /// );
+    /// ```
+    ///
+    /// [`Time::parse`]: crate::datetime::Time::parse
+    /// [`Duration::parse`]: crate::datetime::Duration::parse
+    #[inline]
+    pub fn infer_temporal(mut self) -> Self {
+        self.temporal = true;
+        self
+    }
+
+    /// Read the cells of `column` as binary payloads written in
+    /// `encoding`, into `Cell::Bytes`.
+    ///
+    /// Fields that fail to decode are kept as strings, with an
+    /// [`InvalidEncoding`] warning.
+    ///
+    /// [`InvalidEncoding`]: crate::warning::WarningKind::InvalidEncoding
+    #[inline]
+    pub fn decode_bytes(
+        mut self,
+        column: &str,
+        encoding: BytesEncoding,
+    ) -> Self {
+        self.bytes_columns.retain(|(name, _)| name != column);
+        self.bytes_columns.push((column.to_owned(), encoding));
+        self
+    }
+
+    /// Read integers written with leading zeros, like `00123`, as
+    /// strings: such fields are identifiers or postcodes whose zeros
+    /// matter, not quantities. The integers of columns holding such
+    /// strings are read as strings too, so that the whole column keeps
+    /// the text of its fields.
+    ///
+    /// Off by default. Decimals like `0.5`, and `0` alone, are still
+    /// read as numbers.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"zip,qty\n02134,0\n90210,007\n";
+    /// let options = ParserOptions::new().preserve_leading_zeros();
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::String]);
+    /// assert_eq!(df.column("zip").unwrap().as_str(), [Some("02134"), Some("90210")]);
+    /// assert_eq!(df.column("qty").unwrap().as_str(), [Some("0"), Some("007")]);
+    ///
+    /// let df = CsvParser::parse_buffer(csv, 1);
+    /// assert_eq!(df.dtypes(), [CellType::I64, CellType::I64]);
+    /// ```
+    #[inline]
+    pub fn preserve_leading_zeros(mut self) -> Self {
+        self.leading_zeros = true;
+        self
+    }
+
+    /// Read the cells of `column` as integers, turning fields out of
+    /// `i64` range or holding stray characters into nulls instead of
+    /// promoting the whole column to `F64` or `String`.
+    ///
+    /// Each such field is counted with an [`IntegerOverflow`] or an
+    /// [`InvalidInteger`] warning.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().checked_integers("qty");
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"qty\n12\n7x\n99999999999999999999\n3\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::I64]);
+    /// assert_eq!(df.column("qty").unwrap().as_i64(), [Some(12), None, None, Some(3)]);
+    /// assert_eq!(df.warnings().len(), 2);
+    /// ```
+    ///
+    /// [`IntegerOverflow`]: crate::warning::WarningKind::IntegerOverflow
+    /// [`InvalidInteger`]: crate::warning::WarningKind::InvalidInteger
+    #[inline]
+    pub fn checked_integers(mut self, column: &str) -> Self {
+        if !self.checked_integers.iter().any(|name| name == column) {
+            self.checked_integers.push(column.to_owned());
+        }
+        self
+    }
+
+    /// Read the cells of `column` as exact decimals
+    /// ([`Cell::Decimal128`]), keeping every digit written instead of
+    /// rounding them to the nearest `f64`. Meant for prices, amounts and
+    /// other values that must add up to the cent.
+    ///
+    /// Fields that are not decimals are turned into nulls, each counted
+    /// with an [`InvalidDecimal`] warning.
+    ///
+    /// ```
+    /// use nn::cell::{Cell, CellType};
+    /// use nn::decimal::Decimal128;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = b"item,price\npen,0.10\nink,0.20\ncap,n/a\n";
+    /// let options = ParserOptions::new().exact_decimals("price");
+    /// let df = CsvParser::parse_buffer_with_options(csv, 1, &options).unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::Decimal128]);
+    /// assert_eq!(df.warnings().len(), 1);
+    ///
+    /// let total = df.eval("price * 3").unwrap();
+    /// assert_eq!(total.dtype(), CellType::Decimal128);
+    /// assert_eq!(total.cells()[0].to_string(), "0.30");
+    /// assert!(matches!(df.get(1, "price"), Some(Cell::Decimal128(price))
+    ///     if **price == Decimal128::parse("0.2").unwrap()));
+    /// ```
+    ///
+    /// [`Cell::Decimal128`]: crate::cell::Cell::Decimal128
+    /// [`InvalidDecimal`]: crate::warning::WarningKind::InvalidDecimal
+    #[inline]
+    pub fn exact_decimals(mut self, column: &str) -> Self {
+        if !self.exact_decimals.iter().any(|name| name == column) {
+            self.exact_decimals.push(column.to_owned());
+        }
+        self
+    }
+
+    /// Read the cells of `column` as numbers written for people rather
+    /// than machines: digits grouped by thousands, a currency symbol,
+    /// or a negative amount in accounting parentheses. `mark` tells the
+    /// decimal mark of the column, the other one of `.` and `,` groups
+    /// the digits, along with spaces and apostrophes.
+    ///
+    /// Fields without a fraction are read as integers, others as
+    /// decimals. Fields that are not such numbers are read as usual.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::{DecimalMark, ParserOptions};
+    /// use nn::parser::CsvParser;
+    ///
+    /// let csv = "item,price,prix\npen,\"$1,234.50\",\"1 234,50 €\"\nink,(12.00),\"-3,5\"\n";
+    /// let options = ParserOptions::new()
+    ///     .formatted_numbers("price", DecimalMark::Point)
+    ///     .formatted_numbers("prix", DecimalMark::Comma);
+    /// let df = CsvParser::parse_buffer_with_options(csv.as_bytes(), 1, &options).unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::F64, CellType::F64]);
+    /// assert_eq!(df.column("price").unwrap().as_f64(), [Some(1234.5), Some(-12.0)]);
+    /// assert_eq!(df.column("prix").unwrap().as_f64(), [Some(1234.5), Some(-3.5)]);
+    /// ```
+    #[inline]
+    pub fn formatted_numbers(
+        mut self,
+        column: &str,
+        mark: DecimalMark,
+    ) -> Self {
+        self.number_formats.retain(|(name, _)| name != column);
+        self.number_formats.push((column.to_owned(), mark));
+        self
+    }
+
+    /// Read the header on record `header_row` (starting from `0`) of
+    /// the file, dropping the title records above it. Blank lines are
+    /// not counted, and quoted fields may span lines, as for
+    /// [`Self::skip_records`].
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().header_row(2);
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"Quarterly report\nExported 2024-04-01\nregion,sales\nNorth,10\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.header(), ["region", "sales"]);
+    /// ```
+    #[inline]
+    pub fn header_row(mut self, header_row: usize) -> Self {
+        self.header_row = header_row;
+        self
+    }
+
+    /// Read a header spanning `levels` lines (from [`Self::header_row`]),
+    /// naming each column by joining its non-empty names on each line
+    /// with `separator`.
+    ///
+    /// Spreadsheets write a cell merged over several columns only in
+    /// the first of them, so an empty name in the upper lines is taken
+    /// from the column on its left.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().header_levels(2, "_");
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"id,sales,\n,2023,2024\n1,10,12\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.header(), ["id", "sales_2023", "sales_2024"]);
+    /// ```
+    #[inline]
+    pub fn header_levels(mut self, levels: usize, separator: &str) -> Self {
+        self.header_levels = Some((levels, separator.to_owned()));
+        self
+    }
+
+    /// Read the line right after the header as the unit of each column
+    /// (like `kg` or `m/s`) instead of as a record, see
+    /// [`DataFrame::unit`]. Empty fields mean the column has no unit.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().units_row(true);
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"id,mass,speed\n,kg,m/s\n1,2.5,3\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.unit("mass"), Some("kg"));
+    /// assert_eq!(df.unit("id"), None);
+    /// assert_eq!(df.len(), 1);
+    /// ```
+    ///
+    /// [`DataFrame::unit`]: crate::dframe::DataFrame::unit
+    #[inline]
+    pub fn units_row(mut self, units_row: bool) -> Self {
+        self.units_row = units_row;
+        self
+    }
+
+    /// Drop the last `skip_footer` lines of the file, like totals or
+    /// notes below the records, before the records are read.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let options = ParserOptions::new().skip_footer(1);
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"sales\n10\n12\nTotal: 22\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.len(), 2);
+    /// ```
+    #[inline]
+    pub fn skip_footer(mut self, skip_footer: usize) -> Self {
+        self.skip_footer = skip_footer;
+        self
+    }
+
+    /// Drop the lines at the end of the file for which `predicate`
+    /// returns `true`, like `End of file` markers. Lines are checked
+    /// from the last one up, until one is not part of the footer.
+    ///
+    /// Applies after [`Self::skip_footer`].
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    ///
+    /// let options = ParserOptions::new()
+    ///     .footer(|line| line.starts_with("Total") || line == "End of file");
+    /// ```
+    #[inline]
+    pub fn footer<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.footer = Some(FooterFilter(Arc::new(predicate)));
+        self
+    }
+
+    /// Resolve the options against `header` of the file.
+    pub(crate) fn plan(&self, header: &[String]) -> Result<ParsePlan<'_>> {
+        let terminator = self.terminator();
+        if matches!(terminator, SEPARATOR | QUOTE) {
+            return Err(Error::InvalidArgument(format!(
+                "the record terminator {:?} is the separator or the quote",
+                terminator as char
+            )));
+        }
+
+        let find = |name: &String| {
+            header
+                .iter()
+                .position(|h| h == name)
+                .ok_or_else(|| Error::ColumnNotFound(name.clone()))
+        };
+
+        let filter = match &self.filter {
+            Some(filter) => Some((find(&filter.column)?, &*filter.predicate)),
+            None => None,
+        };
+
+        let mut rules: Vec<_> = header
+            .iter()
+            .map(|_| self.date_order.map(CellRule::DateOrder))
+            .collect();
+        for (name, format) in &self.date_formats {
+            rules[find(name)?] = Some(CellRule::DateFormat(format));
+        }
+        for (name, encoding) in &self.bytes_columns {
+            rules[find(name)?] = Some(CellRule::Bytes(*encoding));
+        }
+        for name in &self.checked_integers {
+            rules[find(name)?] = Some(CellRule::CheckedInteger);
+        }
+        for name in &self.exact_decimals {
+            rules[find(name)?] = Some(CellRule::ExactDecimal);
+        }
+        for (name, mark) in &self.number_formats {
+            rules[find(name)?] = Some(CellRule::Formatted(*mark));
+        }
+        for converter in &self.converters {
+            let index = match &converter.column {
+                ConverterColumn::Name(name) => find(name)?,
+                ConverterColumn::Index(index) if *index < header.len() => {
+                    *index
+                }
+                ConverterColumn::Index(index) => {
+                    return Err(Error::ColumnNotFound(format!("#{}", index)))
+                }
+            };
+            rules[index] = Some(CellRule::Custom(&*converter.convert));
+        }
+        for (name, _) in &self.column_types {
+            find(name)?;
+        }
+        let null_values = &self.null_values[..];
+
+        let Some(use_columns) = &self.use_columns else {
+            return Ok(ParsePlan {
+                filter,
+                rules,
+                null_values,
+                single_pass: self.single_pass,
+                unsafe_fast: self.unsafe_fast,
+                terminator,
+                leading_zeros: self.leading_zeros,
+                temporal: self.temporal,
+                ragged_rows: self.ragged_rows,
+                cancel: self.cancel.as_ref(),
+                pool: self.string_pool.as_ref(),
+                ..ParsePlan::identity(header.len())
+            });
+        };
+
+        use_columns
+            .iter()
+            .try_for_each(|name| find(name).map(|_| ()))?;
+
+        let mut width = 0;
+        let columns = header
+            .iter()
+            .map(|name| {
+                use_columns.contains(name).then(|| {
+                    width += 1;
+                    width - 1
+                })
+            })
+            .collect();
+
+        Ok(ParsePlan {
+            columns,
+            width,
+            filter,
+            rules,
+            null_values,
+            single_pass: self.single_pass,
+            unsafe_fast: self.unsafe_fast,
+            terminator,
+            leading_zeros: self.leading_zeros,
+            temporal: self.temporal,
+            ragged_rows: self.ragged_rows,
+            cancel: self.cancel.as_ref(),
+            pool: self.string_pool.as_ref(),
+        })
+    }
+
+    /// Scan the header of `buffer` as described by `header_row` and
+    /// `header_levels`, returning the header names and the remaining
+    /// body that holds the records.
+    pub(crate) fn split_header<'b>(
+        &self,
+        buffer: &'b [u8],
+    ) -> (Vec<String>, &'b [u8]) {
+        let buffer =
+            buffer.strip_prefix("\u{feff}".as_bytes()).unwrap_or(buffer);
+        let terminator = self.terminator();
+        let start = match self.header_row {
+            0 => 0,
+            skip => self
+                .records(buffer)
+                .nth(skip - 1)
+                .map_or(buffer.len(), |(_, end)| (end + 1).min(buffer.len())),
+        };
+
+        let (levels, separator) = match &self.header_levels {
+            Some((levels, separator)) if *levels > 1 => (*levels, separator),
+            _ => return CsvParser::split_header(&buffer[start..], terminator),
+        };
+
+        // Header lines are records: quoted names may hold terminators
+        let body = buffer[start..].trim_ascii_start();
+        let lines: Vec<_> = self.records(body).take(levels).collect();
+        let mut names: Vec<Vec<String>> = Vec::with_capacity(levels);
+        for (level, (start, end)) in lines.iter().enumerate() {
+            let mut line = header_fields(&body[*start..*end]);
+
+            // Merged cells only hold their name in the first column
+            if level + 1 < levels {
+                let mut previous = String::new();
+                line.iter_mut().for_each(|name| {
+                    if name.is_empty() {
+                        name.clone_from(&previous);
+                    } else {
+                        previous.clone_from(name);
+                    }
+                });
+            }
+
+            names.push(line);
+        }
+        let body = lines
+            .last()
+            .map_or(body, |(_, end)| &body[(end + 1).min(body.len())..]);
+
+        let width = names.iter().map(Vec::len).max().unwrap_or_default();
+        let header = (0..width)
+            .map(|index| {
+                names
+                    .iter()
+                    .filter_map(|line| line.get(index))
+                    .filter(|name| !name.is_empty())
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            })
+            .collect();
+
+        (header, body.trim_ascii())
+    }
+
+    /// Read the units line at the start of `body` for the columns of
+    /// `header` if `units_row` is set, returning the units by column
+    /// name and the remaining records.
+    pub(crate) fn split_units<'b>(
+        &self,
+        header: &[String],
+        body: &'b [u8],
+    ) -> (HashMap<String, String>, &'b [u8]) {
+        if !self.units_row {
+            return (HashMap::new(), body);
+        }
+
+        // The units line is a record: quoted units may hold terminators
+        let Some((start, end)) = self.records(body).next() else {
+            return (HashMap::new(), &[]);
+        };
+        let units = header
+            .iter()
+            .zip(header_fields(&body[start..end]))
+            .filter(|(_, unit)| !unit.is_empty())
+            .map(|(name, unit)| (name.clone(), unit))
+            .collect();
+
+        (units, body[(end + 1).min(body.len())..].trim_ascii_start())
+    }
+
+    /// `body` without the footer records selected by `skip_footer`
+    /// and `footer`
+    fn strip_footer<'b>(&self, body: &'b [u8]) -> &'b [u8] {
+        if self.skip_footer == 0 && self.footer.is_none() {
+            return body.trim_ascii_end();
+        }
+
+        let mut records: Vec<_> = self.records(body).collect();
+        records.truncate(records.len().saturating_sub(self.skip_footer));
+
+        if let Some(FooterFilter(predicate)) = &self.footer {
+            while let Some((start, end)) = records.last() {
+                let line = String::from_utf8_lossy(&body[*start..*end]);
+                if !predicate(line.trim_end_matches('\r')) {
+                    break;
+                }
+                records.pop();
+            }
+        }
+
+        // Blank lines between the records and the footer
+        records
+            .last()
+            .map_or(&[], |(_, end)| body[..*end].trim_ascii_end())
+    }
+
+    /// Byte ranges of the records of `body` that are not blank, without
+    /// their terminator. Terminators inside quotes do not end a record.
+    fn records<'b>(
+        &self,
+        body: &'b [u8],
+    ) -> impl Iterator<Item = (usize, usize)> + 'b {
+        let terminator = self.terminator();
+        let mut position = 0;
+        std::iter::from_fn(move || {
+            while position < body.len() {
+                let (start, rest) = (position, &body[position..]);
+                let end = start
+                    + nth_record_end(rest, terminator, 0).unwrap_or(rest.len());
+                position = end + 1;
+                if !body[start..end].trim_ascii().is_empty() {
+                    return Some((start, end));
+                }
+            }
+            None
+        })
+    }
+
+    /// Restrict `body` to the records selected by `skip_footer`,
+    /// `footer`, `skip_records` and `nrows`, by seeking the
+    /// corresponding record ends.
+    pub(crate) fn select_records<'b>(&self, body: &'b [u8]) -> &'b [u8] {
+        let body = self.strip_footer(body);
+        if self.skip_records == 0 && self.nrows.is_none() {
+            return body;
+        }
+        let mut records = self.records(body);
+
+        let start = match self.skip_records {
+            0 => 0,
+            skip => records
+                .nth(skip - 1)
+                .map_or(body.len(), |(_, end)| (end + 1).min(body.len())),
+        };
+
+        let end = match self.nrows {
+            Some(0) => start,
+            Some(nrows) => {
+                records.nth(nrows - 1).map_or(body.len(), |(_, end)| end)
+            }
+            None => body.len(),
+        };
+
+        &body[start..end.max(start)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CellRule, ParserOptions};
+    use crate::warning::WarningKind;
+
+    #[test]
+    fn checked_integers_take_one_sign() {
+        let rule = CellRule::CheckedInteger;
+        assert_eq!(rule.convert(b"+5", "+5").unwrap().to_string(), "5");
+        assert_eq!(rule.convert(b"-+5", "-+5").unwrap().to_string(), "null");
+        assert!(matches!(
+            CellRule::integer_issue(b"-+5"),
+            WarningKind::InvalidInteger
+        ));
+        assert!(matches!(
+            CellRule::integer_issue(b"-99999999999999999999"),
+            WarningKind::IntegerOverflow
+        ));
+    }
+
+    #[test]
+    fn records_with_quoted_terminators() {
+        let body: &[u8] = b"1,\"a\nb\"\n\n2,c\n3,\"d\n\ne\"\n4,f";
+        let options = ParserOptions::new().skip_records(1).nrows(2);
+        assert_eq!(
+            options.select_records(body),
+            b"\n2,c\n3,\"d\n\ne\"".as_slice()
+        );
+
+        let options = ParserOptions::new().skip_records(3);
+        assert_eq!(options.select_records(body), b"4,f".as_slice());
+
+        let options = ParserOptions::new().skip_records(4);
+        assert!(options.select_records(body).is_empty());
+
+        let options = ParserOptions::new().nrows(1);
+        assert_eq!(options.select_records(body), b"1,\"a\nb\"".as_slice());
+    }
+
+    #[test]
+    fn header_row_after_quoted_terminators() {
+        let buffer: &[u8] =
+            b"\"Quarterly\nreport\"\n\nExported\nid,name\n1,a\n";
+        let options = ParserOptions::new().header_row(2);
+        let (header, body) = options.split_header(buffer);
+        assert_eq!(header, ["id", "name"]);
+        assert_eq!(body, b"1,a".as_slice());
+
+        let options = ParserOptions::new().header_row(3);
+        let (header, body) = options.split_header(buffer);
+        assert_eq!(header, ["1", "a"]);
+        assert!(body.trim_ascii().is_empty());
+
+        let options = ParserOptions::new().header_row(5);
+        assert!(options.split_header(buffer).0.is_empty());
+    }
+
+    #[test]
+    fn quoted_multi_level_header() {
+        let buffer: &[u8] =
+            b"\"Sales, EU\",,Costs\n\"Q1\nrevenue\",Q2,Q1\n1,2,3\n";
+        let options = ParserOptions::new().header_levels(2, "_");
+        let (header, body) = options.split_header(buffer);
+        assert_eq!(
+            header,
+            ["Sales, EU_Q1\nrevenue", "Sales, EU_Q2", "Costs_Q1"]
+        );
+        assert_eq!(body, b"1,2,3".as_slice());
+    }
+
+    #[test]
+    fn quoted_units_row() {
+        let header = ["force".to_owned(), "note".to_owned()];
+        let body: &[u8] = b"\"kg,m\",\"free\ntext\"\n1,a\n";
+        let options = ParserOptions::new().units_row(true);
+        let (units, body) = options.split_units(&header, body);
+        assert_eq!(units["force"], "kg,m");
+        assert_eq!(units["note"], "free\ntext");
+        assert_eq!(body, b"1,a\n".as_slice());
+    }
+
+    #[test]
+    fn footer_after_quoted_terminators() {
+        let body: &[u8] = b"1,a\n2,\"b\nTotal\"\n\nTotal: 3\nEnd\n";
+        let options = ParserOptions::new().skip_footer(2);
+        assert_eq!(
+            options.select_records(body),
+            b"1,a\n2,\"b\nTotal\"".as_slice()
+        );
+
+        let options = ParserOptions::new()
+            .footer(|line| line.starts_with("Total") || line == "End");
+        assert_eq!(
+            options.select_records(body),
+            b"1,a\n2,\"b\nTotal\"".as_slice()
+        );
+
+        let options = ParserOptions::new().skip_footer(3);
+        assert_eq!(options.select_records(body), b"1,a".as_slice());
+
+        let options = ParserOptions::new().skip_footer(4);
+        assert!(options.select_records(body).is_empty());
+    }
+}
@@ -235,7 +235,12 @@ impl ParseState {
             | Self::CellDecimalCurrentWithPointRead
             | Self::CellCurrent => Self::CellCurrent,
 
-            Self::SkippedAssumeEndWhitespace(_) => Self::CellCurrent,
+            // Text after the closing quote, the field is not a number
+            Self::SkippedAssumeEndWhitespace(_)
+            | Self::CellQuoteEnd
+            | Self::CellQuoteNumberEnd
+            | Self::CellQuoteDecimalEnd
+            | Self::CellQuoteDecimalEndWithPointRead => Self::CellCurrent,
 
             _ => Self::CellDecimalStartWithPointRead,
         }
@@ -246,8 +251,14 @@ impl ParseState {
     #[inline(always)]
     fn handle_default(initial_state: Self) -> Self {
         match initial_state {
-            // If quoted, continue reading.
-            Self::CellQuoteStart | Self::CellQuoteCurrent => {
+            // If quoted, continue reading, as a quoted string if it
+            // looked like a number so far.
+            Self::CellQuoteStart
+            | Self::CellQuoteCurrent
+            | Self::CellQuoteNumberStart
+            | Self::CellQuoteNumberCurrent
+            | Self::CellQuoteDecimalStartWithPointRead
+            | Self::CellQuoteDecimalCurrentWithPointRead => {
                 Self::CellQuoteCurrent
             }
 
@@ -260,11 +271,14 @@ impl ParseState {
             | Self::CellNumberCurrent
             | Self::CellDecimalCurrent
             | Self::CellDecimalStartWithPointRead
-            | Self::CellDecimalCurrentWithPointRead
-            | Self::CellQuoteDecimalStartWithPointRead
-            | Self::CellQuoteDecimalCurrentWithPointRead => Self::CellCurrent,
+            | Self::CellDecimalCurrentWithPointRead => Self::CellCurrent,
 
-            Self::SkippedAssumeEndWhitespace(_) => Self::CellCurrent,
+            // Text after the closing quote, the field is not a number
+            Self::SkippedAssumeEndWhitespace(_)
+            | Self::CellQuoteEnd
+            | Self::CellQuoteNumberEnd
+            | Self::CellQuoteDecimalEnd
+            | Self::CellQuoteDecimalEndWithPointRead => Self::CellCurrent,
 
             _ => Self::CellString,
         }
@@ -279,6 +293,8 @@ impl ParseState {
             // that separator is read.
             Self::CellQuoteCurrent
             | Self::CellQuoteStart
+            | Self::CellQuoteNumberCurrent
+            | Self::CellQuoteNumberStart
             | Self::CellQuoteDecimalCurrent
             | Self::CellQuoteDecimalStart
             | Self::CellQuoteDecimalStartWithPointRead
@@ -332,7 +348,12 @@ impl ParseState {
 
             Self::CellString | Self::CellCurrent => Self::CellCurrent,
 
-            Self::SkippedAssumeEndWhitespace(_) => Self::CellCurrent,
+            // Text after the closing quote, the field is not a number
+            Self::SkippedAssumeEndWhitespace(_)
+            | Self::CellQuoteEnd
+            | Self::CellQuoteNumberEnd
+            | Self::CellQuoteDecimalEnd
+            | Self::CellQuoteDecimalEndWithPointRead => Self::CellCurrent,
 
             _ => Self::CellNumberStart,
         }
@@ -342,7 +363,7 @@ impl ParseState {
     /// and knowing that character is end line.
     ///
     /// Jump from
-    /// ```
+    /// ```text
     /// CellQuoteCurrent | CellQuoteStart => CellQuoteCurrent, /// (While reading quotes, we're still reading cell),
     /// CellNumberCurrent | CellNumberStart => CellNumberEnd,
     /// CellDecimalStart | CellDecimalCurrent => CellDecimalEnd,
@@ -355,6 +376,8 @@ impl ParseState {
             // should default to normal quoted string.
             Self::CellQuoteCurrent
             | Self::CellQuoteStart
+            | Self::CellQuoteNumberCurrent
+            | Self::CellQuoteNumberStart
             | Self::CellQuoteDecimalCurrent
             | Self::CellQuoteDecimalStart
             | Self::CellQuoteDecimalStartWithPointRead
@@ -384,6 +407,17 @@ impl ParseState {
         match initial_state {
             // If previous started or running, end the values
             Self::CellQuoteStart | Self::CellQuoteCurrent => Self::CellQuoteEnd,
+
+            // A quote right after the closing quote is a doubled quote:
+            // a quote within the quoted string, which goes on
+            Self::CellQuoteEnd
+            | Self::CellQuoteNumberEnd
+            | Self::CellQuoteDecimalEnd
+            | Self::CellQuoteDecimalEndWithPointRead => Self::CellQuoteCurrent,
+
+            Self::CellQuoteNumberStart | Self::CellQuoteNumberCurrent => {
+                Self::CellQuoteNumberEnd
+            }
             Self::CellQuoteDecimalStart | Self::CellQuoteDecimalCurrent => {
                 Self::CellQuoteDecimalEnd
             }
@@ -406,6 +440,8 @@ impl ParseState {
             // should default to normal quoted string.
             Self::CellQuoteCurrent
             | Self::CellQuoteStart
+            | Self::CellQuoteNumberCurrent
+            | Self::CellQuoteNumberStart
             | Self::CellQuoteDecimalCurrent
             | Self::CellQuoteDecimalStart
             | Self::CellQuoteDecimalStartWithPointRead
@@ -459,7 +495,8 @@ impl ParseState {
                 Self::CellQuoteCurrent
             }
 
-            Self::CellCurrent
+            Self::CellString
+            | Self::CellCurrent
             | Self::CellDecimalStartWithPointRead
             | Self::CellDecimalCurrentWithPointRead => Self::CellCurrent,
 
@@ -510,3 +547,99 @@ impl ParseState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ParseState;
+    use super::ParseState::*;
+
+    /// State after each byte of `bytes`, read from the start of a
+    /// record
+    fn states(bytes: &[u8]) -> Vec<ParseState> {
+        bytes
+            .iter()
+            .scan(NewLine, |state, c| {
+                *state = ParseState::get_scan_state_from_data(*state, *c);
+                Some(*state)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quoted_number_then_separator() {
+        let states = states(b"\"12\",3");
+        assert_eq!(states[3..], [CellQuoteNumberEnd, CellSep, CellNumberStart]);
+    }
+
+    #[test]
+    fn quoted_number_then_line_end() {
+        assert_eq!(states(b"\"12\"\n")[3..], [CellQuoteNumberEnd, NewLine]);
+        assert_eq!(
+            states(b"\"1.5\"\r\n")[4..],
+            [CellQuoteDecimalEndWithPointRead, CarriageRet, NewLine]
+        );
+    }
+
+    #[test]
+    fn structural_bytes_inside_quoted_number() {
+        for field in [&b"\"1,2\""[..], b"\"1\n2\"", b"\"1\r\n2\"", b"\"1 2\""] {
+            let states = states(field);
+            let (last, inside) = states[2..].split_last().unwrap();
+            assert!(inside.iter().all(|state| *state == CellQuoteCurrent));
+            assert_eq!(*last, CellQuoteEnd);
+        }
+    }
+
+    #[test]
+    fn doubled_quote_in_quoted_number() {
+        assert_eq!(
+            states(b"\"1\"\"2\",")[1..],
+            [
+                CellQuoteNumberCurrent,
+                CellQuoteNumberEnd,
+                CellQuoteCurrent,
+                CellQuoteCurrent,
+                CellQuoteEnd,
+                CellSep,
+            ]
+        );
+        assert_eq!(
+            states(b"\"\"\"\"\n")[1..],
+            [CellQuoteEnd, CellQuoteCurrent, CellQuoteEnd, NewLine]
+        );
+    }
+
+    #[test]
+    fn text_after_quoted_number() {
+        assert_eq!(
+            states(b"\"12\"ab,")[3..],
+            [CellQuoteNumberEnd, CellCurrent, CellCurrent, CellSep]
+        );
+        assert_eq!(
+            states(b"\"12\"3.5\n")[3..],
+            [
+                CellQuoteNumberEnd,
+                CellCurrent,
+                CellCurrent,
+                CellCurrent,
+                NewLine,
+            ]
+        );
+        assert_eq!(
+            states(b"\"a\"b,")[2..],
+            [CellQuoteEnd, CellCurrent, CellSep]
+        );
+    }
+
+    #[test]
+    fn space_inside_unquoted_string() {
+        assert_eq!(
+            states(b"a b,c"),
+            [CellString, CellCurrent, CellCurrent, CellSep, CellString]
+        );
+        assert_eq!(
+            states(b"ab  c\n")[1..],
+            [CellCurrent, CellCurrent, CellCurrent, CellCurrent, NewLine]
+        );
+    }
+}
@@ -1,10 +1,88 @@
+use std::borrow::Cow;
+use std::ops::ControlFlow;
+
+use crate::cancel::CancelToken;
 use crate::parse_state::ParseState;
-use std::thread::Scope;
+use crate::sidecar::{sidecar_path, TypeHints};
 
 use crate::cell::{Cell, CellType};
-use crate::dframe::DataFrame;
+use crate::datetime::{Duration, Time};
+use crate::decompress::decompress;
+use crate::dframe::{CastPolicy, DataFrame};
+use crate::diagnostic::{self, Diagnostic};
+use crate::error::{Error, Result};
+use crate::glob;
+use crate::intern::StringPool;
+use crate::io::number::{parse_f64, parse_i64, parse_u64};
+use crate::iter::chunk_iter::DataFrameChunkIterator;
+use crate::options::{CellRule, ParsePlan, ParserOptions};
+use crate::scan::{count_byte, find_byte, find_structural};
+use crate::threads::run_jobs;
+use crate::warning::{ParseWarning, WarningKind};
 use vector::Vector;
 
+/// Smallest slice of the records worth its own thread, in bytes
+const MIN_SLICE_LEN: usize = 1 << 12;
+
+/// Cells scanned between two checks of the cancel token
+const CANCEL_CHECK_CELLS: usize = 1 << 12;
+
+/// Smallest file worth a thread of its own with [`CsvParser::parse_auto`],
+/// in bytes: below it, spawning and stitching cost more than they save
+const AUTO_BYTES_PER_THREAD: usize = 1 << 20;
+
+/// Returns `true` if `slice` is an integer written with leading zeros,
+/// such as `00123`
+#[inline]
+fn has_leading_zeros(slice: &[u8]) -> bool {
+    let digits = match slice.first() {
+        Some(b'-' | b'+') => &slice[1..],
+        _ => slice,
+    };
+    digits.len() > 1
+        && digits[0] == b'0'
+        && digits.iter().all(u8::is_ascii_digit)
+}
+
+/// Cells the parser lays rows out into
+trait RowBuffer {
+    /// Make room for the cells up to `row_end`, returns `false` if the
+    /// buffer cannot hold them
+    fn fits(&mut self, row_end: usize) -> bool;
+
+    /// Cells written so far
+    fn cells(&mut self) -> &mut [Cell];
+}
+
+/// Region of a buffer sized from the line count of the slice
+impl RowBuffer for [Cell] {
+    #[inline(always)]
+    fn fits(&mut self, row_end: usize) -> bool {
+        row_end <= self.len()
+    }
+
+    #[inline(always)]
+    fn cells(&mut self) -> &mut [Cell] {
+        self
+    }
+}
+
+/// Buffer growing with the rows, for single pass parsing
+impl RowBuffer for Vec<Cell> {
+    #[inline(always)]
+    fn fits(&mut self, row_end: usize) -> bool {
+        if self.len() < row_end {
+            self.resize(row_end, Cell::Null);
+        }
+        true
+    }
+
+    #[inline(always)]
+    fn cells(&mut self) -> &mut [Cell] {
+        self
+    }
+}
+
 pub struct CsvParser<'a> {
     /// Buffer to parse from
     byte_buffer: &'a [u8],
@@ -14,6 +92,8 @@ pub struct CsvParser<'a> {
     state: ParseState,
     /// Headers
     header_scanned: Vec<String>,
+    /// Byte ending a record
+    terminator: u8,
 }
 
 impl<'a> CsvParser<'a> {
@@ -25,13 +105,38 @@ impl<'a> CsvParser<'a> {
             offset: 0,
             header_scanned: Vec::new(),
             state: ParseState::Start,
+            terminator: b'\n',
         }
     }
 
+    /// Parser ending records with `terminator` instead of `\n`
+    #[inline]
+    pub fn with_terminator(mut self, terminator: u8) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Byte `c` as read by the state machine: the terminator reads as
+    /// a line end, and line ends that are not the terminator as spaces
+    #[inline(always)]
+    fn record_byte(&self, c: u8) -> u8 {
+        match c {
+            c if c == self.terminator => b'\n',
+            b'\n' => b' ',
+            c => c,
+        }
+    }
+
+    /// Current byte as read by the state machine, see
+    /// [`Self::record_byte`]
+    #[inline(always)]
+    fn curr_token(&self) -> Option<u8> {
+        self.get_curr_byte().map(|c| self.record_byte(c))
+    }
+
     #[inline(always)]
     pub fn get_curr_byte(&self) -> Option<u8> {
-        (self.offset < self.byte_buffer.len())
-            .then_some(self.byte_buffer[self.offset])
+        self.byte_buffer.get(self.offset).copied()
     }
 
     /// Move to next byte
@@ -43,14 +148,14 @@ impl<'a> CsvParser<'a> {
     /// Skip all the whitespaces
     #[inline]
     fn skip_whitespace(&mut self) {
-        while let Some(b' ' | b'\n' | 9..=13) = self.get_curr_byte() {
+        while let Some(b' ' | b'\n' | 9..=13) = self.curr_token() {
             self.move_next()
         }
     }
 
     #[inline(always)]
     fn scan_start(&self) -> ParseState {
-        match self.get_curr_byte() {
+        match self.curr_token() {
             Some(b'"') => ParseState::HeaderQuoteStart,
             Some(b',') => ParseState::HeaderSep,
             Some(b'\n') | None => ParseState::NewLine,
@@ -58,26 +163,39 @@ impl<'a> CsvParser<'a> {
         }
     }
 
+    /// Scan a quoted header name, whose doubled quotes are read as one
     #[inline]
     fn scan_header_quote(&mut self) -> String {
         self.move_next();
         let starting_point = self.offset;
+        let mut escaped = false;
 
         loop {
-            match self.get_curr_byte() {
+            match self.curr_token() {
+                Some(b'"')
+                    if self.byte_buffer.get(self.offset + 1) == Some(&b'"') =>
+                {
+                    escaped = true;
+                    self.offset += 2;
+                }
                 Some(b'"') => {
                     self.state = ParseState::HeaderQuoteEnd;
                     break;
                 }
+                // Unterminated quote, the header ends with the buffer
+                None => {
+                    self.state = ParseState::NewLine;
+                    break;
+                }
                 _ => self.move_next(),
             }
         }
 
-        unsafe {
-            core::str::from_utf8_unchecked(
-                &self.byte_buffer[starting_point..self.offset],
-            )
-            .to_owned()
+        let name = &self.byte_buffer[starting_point..self.offset];
+        if escaped {
+            String::from_utf8_lossy(&Self::unescape_quotes(name)).into_owned()
+        } else {
+            String::from_utf8_lossy(name).into_owned()
         }
     }
 
@@ -87,12 +205,12 @@ impl<'a> CsvParser<'a> {
         self.move_next();
 
         loop {
-            match self.get_curr_byte() {
+            match self.curr_token() {
                 Some(b',') => {
                     self.state = ParseState::HeaderSep;
                     break;
                 }
-                Some(b'\r' | b'\n') => {
+                Some(b'\r' | b'\n') | None => {
                     self.state = ParseState::NewLine;
                     break;
                 }
@@ -100,12 +218,8 @@ impl<'a> CsvParser<'a> {
             }
         }
 
-        unsafe {
-            core::str::from_utf8_unchecked(
-                &self.byte_buffer[starting_point..self.offset],
-            )
-            .to_owned()
-        }
+        String::from_utf8_lossy(&self.byte_buffer[starting_point..self.offset])
+            .into_owned()
     }
 
     /// Scan header
@@ -153,24 +267,110 @@ impl<'a> CsvParser<'a> {
         (self.header_scanned.to_owned(), self.offset)
     }
 
-    #[inline]
-    fn convert_from_slice(slice: &str, state: ParseState) -> (Cell, CellType) {
+    /// Cell type decided by the final `state` of a scanned cell
+    #[inline(always)]
+    fn cell_type_of(state: ParseState) -> CellType {
         match state {
             ParseState::CellNumberStart
             | ParseState::CellNumberCurrent
             | ParseState::CellNumberEnd
-            | ParseState::CellQuoteNumberEnd => {
-                (Cell::Number(slice.parse::<i64>().unwrap()), CellType::I64)
-            }
+            | ParseState::CellQuoteNumberEnd => CellType::I64,
 
             ParseState::CellDecimalEnd
             | ParseState::CellDecimalEndWithPointRead
             | ParseState::CellQuoteDecimalEnd
-            | ParseState::CellQuoteDecimalEndWithPointRead => {
-                (Cell::Decimal(slice.parse::<f64>().unwrap()), CellType::F64)
-            }
+            | ParseState::CellQuoteDecimalEndWithPointRead => CellType::F64,
 
-            _ => (Cell::String(slice.to_owned()), CellType::String),
+            _ => CellType::String,
+        }
+    }
+
+    /// Whether `state` is reached on the closing quote of a quoted cell
+    #[inline(always)]
+    fn closes_quote(state: ParseState) -> bool {
+        matches!(
+            state,
+            ParseState::CellQuoteEnd
+                | ParseState::CellQuoteNumberEnd
+                | ParseState::CellQuoteDecimalEnd
+                | ParseState::CellQuoteDecimalEndWithPointRead
+        )
+    }
+
+    /// Time (`HH:MM:SS`) or ISO 8601 duration written in `slice`
+    #[inline]
+    fn parse_temporal(slice: &str) -> Option<Cell> {
+        match slice.as_bytes().first()? {
+            b'0'..=b'9' => Time::parse(slice).map(Cell::Time),
+            b'P' | b'-' => Duration::parse(slice).map(Cell::Duration),
+            _ => None,
+        }
+    }
+
+    /// Convert `slice` into the cell decided by its `state`.
+    ///
+    /// Numbers are read from the bytes of the slice, see
+    /// [`crate::io::number`]. Integers above `i64::MAX` are read as
+    /// unsigned numbers, integers out of `u64` range too are promoted
+    /// to decimals, and numbers that fail to parse are kept as strings.
+    /// With `temporal` set, text is recognized as a time or a duration
+    /// when it is written as one. With a `pool`, text is interned and
+    /// read as a category, so that repeated values are not allocated
+    /// again.
+    #[inline]
+    fn convert_from_slice(
+        slice: &str,
+        state: ParseState,
+        temporal: bool,
+        pool: Option<&StringPool>,
+    ) -> Cell {
+        let as_text = |slice: &str| {
+            temporal
+                .then(|| Self::parse_temporal(slice))
+                .flatten()
+                .unwrap_or_else(|| Self::text_cell(slice, pool))
+        };
+        let as_decimal = |slice: &str| {
+            parse_f64(slice.as_bytes())
+                .map_or_else(|| as_text(slice), Cell::Decimal)
+        };
+
+        match Self::cell_type_of(state) {
+            CellType::I64 => match parse_i64(slice.as_bytes()) {
+                Some(number) => Cell::Number(number),
+                None => parse_u64(slice.as_bytes())
+                    .map_or_else(|| as_decimal(slice), Cell::UInt),
+            },
+            CellType::F64 => as_decimal(slice),
+            _ => as_text(slice),
+        }
+    }
+
+    /// Cell holding `text`: its code in `pool` if given (and not full),
+    /// a string otherwise
+    #[inline]
+    fn text_cell(text: &str, pool: Option<&StringPool>) -> Cell {
+        pool.and_then(|pool| pool.intern(text))
+            .map_or_else(|| Cell::String(text.to_owned()), Cell::Categorical)
+    }
+
+    /// Type of the cell `convert_from_slice` would return without
+    /// `temporal`, as chunks are read, without allocating it
+    #[inline]
+    fn type_from_slice(slice: &str, state: ParseState) -> CellType {
+        match Self::cell_type_of(state) {
+            CellType::I64 if parse_i64(slice.as_bytes()).is_some() => {
+                CellType::I64
+            }
+            CellType::I64 if parse_u64(slice.as_bytes()).is_some() => {
+                CellType::U64
+            }
+            CellType::I64 | CellType::F64
+                if parse_f64(slice.as_bytes()).is_some() =>
+            {
+                CellType::F64
+            }
+            _ => CellType::String,
         }
     }
 
@@ -217,22 +417,42 @@ impl<'a> CsvParser<'a> {
         mut_slices
     }
 
-    /// Get total lines from the file
-    /// Cannot work properly with csv files handling \r\n new line
-    /// (CRLF).
+    /// Run the state machine through the buffer and hand over each
+    /// completed cell to `on_cell` as `(field, record_end, slice, state)`,
+    /// where `field` is the index of the cell in its record, `record_end`
+    /// tells whether the cell closes the record, `slice` is the content
+    /// (`None` for empty cells) and `state` decides the type of the
+    /// cell. Unquoted content is trimmed, quoted content is kept as it
+    /// is between the quotes, with its doubled quotes read as one.
+    ///
+    /// A buffer not ending with a new line is closed as if it did, so
+    /// that the last cell of the buffer is not lost. The scan stops
+    /// early once `cancel` is cancelled, or when `on_cell` breaks.
     #[allow(unused_assignments)]
-    fn parse_content_on_buffer(
-        &mut self,
-        column_data: &mut [Cell],
-        res_type: &mut [CellType],
-    ) {
-        // Column data
+    fn scan_cells<F>(&mut self, cancel: Option<&CancelToken>, mut on_cell: F)
+    where
+        F: FnMut(
+            usize,
+            bool,
+            Option<Cow<'a, [u8]>>,
+            ParseState,
+        ) -> ControlFlow<()>,
+    {
+        let buffer = self.byte_buffer;
         let (mut start, mut end): (Option<usize>, Option<usize>) = (None, None);
-        let (mut save_state, mut arr_index) = (None, 0);
+        let (mut save_state, mut arr_index, mut field) = (None, 0, 0);
+        let mut escaped = false;
+
+        let terminator = self.terminator;
+        let closing_lf =
+            !buffer.is_empty() && buffer.last() != Some(&terminator);
+        let total = buffer.len() + closing_lf as usize;
+        let mut index = 0;
 
-        self.byte_buffer.iter().enumerate().for_each(|(index, c)| {
+        while index < total {
+            let c = buffer.get(index).map_or(b'\n', |c| self.record_byte(*c));
             let prev_state = self.state;
-            self.state = ParseState::get_scan_state_from_data(self.state, *c);
+            self.state = ParseState::get_scan_state_from_data(self.state, c);
 
             match self.state {
                 // Scan start, get the current state based on the
@@ -245,56 +465,65 @@ impl<'a> CsvParser<'a> {
                     start = Some(index);
                 }
 
-                // Starting quoted values,
+                // Starting quoted values, a point right after the
+                // opening quote is part of the value
                 ParseState::CellQuoteStart
                 | ParseState::CellQuoteNumberStart
-                | ParseState::CellQuoteDecimalStart
-                | ParseState::CellQuoteDecimalStartWithPointRead => {
+                | ParseState::CellQuoteDecimalStart => {
                     start = Some(index + 1);
                 }
 
-                // Scan start of quoted header string,
-                // read till the end of quote.
+                // Doubled quote: the quoted value goes on past the quote
+                // taken for its end
+                ParseState::CellQuoteCurrent
+                    if Self::closes_quote(prev_state) =>
+                {
+                    (end, save_state, escaped) = (None, None, true);
+                }
+
+                // End of cell: hand over the slice read so far
                 ParseState::CellNumberEnd
                 | ParseState::CellDecimalEnd
                 | ParseState::CellDecimalEndWithPointRead
                 | ParseState::CellSep
                 | ParseState::NewLine => {
-                    let (push_value, result_type) = if end.is_none()
-                        && start.is_none()
-                    {
-                        (Cell::Null, CellType::Null)
+                    let slice = if end.is_none() && start.is_none() {
+                        None
                     } else {
                         let ep = end.unwrap_or(index);
-                        let save_state_as = save_state.unwrap_or(self.state);
                         let sp = start.unwrap_or(index);
-
-                        if sp != ep {
-                            let slice =
-                                Self::trim_ascii(&self.byte_buffer[sp..ep]);
-                            let str_slice = unsafe {
-                                core::str::from_utf8_unchecked(slice)
-                            };
-
-                            Self::convert_from_slice(str_slice, save_state_as)
-                        } else {
-                            (Cell::Null, CellType::Null)
-                        }
+                        let quoted = save_state.is_some_and(Self::closes_quote);
+
+                        (sp != ep).then(|| {
+                            let content = &buffer[sp..ep];
+                            match (quoted, escaped) {
+                                (true, true) => {
+                                    Cow::Owned(Self::unescape_quotes(content))
+                                }
+                                (true, false) => Cow::Borrowed(content),
+                                _ => Cow::Borrowed(Self::trim_ascii(content)),
+                            }
+                        })
                     };
 
-                    let col = arr_index % res_type.len();
-                    (start, end, save_state) = (None, None, None);
-
-                    if arr_index < column_data.len() {
-                        column_data[arr_index] = push_value;
-
-                        let prev_type = res_type[col];
-                        let val = prev_type.infer_type(result_type);
-
-                        res_type[col] = val;
+                    let save_state_as = save_state.unwrap_or(self.state);
+                    let record_end = c == b'\n';
+                    if on_cell(field, record_end, slice, save_state_as)
+                        .is_break()
+                    {
+                        return;
                     }
 
+                    (start, end, save_state, escaped) =
+                        (None, None, None, false);
                     arr_index += 1;
+                    field = if record_end { 0 } else { field + 1 };
+
+                    if arr_index % CANCEL_CHECK_CELLS == 0
+                        && cancel.is_some_and(CancelToken::is_cancelled)
+                    {
+                        return;
+                    }
                 }
 
                 // Scan start of quoted header string,
@@ -307,17 +536,297 @@ impl<'a> CsvParser<'a> {
                     save_state = Some(self.state);
                 }
 
-                ParseState::CarriageRet => {
-                    if end.is_none() {
-                        end = Some(index);
-                        save_state = Some(prev_state);
-                    }
+                ParseState::CarriageRet if end.is_none() => {
+                    end = Some(index);
+                    save_state = Some(prev_state);
+                }
+
+                // Text after a closing quote is dropped, the quoted
+                // content is read as a string
+                ParseState::CellCurrent if end.is_some() => {
+                    save_state = Some(ParseState::CellQuoteEnd);
                 }
 
                 // Scan as it is
                 _ => {}
             }
+
+            // Skip the bytes that cannot change the state: anything
+            // but a quote inside quotes, and anything but a separator,
+            // quote or line end inside an unquoted string
+            index += 1;
+            let rest = buffer.get(index..).unwrap_or_default();
+            index += match self.state {
+                ParseState::CellQuoteCurrent => find_byte(rest, b'"'),
+                ParseState::CellCurrent => find_structural(rest, terminator),
+                _ => Some(0),
+            }
+            .unwrap_or(rest.len());
+        }
+    }
+
+    /// Parse the buffer into `column_data` as laid out by `plan`,
+    /// recording the inferred type of each column in `res_type` and
+    /// issues found in cells in `warnings`.
+    ///
+    /// Cells of skipped columns are not converted, and rows rejected
+    /// by the filter of the plan are overwritten by the next row. The
+    /// parse stops after the first ragged record the policy of the plan
+    /// fails on. Returns total rows written.
+    fn parse_content_on_buffer<B: RowBuffer + ?Sized>(
+        &mut self,
+        column_data: &mut B,
+        res_type: &mut [CellType],
+        warnings: &mut Vec<ParseWarning>,
+        plan: &ParsePlan,
+    ) -> usize {
+        let (source_width, width) = (plan.columns.len(), plan.width);
+        let filter_col = plan.filter.as_ref().map(|(col, _)| *col);
+        let (mut row, mut rejected) = (0, false);
+        let mut row_warnings = warnings.len();
+
+        self.scan_cells(plan.cancel, |field, record_end, slice, state| {
+            let slice = slice.as_deref();
+            let target = plan.columns.get(field).copied().flatten();
+            let is_key = filter_col == Some(field);
+            let row_end = (row + 1) * width;
+
+            // Fields past the last column are dropped
+            if !rejected
+                && column_data.fits(row_end)
+                && (target.is_some() || is_key)
+            {
+                let rule = plan.rules[field];
+                let slice = slice.filter(|slice| {
+                    !plan
+                        .null_values
+                        .iter()
+                        .any(|null| null.as_bytes() == *slice)
+                });
+                let mut invalid_utf8 = false;
+                let mut push_value = match slice {
+                    Some(slice) => {
+                        let str_slice = if plan.unsafe_fast {
+                            // SAFETY: the caller of `unsafe_fast` vouched
+                            // for the buffer being valid UTF-8
+                            Cow::Borrowed(unsafe {
+                                core::str::from_utf8_unchecked(slice)
+                            })
+                        } else {
+                            let str_slice = String::from_utf8_lossy(slice);
+                            invalid_utf8 = matches!(str_slice, Cow::Owned(_));
+                            str_slice
+                        };
+                        rule.and_then(|rule| rule.convert(slice, &str_slice))
+                            .unwrap_or_else(|| {
+                                if plan.leading_zeros
+                                    && Self::cell_type_of(state)
+                                        == CellType::I64
+                                    && has_leading_zeros(slice)
+                                {
+                                    return Cell::String(str_slice.to_string());
+                                }
+                                // The predicate of the filter reads the
+                                // key as a string, interned once passed
+                                Self::convert_from_slice(
+                                    &str_slice,
+                                    state,
+                                    plan.temporal,
+                                    plan.pool.filter(|_| !is_key),
+                                )
+                            })
+                    }
+                    None => Cell::Null,
+                };
+
+                if let (true, Some((_, predicate))) = (is_key, &plan.filter) {
+                    rejected = !predicate(&push_value);
+                    if let (Cell::String(text), Some(_), false) =
+                        (&push_value, target, rejected)
+                    {
+                        push_value = Self::text_cell(text, plan.pool);
+                    }
+                }
+                if let Some(col) = target {
+                    if invalid_utf8 {
+                        warnings.push(ParseWarning {
+                            row,
+                            column: col,
+                            kind: WarningKind::InvalidUtf8,
+                        });
+                    }
+                    if Self::cell_type_of(state) == CellType::I64
+                        && matches!(push_value, Cell::Decimal(_))
+                    {
+                        warnings.push(ParseWarning {
+                            row,
+                            column: col,
+                            kind: WarningKind::IntegerOverflow,
+                        });
+                    }
+                    if let (Some(CellRule::CheckedInteger), Some(slice)) =
+                        (rule, slice)
+                    {
+                        if matches!(push_value, Cell::Null) {
+                            warnings.push(ParseWarning {
+                                row,
+                                column: col,
+                                kind: CellRule::integer_issue(slice),
+                            });
+                        }
+                    }
+                    if matches!(rule, Some(CellRule::ExactDecimal))
+                        && slice.is_some()
+                        && matches!(push_value, Cell::Null)
+                    {
+                        warnings.push(ParseWarning {
+                            row,
+                            column: col,
+                            kind: WarningKind::InvalidDecimal,
+                        });
+                    }
+                    if matches!(rule, Some(CellRule::Bytes(_)))
+                        && !matches!(push_value, Cell::Bytes(_) | Cell::Null)
+                    {
+                        warnings.push(ParseWarning {
+                            row,
+                            column: col,
+                            kind: WarningKind::InvalidEncoding,
+                        });
+                    }
+                    column_data.cells()[row * width + col] = push_value;
+                }
+            }
+
+            if !record_end {
+                return ControlFlow::Continue(());
+            }
+
+            // Blank lines hold no record
+            if field == 0 && slice.is_none() && source_width > 1 {
+                warnings.truncate(row_warnings);
+                rejected = false;
+                return ControlFlow::Continue(());
+            }
+
+            // Short record: the missing fields are nulls
+            if !rejected && column_data.fits(row_end) {
+                (field + 1..source_width).for_each(|source_col| {
+                    if filter_col == Some(source_col) {
+                        if let Some((_, predicate)) = &plan.filter {
+                            rejected = !predicate(&Cell::Null);
+                        }
+                    }
+                    if let Some(col) = plan.columns[source_col] {
+                        column_data.cells()[row * width + col] = Cell::Null;
+                    }
+                });
+            }
+            let failed =
+                !rejected && plan.ragged_rows.rejects(field + 1, source_width);
+            if field + 1 != source_width {
+                warnings.push(ParseWarning {
+                    row,
+                    column: 0,
+                    kind: WarningKind::RaggedRow { fields: field + 1 },
+                });
+            }
+
+            // End of record: keep the row if it passed the filter
+            if !rejected && column_data.fits(row_end) {
+                column_data.cells()[row * width..row_end]
+                    .iter()
+                    .zip(res_type.iter_mut())
+                    .for_each(|(cell, t)| *t = t.infer_type(cell.cell_type()));
+                row += 1;
+            } else {
+                warnings.truncate(row_warnings);
+            }
+            row_warnings = warnings.len();
+            rejected = false;
+
+            if failed {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        row
+    }
+
+    /// Infer the type of each column in `res_type` from the buffer,
+    /// without materializing any `Cell`, and flag in `negative` the
+    /// columns holding a negative integer.
+    pub(crate) fn infer_types_on_buffer(
+        &mut self,
+        res_type: &mut [CellType],
+        negative: &mut [bool],
+    ) {
+        self.scan_cells(None, |field, _, slice, state| {
+            let slice = slice.as_deref();
+            let result_type =
+                slice.map_or(
+                    CellType::Null,
+                    |slice| match core::str::from_utf8(slice) {
+                        Ok(str_slice) => {
+                            Self::type_from_slice(str_slice, state)
+                        }
+                        Err(_) => CellType::String,
+                    },
+                );
+
+            if let Some(t) = res_type.get_mut(field) {
+                *t = t.infer_type(result_type);
+            }
+            if result_type == CellType::I64
+                && slice.is_some_and(|slice| slice.starts_with(b"-"))
+            {
+                if let Some(negative) = negative.get_mut(field) {
+                    *negative = true;
+                }
+            }
+            ControlFlow::Continue(())
+        });
+    }
+
+    /// Move the first `written[i]` rows of each thread region `i` (of
+    /// `length[i].0` rows each) next to each other in a new buffer of
+    /// `total` rows.
+    fn compact_rows(
+        result: &mut [Cell],
+        length: &[(usize, usize, usize)],
+        written: &[usize],
+        width: usize,
+        total: usize,
+    ) -> Vector<Cell> {
+        let mut compact: Vector<Cell> = Vector::zeroed(total * width);
+        let (mut region_start, mut index) = (0, 0);
+
+        length.iter().zip(written).for_each(|((len, _, _), rows)| {
+            result[region_start..region_start + rows * width]
+                .iter_mut()
+                .for_each(|cell| {
+                    compact[index] = std::mem::replace(cell, Cell::Null);
+                    index += 1;
+                });
+            region_start += len * width;
         });
+
+        compact
+    }
+
+    /// Quoted content `slice` with each doubled quote read as one
+    fn unescape_quotes(slice: &[u8]) -> Vec<u8> {
+        let mut unescaped = Vec::with_capacity(slice.len());
+        let mut bytes = slice.iter().peekable();
+        while let Some(byte) = bytes.next() {
+            unescaped.push(*byte);
+            if *byte == b'"' {
+                bytes.next_if_eq(&&b'"');
+            }
+        }
+        unescaped
     }
 
     /// Trim ascii having whitespaces, and returns a new `slice`
@@ -336,118 +845,433 @@ impl<'a> CsvParser<'a> {
         }
     }
 
-    /// Returns total lines with starting point and ending point
-    /// of the buffer to be read.
+    /// Split `mmaped_buffer` at line ends in at most `thread_number`
+    /// non-empty slices, of at least `MIN_SLICE_LEN` bytes each (but
+    /// the last one), as `(start, end)`: small buffers or large thread
+    /// counts give fewer slices, and an empty buffer a single empty
+    /// slice.
     ///
     /// ## Note
     /// Not accurate, should also work for multi-lined cell.
-    fn get_total_lines_in_a_file<'c>(
-        mmaped_buffer: &'c [u8],
-        scope: &'c Scope<'c, '_>,
+    fn split_at_lines(
+        mmaped_buffer: &[u8],
         thread_number: usize,
-    ) -> Vec<(usize, usize, usize)> {
-        // Thread should be processing sub-array of elements.
-        let slots_division = mmaped_buffer.len() / thread_number;
-
-        // Parsing and finding the end point of the line.
-        let mut end_prefix = mmaped_buffer[slots_division..]
-            .iter()
-            .position(|c| *c == b'\n')
-            .unwrap_or(0)
-            + slots_division;
+        terminator: u8,
+    ) -> Vec<(usize, usize)> {
+        let len = mmaped_buffer.len();
+        let thread_number =
+            thread_number.clamp(1, len / MIN_SLICE_LEN + 1).max(1);
 
-        let mut slices: Vec<(&'c [u8], usize, usize)> =
-            Vec::with_capacity(thread_number);
+        // Thread should be processing sub-array of elements.
+        let slots_division = len / thread_number;
 
-        slices.push((&mmaped_buffer[..end_prefix], 0, end_prefix));
+        let mut slices: Vec<(usize, usize)> = Vec::with_capacity(thread_number);
+        let mut start = 0;
+        for multiplier in 1..thread_number {
+            let end_pos = multiplier * slots_division;
 
-        slices.extend((1..thread_number - 1).map(|multiplier| {
-            let spos = end_prefix + 1;
-            let end_pos = (multiplier + 1) * slots_division;
+            // Slices already reaching past this division are merged
+            if end_pos < start {
+                continue;
+            }
 
-            // Seek the start position to start from position next to \n
-            let epos = mmaped_buffer[end_pos..]
-                .iter()
-                .position(|c| *c == b'\n')
-                .unwrap_or(0)
-                + end_pos;
+            // Seek the end of the line holding the division
+            let Some(epos) = find_byte(&mmaped_buffer[end_pos..], terminator)
+                .map(|pos| pos + end_pos)
+            else {
+                break;
+            };
 
-            end_prefix = epos;
-            (&mmaped_buffer[spos..epos], spos, epos)
-        }));
+            slices.push((start, epos));
+            start = epos + 1;
+        }
+        slices.push((start.min(len), len));
+        slices
+    }
 
-        slices.push((
-            &mmaped_buffer[end_prefix..],
-            end_prefix + 1,
-            mmaped_buffer.len(),
-        ));
+    /// Returns total lines with starting point and ending point
+    /// of each slice of the buffer to be read, see
+    /// [`Self::split_at_lines`].
+    fn get_total_lines_in_a_file(
+        mmaped_buffer: &[u8],
+        thread_number: usize,
+        terminator: u8,
+    ) -> Vec<(usize, usize, usize)> {
+        let slices =
+            Self::split_at_lines(mmaped_buffer, thread_number, terminator);
 
-        slices
-            .into_iter()
-            .map(|(slice, start, end)| {
-                (
-                    scope.spawn(move || slice.split(|c| *c == b'\n').count()),
-                    start,
-                    end,
-                )
-            })
-            .map(|(c, st, ed)| (c.join().unwrap(), st, ed))
-            .collect()
+        run_jobs(slices.into_iter().map(|(start, end)| {
+            let slice = &mmaped_buffer[start..end];
+            move || (count_byte(slice, terminator) + 1, start, end)
+        }))
     }
 
     /// Parsing CSV file `file_name` using multiple threads
     ///
     /// Opens the file in memory mapped IO (read-only) and collects the data
-    /// on the memory, to be used later via `DataFrame` struct
+    /// on the memory, to be used later via `DataFrame` struct. Gzip and
+    /// zstd compressed files are detected from their magic bytes and
+    /// decompressed first (requires `gzip` / `zstd` features).
+    ///
+    /// Any `total_threads` is valid: it is clamped to the size of the
+    /// file, so that small files are read by fewer threads (and `0`
+    /// means one thread).
     ///
     /// To do: Selecting different strategies for parsing: Do either
     /// 1. Read alternate lines
     /// 2. Read batch lines
     ///     - (challenge: seeking starting point to valid new line,
-    ///        so this part is incomplete)
+    ///       so this part is incomplete)
     pub fn parse_multi_threaded(
         file_name: &'a str,
         total_threads: usize,
-    ) -> DataFrame {
-        let fd = std::fs::OpenOptions::new()
-            .read(true)
-            .open(file_name)
-            .unwrap();
-
-        let mmaped = unsafe {
-            memmap2::MmapOptions::new()
-                .populate()
-                .stack()
-                .map(&fd)
-                .unwrap()
+    ) -> Result<DataFrame> {
+        Self::parse_with_options(
+            file_name,
+            total_threads,
+            &ParserOptions::default(),
+        )
+    }
+
+    /// Parsing CSV file `file_name` using multiple threads, reading
+    /// records as described by `options`.
+    ///
+    /// The type hints in `<file_name>.types`, if the file exists, are
+    /// added to `options` (see [`crate::sidecar`]), unless
+    /// [`ParserOptions::skip_sidecar`] is set.
+    pub fn parse_with_options(
+        file_name: &'a str,
+        total_threads: usize,
+        options: &ParserOptions,
+    ) -> Result<DataFrame> {
+        let hints = match options.skip_sidecar {
+            true => None,
+            false => TypeHints::load(&sidecar_path(file_name))?,
+        };
+
+        let fd = std::fs::OpenOptions::new().read(true).open(file_name)?;
+
+        let mmaped =
+            unsafe { memmap2::MmapOptions::new().populate().stack().map(&fd)? };
+
+        // Compressed files are inflated in memory before the
+        // state machine runs, plain files are parsed from the map.
+        let decoded = decompress(&mmaped)?;
+        let buffer = decoded.as_deref().unwrap_or(&mmaped);
+
+        // Hints are kept for the columns of the file only, so that a
+        // sidecar shared by several drops does not fail on one missing
+        // a column.
+        let hinted;
+        let options = match hints {
+            Some(hints) => {
+                let (header, _) = options.split_header(buffer);
+                hinted = hints.for_header(&header).apply(options.clone());
+                &hinted
+            }
+            None => options,
         };
 
-        let mut p = CsvParser::new(&mmaped);
+        let mut df =
+            Self::parse_buffer_with_options(buffer, total_threads, options)?;
+
+        if let Some(name) = &options.source_column {
+            let path = Self::text_cell(file_name, options.string_pool.as_ref());
+            df.add_column(name, vec![path; df.len()])?;
+            if let Some(pool) = &options.string_pool {
+                df.adopt_pool(pool);
+            }
+        }
+        Ok(df)
+    }
+
+    /// Parsing every file matching `pattern` into a single Data Frame,
+    /// with `total_threads` files parsed at a time. See [`crate::glob`]
+    /// for the wildcards of `pattern`.
+    #[inline]
+    pub fn parse_glob(
+        pattern: &str,
+        total_threads: usize,
+    ) -> Result<DataFrame> {
+        Self::parse_glob_with_options(
+            pattern,
+            total_threads,
+            &ParserOptions::default(),
+        )
+    }
+
+    /// Parsing every file matching `pattern` like [`Self::parse_glob`],
+    /// reading each file as described by `options`.
+    ///
+    /// Rows are concatenated in the order of the paths. Every file must
+    /// hold the same columns, in any order: column types are unified
+    /// like while parsing, and columns holding values of different
+    /// types in different files are read as strings. Set
+    /// [`ParserOptions::source_column`] to tell which file each row
+    /// comes from.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    ///
+    /// let dir = std::env::temp_dir().join("nn_parse_glob");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// std::fs::write(dir.join("2023-01.csv"), "day,sales\n1,10\n2,12\n").unwrap();
+    /// std::fs::write(dir.join("2023-02.csv"), "sales,day\n9.5,1\n").unwrap();
+    /// std::fs::write(dir.join("2022-12.csv"), "day,sales\n31,8\n").unwrap();
+    ///
+    /// let pattern = dir.join("2023-*.csv");
+    /// let options = ParserOptions::new().source_column("source_file");
+    /// let df = CsvParser::parse_glob_with_options(pattern.to_str().unwrap(), 2, &options)
+    ///     .unwrap();
+    /// assert_eq!(df.header(), ["day", "sales", "source_file"]);
+    /// assert_eq!(df.dtypes(), [CellType::I64, CellType::F64, CellType::String]);
+    /// assert_eq!(df.column("sales").unwrap().as_f64(), [Some(10.0), Some(12.0), Some(9.5)]);
+    /// assert!(df.column("source_file").unwrap().as_str()[2].unwrap().ends_with("2023-02.csv"));
+    /// # std::fs::remove_dir_all(dir).unwrap();
+    /// ```
+    pub fn parse_glob_with_options(
+        pattern: &str,
+        total_threads: usize,
+        options: &ParserOptions,
+    ) -> Result<DataFrame> {
+        #[cfg(feature = "threads")]
+        if let Some(pool) = &options.thread_pool {
+            let options = ParserOptions {
+                thread_pool: None,
+                ..options.clone()
+            };
+            return pool.install(|| {
+                Self::parse_glob_with_options(pattern, total_threads, &options)
+            });
+        }
+
+        let paths: Vec<String> = glob::expand(pattern)
+            .into_iter()
+            .filter_map(|path| path.to_str().map(str::to_owned))
+            .collect();
+        if paths.is_empty() {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no file matches {}", pattern),
+            )));
+        }
+
+        // Each file is parsed on a thread of its own
+        let mut frames = Vec::with_capacity(paths.len());
+        for batch in paths.chunks(total_threads.max(1)) {
+            let parsed = run_jobs(batch.iter().map(|path| {
+                move || CsvParser::parse_with_options(path, 1, options)
+            }));
+            for df in parsed {
+                frames.push(df?);
+            }
+        }
+
+        // Columns of two different types in two files become strings
+        let mut df = frames.remove(0);
+        for index in 0..df.hlen() {
+            let name = df.header()[index].clone();
+            let dtypes = frames
+                .iter()
+                .map(|frame| Ok(frame.dtypes()[frame.column_index(&name)?]))
+                .collect::<Result<Vec<_>>>()?;
+            let unified =
+                dtypes.iter().fold(df.dtypes()[index], |unified, dtype| {
+                    unified.infer_type(*dtype)
+                });
+            if unified != CellType::String {
+                continue;
+            }
+            for frame in std::iter::once(&mut df).chain(frames.iter_mut()) {
+                frame.cast(
+                    name.as_str(),
+                    CellType::String,
+                    CastPolicy::Strict,
+                )?;
+            }
+        }
+
+        for frame in frames {
+            df.extend(frame)?;
+        }
+        df.unify_unsigned();
+        Ok(df)
+    }
+
+    /// Parsing CSV content from an in-memory `buffer` using
+    /// `total_threads` threads.
+    #[inline]
+    pub fn parse_buffer(mmaped: &[u8], total_threads: usize) -> DataFrame {
+        let (scanned_header, mmaped_slice) = Self::split_header(mmaped, b'\n');
+        let plan = ParsePlan::identity(scanned_header.len());
+        let (result, res, warnings) =
+            Self::parse_body(mmaped_slice, &plan, total_threads);
+
+        let mut df =
+            DataFrame::new(result, scanned_header, res).with_warnings(warnings);
+        df.unify_unsigned();
+        df
+    }
+
+    /// Parsing CSV content from an in-memory `buffer` using
+    /// `total_threads` threads, reading records as described by `options`
+    pub fn parse_buffer_with_options(
+        mmaped: &[u8],
+        total_threads: usize,
+        options: &ParserOptions,
+    ) -> Result<DataFrame> {
+        #[cfg(feature = "threads")]
+        if let Some(pool) = &options.thread_pool {
+            let options = ParserOptions {
+                thread_pool: None,
+                ..options.clone()
+            };
+            return pool.install(|| {
+                Self::parse_buffer_with_options(mmaped, total_threads, &options)
+            });
+        }
+
+        let (scanned_header, mmaped_slice) = options.split_header(mmaped);
+        let (mut units, mmaped_slice) =
+            options.split_units(&scanned_header, mmaped_slice);
+        let mmaped_slice = options.select_records(mmaped_slice);
+        let plan = options.plan(&scanned_header)?;
+
+        let cancelled = || plan.cancel.is_some_and(CancelToken::is_cancelled);
+        if cancelled() {
+            return Err(Error::Cancelled { rows: 0 });
+        }
+        let (result, res, warnings) =
+            Self::parse_body(mmaped_slice, &plan, total_threads);
+        if cancelled() {
+            return Err(Error::Cancelled {
+                rows: result.len() / plan.width.max(1),
+            });
+        }
+        let expected = plan.columns.len();
+        let ragged = warnings.iter().find_map(|warning| match warning.kind {
+            WarningKind::RaggedRow { fields }
+                if plan.ragged_rows.rejects(fields, expected) =>
+            {
+                Some((warning.row, fields))
+            }
+            _ => None,
+        });
+        if let Some((row, found)) = ragged {
+            return Err(Error::RaggedRow {
+                row,
+                expected,
+                found,
+            });
+        }
+
+        let header: Vec<String> = scanned_header
+            .into_iter()
+            .zip(plan.columns.iter())
+            .filter_map(|(name, col)| col.map(|_| name))
+            .collect();
+        units.retain(|name, _| header.contains(name));
+
+        let mut df = DataFrame::new(result, header, res)
+            .with_warnings(warnings)
+            .with_units(units);
+        if let Some(pool) = plan.pool {
+            df.adopt_pool(pool);
+        }
+        df.unify_unsigned();
+        for (name, dtype) in &options.column_types {
+            // Columns may be left out by `use_columns`
+            if df.header().contains(name) {
+                df.cast(name, *dtype, CastPolicy::NullOnError)?;
+            }
+        }
+        if options.leading_zeros {
+            // Identifier columns keep the text of every field
+            let identifiers: Vec<String> = (0..df.hlen())
+                .filter(|index| df.dtypes()[*index] == CellType::String)
+                .filter(|index| {
+                    df.iter().any(|row| {
+                        matches!(&row[*index], Cell::String(text)
+                            if has_leading_zeros(text.as_bytes()))
+                    })
+                })
+                .map(|index| df.header()[index].clone())
+                .collect();
+            for name in &identifiers {
+                df.cast(name, CellType::String, CastPolicy::Strict)?;
+            }
+        }
+
+        Ok(df)
+    }
+
+    /// Scan the header of `buffer`, ending with `terminator`, returning
+    /// the header names and the remaining body (trimmed) that holds the
+    /// records.
+    pub(crate) fn split_header(
+        buffer: &[u8],
+        terminator: u8,
+    ) -> (Vec<String>, &[u8]) {
+        // Files written for Excel start with a byte order mark
+        let buffer =
+            buffer.strip_prefix("\u{feff}".as_bytes()).unwrap_or(buffer);
+        let mut p = CsvParser::new(buffer).with_terminator(terminator);
         let (scanned_header, offset_from_scanner) = p.scan_header();
         let next_pos = offset_from_scanner
-            + mmaped
+            + buffer
                 .iter()
                 .skip(offset_from_scanner)
-                .position(|c| *c == b'\n')
-                .unwrap_or(0);
+                .position(|c| *c == terminator)
+                .map_or(0, |pos| pos + 1);
+
+        (scanned_header, Self::trim_ascii(&buffer[next_pos..]))
+    }
+
+    /// Parse the records in `mmaped_slice` (no header) using
+    /// `total_threads` threads, laying out and filtering the records as
+    /// described by `plan`.
+    ///
+    /// Returns the row-major cells, the type of each column and the
+    /// issues found in cells.
+    pub(crate) fn parse_body(
+        mmaped_slice: &[u8],
+        plan: &ParsePlan,
+        total_threads: usize,
+    ) -> (Vector<Cell>, Vector<CellType>, Vec<ParseWarning>) {
+        let width = plan.width;
 
-        let mmaped_slice = Self::trim_ascii(&mmaped[next_pos..]);
+        if mmaped_slice.is_empty() || width == 0 {
+            let mut res = Vector::zeroed(width);
+            res.fill(CellType::Null);
+            return (Vector::zeroed(0), res, Vec::new());
+        }
+        if plan.single_pass {
+            return Self::parse_body_single_pass(
+                mmaped_slice,
+                plan,
+                total_threads,
+            );
+        }
 
         // Calculate total lines read
         // To do: single threaded CSV parsing
-        let length = std::thread::scope(|scope| {
-            Self::get_total_lines_in_a_file(mmaped_slice, scope, total_threads)
-        });
+        let length = Self::get_total_lines_in_a_file(
+            mmaped_slice,
+            total_threads,
+            plan.terminator,
+        );
 
-        let c = length.iter().fold(0, |prev, curr| prev + curr.0) - 1;
+        let c = length.iter().fold(0, |prev, curr| prev + curr.0);
 
         // Initialized result with zero value.
-        let mut result: Vector<Cell> = Vector::zeroed(c * scanned_header.len());
+        let mut result: Vector<Cell> = Vector::zeroed(c * width);
         let mut result_types: Vector<Vector<CellType>> =
-            Vector::zeroed(total_threads);
+            Vector::zeroed(length.len());
 
         result_types.iter_mut().for_each(|res| {
-            *res = Vector::zeroed(scanned_header.len());
+            *res = Vector::zeroed(width);
             res.fill(CellType::Null);
         });
 
@@ -457,47 +1281,177 @@ impl<'a> CsvParser<'a> {
             &mut result,
             &length,
             // total_threads,
-            scanned_header.len(),
+            width,
         );
 
-        std::thread::scope(|scope| {
-            // Trim whitespaces
-            // To do: for each thread, start from offset just next to new line
-            let mmaped2 = &mmaped_slice;
+        // Trim whitespaces
+        // To do: for each thread, start from offset just next to new line
+        let mmaped2 = &mmaped_slice;
 
+        let written = run_jobs(
             sliced_buffer
                 .iter_mut()
                 .zip(result_types.iter_mut().map(|c| &mut c[..]))
-                .zip(length)
-                .enumerate()
-                .for_each(|(_, ((res, res_types), (len, start, end)))| {
+                .zip(length.iter().copied())
+                .map(|((res, res_types), (len, start, end))| {
                     // Each thread is alloted a specific `non-overlapping` region of the
                     // slice in `result`, which is ensured by function `split_slices`
                     // The values are recorded in res.
-                    debug_assert_eq!(res.len(), len * scanned_header.len());
-                    // let type_slice = &mut result_types[index][..];
-                    scope.spawn(move || {
-                        CsvParser::new(&mmaped2[start..end])
-                            .parse_content_on_buffer(res, &mut res_types[..]);
-                    });
-                });
+                    debug_assert_eq!(res.len(), len * width);
+                    move || {
+                        let mut warnings = Vec::new();
+                        let rows = CsvParser::new(&mmaped2[start..end])
+                            .with_terminator(plan.terminator)
+                            .parse_content_on_buffer(
+                                &mut res[..],
+                                &mut res_types[..],
+                                &mut warnings,
+                                plan,
+                            );
+                        (rows, warnings)
+                    }
+                }),
+        );
+
+        let (written, warnings): (Vec<usize>, Vec<Vec<ParseWarning>>) =
+            written.into_iter().unzip();
+        let warnings = Self::shift_warnings(&written, warnings);
+
+        // Rows filtered out (or over-counted) leave gaps at the end of
+        // each thread's region.
+        let total_written = written.iter().sum::<usize>();
+        if total_written != c {
+            result = Self::compact_rows(
+                &mut result,
+                &length,
+                &written,
+                width,
+                total_written,
+            );
+        }
+
+        let res = Self::merge_types(width, result_types.iter().map(|t| &t[..]));
+
+        (result, res, warnings)
+    }
+
+    /// Parse the records in `mmaped_slice` like [`Self::parse_body`],
+    /// in a single pass: each thread lays its rows out in a buffer of
+    /// its own growing with the rows, instead of a region sized from a
+    /// first scan counting the lines, and the buffers are stitched
+    /// together at the end.
+    fn parse_body_single_pass(
+        mmaped_slice: &[u8],
+        plan: &ParsePlan,
+        total_threads: usize,
+    ) -> (Vector<Cell>, Vector<CellType>, Vec<ParseWarning>) {
+        let width = plan.width;
+        let slices =
+            Self::split_at_lines(mmaped_slice, total_threads, plan.terminator);
+
+        let parsed = run_jobs(slices.into_iter().map(|(start, end)| {
+            let slice = &mmaped_slice[start..end];
+            move || {
+                let (mut cells, mut warnings) = (Vec::new(), Vec::new());
+                let mut types = vec![CellType::Null; width];
+                let rows = CsvParser::new(slice)
+                    .with_terminator(plan.terminator)
+                    .parse_content_on_buffer(
+                        &mut cells,
+                        &mut types[..],
+                        &mut warnings,
+                        plan,
+                    );
+                // Rows filtered out leave a row at the end
+                cells.truncate(rows * width);
+                (cells, types, (rows, warnings))
+            }
+        }));
+
+        let total = parsed.iter().map(|(cells, _, _)| cells.len()).sum();
+        let mut result: Vector<Cell> = Vector::zeroed(total);
+        let mut index = 0;
+        let mut types = Vec::with_capacity(parsed.len());
+        let mut written = Vec::with_capacity(parsed.len());
+        for (cells, thread_types, thread_written) in parsed {
+            cells.into_iter().for_each(|cell| {
+                result[index] = cell;
+                index += 1;
+            });
+            types.push(thread_types);
+            written.push(thread_written);
+        }
+
+        let (written, warnings): (Vec<usize>, Vec<Vec<ParseWarning>>) =
+            written.into_iter().unzip();
+        let warnings = Self::shift_warnings(&written, warnings);
+        let res = Self::merge_types(width, types.iter().map(|t| &t[..]));
+
+        (result, res, warnings)
+    }
+
+    /// Warnings of each thread, which wrote `written` rows, shifted
+    /// from the rows of the thread to the rows of the result
+    fn shift_warnings(
+        written: &[usize],
+        warnings: Vec<Vec<ParseWarning>>,
+    ) -> Vec<ParseWarning> {
+        let mut row_offset = 0;
+        written
+            .iter()
+            .zip(warnings)
+            .flat_map(|(rows, warnings)| {
+                let offset = row_offset;
+                row_offset += rows;
+                warnings.into_iter().map(move |warning| ParseWarning {
+                    row: warning.row + offset,
+                    ..warning
+                })
+            })
+            .collect()
+    }
+
+    /// Type of each of the `width` columns, from the types inferred by
+    /// each thread
+    fn merge_types<'t>(
+        width: usize,
+        types: impl Iterator<Item = &'t [CellType]>,
+    ) -> Vector<CellType> {
+        let mut res = Vector::zeroed(width);
+        res.fill(CellType::Null);
+        types.for_each(|arr| {
+            res.iter_mut()
+                .zip(arr)
+                .for_each(|(p, c)| *p = p.infer_type(*c))
         });
+        res
+    }
 
-        let res = result_types.iter_mut().fold(
-            {
-                let mut res = Vector::zeroed(scanned_header.len());
-                res.fill(CellType::Null);
-                res
-            },
-            |mut prev, arr| {
-                prev.iter_mut()
-                    .zip(arr.iter_mut())
-                    .for_each(|(p, c)| *p = p.infer_type(*c));
-                prev
-            },
-        );
+    /// Parsing CSV file `file_name` in chunks of `chunk_rows` rows.
+    ///
+    /// Returns an iterator of `DataFrame` with the same header and column
+    /// types, so that files too large to materialize at once can be
+    /// processed incrementally. Fails if `chunk_rows` is zero.
+    #[inline]
+    pub fn parse_chunks(
+        file_name: &str,
+        chunk_rows: usize,
+    ) -> Result<DataFrameChunkIterator> {
+        DataFrameChunkIterator::new(file_name, chunk_rows)
+    }
 
-        DataFrame::new(result, scanned_header, res)
+    /// Check the CSV file `file_name` against RFC 4180 without parsing
+    /// it into a `DataFrame`, see [`diagnostic::validate`].
+    ///
+    /// Returns the issues found, with the byte where each occurs.
+    pub fn validate(file_name: &str) -> Result<Vec<Diagnostic>> {
+        let fd = std::fs::OpenOptions::new().read(true).open(file_name)?;
+        let mmaped = unsafe { memmap2::MmapOptions::new().map(&fd)? };
+
+        Ok(match decompress(&mmaped)? {
+            Some(decoded) => diagnostic::validate(&decoded),
+            None => diagnostic::validate(&mmaped),
+        })
     }
 
     /// Parsing CSV file `file_name` using single thread
@@ -505,8 +1459,143 @@ impl<'a> CsvParser<'a> {
     /// Opens the file in memory mapped IO (read-only) and
     /// collects the data from file
     #[inline]
-    #[allow(unused)]
-    pub fn parse(file_name: &'a str) -> DataFrame {
+    pub fn parse(file_name: &'a str) -> Result<DataFrame> {
         Self::parse_multi_threaded(file_name, 1)
     }
+
+    /// Total threads [`Self::parse_auto`] uses for a file of `bytes`
+    /// bytes: one per available core, but no more than one per MiB, so
+    /// that small files are parsed on the calling thread.
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// assert_eq!(CsvParser::auto_threads(64 << 10), 1);
+    /// let cores = std::thread::available_parallelism().map_or(1, usize::from);
+    /// assert_eq!(CsvParser::auto_threads(1 << 40), cores);
+    /// ```
+    pub fn auto_threads(bytes: usize) -> usize {
+        let cores = std::thread::available_parallelism().map_or(1, usize::from);
+        cores.min(bytes / AUTO_BYTES_PER_THREAD).max(1)
+    }
+
+    /// Parsing CSV file `file_name` using as many threads as worth it
+    /// for its size, see [`Self::auto_threads`].
+    ///
+    /// ```
+    /// use nn::parser::CsvParser;
+    ///
+    /// let path = std::env::temp_dir().join("nn_parse_auto.csv");
+    /// std::fs::write(&path, "id,name\n1,ada\n2,bob\n").unwrap();
+    ///
+    /// let df = CsvParser::parse_auto(path.to_str().unwrap()).unwrap();
+    /// assert_eq!(df.len(), 2);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn parse_auto(file_name: &'a str) -> Result<DataFrame> {
+        Self::parse_auto_with_options(file_name, &ParserOptions::default())
+    }
+
+    /// Parsing CSV file `file_name` like [`Self::parse_auto`], reading
+    /// records as described by `options`
+    pub fn parse_auto_with_options(
+        file_name: &'a str,
+        options: &ParserOptions,
+    ) -> Result<DataFrame> {
+        let bytes = std::fs::metadata(file_name)?.len();
+        let threads =
+            Self::auto_threads(bytes.try_into().unwrap_or(usize::MAX));
+        Self::parse_with_options(file_name, threads, options)
+    }
+
+    /// Parse `file_name` and deserialize each row into `T`, matching
+    /// the header names with the fields of `T`
+    #[cfg(feature = "serde")]
+    pub fn parse_into<T: serde::de::DeserializeOwned>(
+        file_name: &'a str,
+    ) -> Result<Vec<T>> {
+        Self::parse(file_name)?.iter_as().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CsvParser;
+    use crate::cell::{Cell, CellType};
+    use crate::options::{ParserOptions, RaggedRows};
+
+    #[test]
+    fn text_after_closing_quote() {
+        let df = CsvParser::parse_buffer(
+            b"a,b\n\"12\"ab,\"x\"y\n\"3\",\"1.5\" \n",
+            1,
+        );
+        assert_eq!(df.dtypes(), [CellType::String, CellType::String]);
+        let texts: Vec<_> = df
+            .iter()
+            .map(|row| format!("{},{}", row[0], row[1]))
+            .collect();
+        assert_eq!(texts, ["12,x", "3,1.5"]);
+    }
+
+    #[test]
+    fn doubled_quotes_in_quoted_fields() {
+        let df = CsvParser::parse_buffer(
+            b"a,b\n\"1\"\"2\",\"he said \"\"hi\"\"\"\n\"  pad \",\"\"\"\"\n",
+            1,
+        );
+        assert_eq!(df.dtypes(), [CellType::String, CellType::String]);
+        let texts: Vec<_> = df
+            .iter()
+            .map(|row| format!("{}|{}", row[0], row[1]))
+            .collect();
+        assert_eq!(texts, ["1\"2|he said \"hi\"", "  pad |\""]);
+    }
+
+    #[test]
+    fn space_inside_unquoted_string() {
+        let df = CsvParser::parse_buffer(b"x,y\na b,c\nI  am, d \n", 1);
+        let texts: Vec<_> = df
+            .iter()
+            .map(|row| format!("{}|{}", row[0], row[1]))
+            .collect();
+        assert_eq!(texts, ["a b|c", "I  am|d"]);
+    }
+
+    #[test]
+    fn truncated_ragged_rows_pad_short_records() {
+        let options = ParserOptions::new().ragged_rows(RaggedRows::Truncate);
+        let df = CsvParser::parse_buffer_with_options(
+            b"id,name,age\n1,ada\n2,bob,40,extra\n3\n",
+            1,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(df.len(), 3);
+        assert_eq!(df.column("age").unwrap().as_i64(), [None, Some(40), None]);
+        assert_eq!(df.column("name").unwrap().as_str()[2], None);
+        assert_eq!(df.warnings().len(), 3);
+    }
+
+    #[test]
+    fn interned_text_keeps_filters_and_mixed_columns() {
+        let options = ParserOptions::new()
+            .intern_strings()
+            .filter("region", |cell| cell.as_str() != Some("West"));
+        let df = CsvParser::parse_buffer_with_options(
+            b"region,code\nNorth,A1\nWest,B2\nNorth,42\n",
+            1,
+            &options,
+        )
+        .unwrap();
+
+        // Text among numbers is read back as strings
+        assert_eq!(df.dtypes(), [CellType::Categorical, CellType::String]);
+        assert_eq!(df.categories("region").unwrap(), ["North", "A1"]);
+        assert!(df.categories("code").is_none());
+        assert!(
+            matches!(df.get(0, "code"), Some(Cell::String(code)) if code == "A1")
+        );
+        assert_eq!(df.column("region").unwrap().as_str(), [Some("North"); 2]);
+    }
 }
@@ -0,0 +1,187 @@
+//! Byte scanning a word of 8 bytes at a time (SWAR), used by the
+//! parser to skip the regions of a buffer where the state machine
+//! cannot change state: the inside of quotes, and unquoted strings up
+//! to the next separator, quote or line end.
+
+/// Byte separating the cells of a record
+pub(crate) const SEPARATOR: u8 = b',';
+/// Byte opening and closing a quoted cell
+pub(crate) const QUOTE: u8 = b'"';
+
+/// Bytes of a word
+const WORD: usize = std::mem::size_of::<u64>();
+/// `0x01` in every byte
+const ONES: u64 = u64::MAX / 0xff;
+/// `0x7f` in every byte
+const LOW_BITS: u64 = ONES * 0x7f;
+
+/// `byte` in every byte of a word
+#[inline(always)]
+const fn splat(byte: u8) -> u64 {
+    ONES * byte as u64
+}
+
+/// Word holding the high bit of each zero byte of `word`, and no other
+/// bit
+#[inline(always)]
+const fn zero_bytes(word: u64) -> u64 {
+    !(((word & LOW_BITS) + LOW_BITS) | word | LOW_BITS)
+}
+
+/// Word holding the high bit of each byte of `word` equal to `byte`
+#[inline(always)]
+const fn equal_bytes(word: u64, byte: u8) -> u64 {
+    zero_bytes(word ^ splat(byte))
+}
+
+/// Position of the first byte of `haystack` matching `is_match`, with
+/// `mask` the matches of a word as built by [`equal_bytes`]
+#[inline(always)]
+fn find_by(
+    haystack: &[u8],
+    mask: impl Fn(u64) -> u64,
+    is_match: impl Fn(u8) -> bool,
+) -> Option<usize> {
+    let chunks = haystack.chunks_exact(WORD);
+    let tail = chunks.remainder();
+
+    for (index, chunk) in chunks.enumerate() {
+        // Every chunk holds exactly a word
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        let matches = mask(word);
+        if matches != 0 {
+            // Bytes are read little endian: the first byte is the
+            // lowest one
+            return Some(index * WORD + matches.trailing_zeros() as usize / 8);
+        }
+    }
+
+    let start = haystack.len() - tail.len();
+    tail.iter()
+        .position(|c| is_match(*c))
+        .map(|pos| start + pos)
+}
+
+/// Position of the first `needle` in `haystack`
+#[inline]
+pub(crate) fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    find_by(haystack, |word| equal_bytes(word, needle), |c| c == needle)
+}
+
+/// Position of the first byte of `haystack` that may end an unquoted
+/// cell: a separator, a quote, a line end or the record `terminator`
+#[inline]
+pub(crate) fn find_structural(
+    haystack: &[u8],
+    terminator: u8,
+) -> Option<usize> {
+    find_by(
+        haystack,
+        |word| {
+            equal_bytes(word, SEPARATOR)
+                | equal_bytes(word, QUOTE)
+                | equal_bytes(word, b'\n')
+                | equal_bytes(word, b'\r')
+                | equal_bytes(word, terminator)
+        },
+        |c| matches!(c, SEPARATOR | QUOTE | b'\n' | b'\r') || c == terminator,
+    )
+}
+
+/// Total `needle` in `haystack`
+#[inline]
+pub(crate) fn count_byte(haystack: &[u8], needle: u8) -> usize {
+    let chunks = haystack.chunks_exact(WORD);
+    let tail = chunks.remainder();
+
+    let words: usize = chunks
+        .map(|chunk| {
+            let word = u64::from_le_bytes(chunk.try_into().unwrap());
+            equal_bytes(word, needle).count_ones() as usize
+        })
+        .sum();
+
+    words + tail.iter().filter(|c| **c == needle).count()
+}
+
+/// Position of the record `terminator` ending record `n` (starting
+/// from `0`) of `haystack`, skipping the terminators inside quotes
+pub(crate) fn nth_record_end(
+    haystack: &[u8],
+    terminator: u8,
+    n: usize,
+) -> Option<usize> {
+    let (mut position, mut quoted, mut records) = (0, false, 0);
+    loop {
+        let rest = &haystack[position..];
+        let found = position
+            + if quoted {
+                find_byte(rest, b'"')?
+            } else {
+                find_by(
+                    rest,
+                    |word| {
+                        equal_bytes(word, b'"') | equal_bytes(word, terminator)
+                    },
+                    |c| c == b'"' || c == terminator,
+                )?
+            };
+
+        if haystack[found] == b'"' {
+            quoted = !quoted;
+        } else if records == n {
+            return Some(found);
+        } else {
+            records += 1;
+        }
+        position = found + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytes around the needle, to catch borrows and carries between
+    /// the bytes of a word
+    const FILLERS: [u8; 6] = [b'a', 0x00, 0x2b, 0x2d, 0x80, 0xff];
+
+    #[test]
+    fn find_byte_at_every_position() {
+        for filler in FILLERS {
+            for len in 0..=3 * WORD {
+                let mut haystack = vec![filler; len];
+                assert_eq!(find_byte(&haystack, b','), None, "{}", len);
+                for pos in (0..len).rev() {
+                    haystack[pos] = b',';
+                    assert_eq!(find_byte(&haystack, b','), Some(pos));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_structural_at_word_boundaries() {
+        for byte in [b',', b'"', b'\n', b'\r', b';'] {
+            for pos in [0, WORD - 1, WORD, 2 * WORD - 1, 2 * WORD, 2 * WORD + 3]
+            {
+                let mut haystack = vec![0xc3; 2 * WORD + 5];
+                haystack[pos] = byte;
+                assert_eq!(find_structural(&haystack, b';'), Some(pos));
+            }
+        }
+        assert_eq!(find_structural(&[b'a'; 17], b';'), None);
+    }
+
+    #[test]
+    fn count_byte_across_words() {
+        for len in 0..=3 * WORD {
+            let haystack: Vec<u8> = (0..len)
+                .map(|i| if i % 3 == 0 { b'\n' } else { 0x8a })
+                .collect();
+            assert_eq!(count_byte(&haystack, b'\n'), len.div_ceil(3));
+        }
+        assert_eq!(count_byte(&[b'\n'; 8], b'\n'), 8);
+        assert_eq!(count_byte(&[0xff; 8], 0xff), 8);
+    }
+}
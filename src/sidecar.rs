@@ -0,0 +1,380 @@
+//! Type hints shipped next to a CSV file.
+//!
+//! When parsing `data.csv` with [`CsvParser::parse_with_options`] (and
+//! the functions calling it), the hints in `data.csv.types` are loaded
+//! if the file exists, so that recurring data drops can come with
+//! their corrections without changing the code reading them.
+//!
+//! Hints are written in a subset of TOML: strings, arrays of strings on
+//! a single line, a `[columns.<name>]` table per column (quote names
+//! holding spaces or dots) and `#` comments.
+//!
+//! ```toml
+//! # Markers read as nulls in every column
+//! null_values = ["NA", "-"]
+//!
+//! [columns.zip]
+//! type = "str"
+//!
+//! [columns."date of birth"]
+//! type = "date"
+//! format = "%d/%m/%Y"
+//! ```
+//!
+//! Types are named as displayed by [`CellType`]: `str`, `i64`, `u64`,
+//! `f64`, `dec`, `date`, `time`, `duration`, `bytes`, `bool` or `cat`.
+//! With the `serde` feature, hints can also be written in JSON:
+//!
+//! ```json
+//! {
+//!     "null_values": ["NA", "-"],
+//!     "columns": {"date of birth": {"type": "date", "format": "%d/%m/%Y"}}
+//! }
+//! ```
+//!
+//! Options set explicitly on [`ParserOptions`] take precedence over
+//! the hints. Hints naming a column missing from the file are skipped.
+//!
+//! [`CsvParser::parse_with_options`]: crate::parser::CsvParser::parse_with_options
+
+use crate::cell::CellType;
+use crate::error::{Error, Result};
+use crate::options::ParserOptions;
+
+/// Path of the type hints of the CSV file `file_name`
+#[inline]
+pub fn sidecar_path(file_name: &str) -> String {
+    format!("{}.types", file_name)
+}
+
+/// Hints of a single column
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ColumnHint {
+    /// Name of the column
+    name: String,
+    /// Type the column is converted to
+    dtype: Option<CellType>,
+    /// Format of the dates of the column
+    format: Option<String>,
+}
+
+/// Column types, date formats and null markers read from a type hints
+/// file, see the [module documentation](self).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeHints {
+    /// Fields read as nulls in every column
+    null_values: Vec<String>,
+    /// Hints of each column, in the order of the file
+    columns: Vec<ColumnHint>,
+}
+
+/// Error on `line` (starting from `1`) of the hints
+#[inline]
+fn invalid(line: usize, message: &str) -> Error {
+    Error::InvalidTypeHints {
+        line,
+        message: message.to_owned(),
+    }
+}
+
+/// TOML string at the start of `text`, and the text after it
+fn toml_string(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.strip_prefix('"')?.char_indices();
+    let mut string = String::new();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((string, &text[index + 2..])),
+            '\\' => string.push(match chars.next()?.1 {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            c => string.push(c),
+        }
+    }
+    None
+}
+
+/// TOML string making up the whole `value`
+fn toml_value(value: &str) -> Option<String> {
+    let (string, rest) = toml_string(value)?;
+    rest.trim().is_empty().then_some(string)
+}
+
+/// TOML array of strings making up the whole `value`
+fn toml_array(value: &str) -> Option<Vec<String>> {
+    let mut rest = value.strip_prefix('[')?.trim_start();
+    let mut strings = Vec::new();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return after.trim().is_empty().then_some(strings);
+        }
+        let (string, after) = toml_string(rest)?;
+        strings.push(string);
+        rest = after.trim_start();
+        match rest.strip_prefix(',') {
+            Some(after) => rest = after.trim_start(),
+            None if rest.starts_with(']') => {}
+            None => return None,
+        }
+    }
+}
+
+/// `line` without its trailing comment
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl TypeHints {
+    /// Hints read from `text`, written in TOML or (with the `serde`
+    /// feature) in JSON.
+    ///
+    /// ```
+    /// use nn::cell::CellType;
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    /// use nn::sidecar::TypeHints;
+    ///
+    /// let hints = TypeHints::parse(
+    ///     "null_values = [\"NA\"]\n[columns.zip]\ntype = \"str\"\n",
+    /// )
+    /// .unwrap();
+    /// let options = hints.apply(ParserOptions::new());
+    /// let df = CsvParser::parse_buffer_with_options(
+    ///     b"zip,qty\n75001,NA\n",
+    ///     1,
+    ///     &options,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(df.dtypes(), [CellType::String, CellType::Null]);
+    ///
+    /// assert!(TypeHints::parse("[columns.zip]\ntype = \"text\"\n").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self> {
+        if text.trim_start().starts_with('{') {
+            return Self::parse_json(text);
+        }
+
+        let mut hints = Self::default();
+        let mut column: Option<usize> = None;
+        for (line_no, line) in text.lines().enumerate() {
+            let line_no = line_no + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .strip_suffix(']')
+                    .and_then(|table| table.trim().strip_prefix("columns."))
+                    .map(str::trim)
+                    .ok_or_else(|| {
+                        invalid(line_no, "expected [columns.<name>]")
+                    })?;
+                let name = match name.starts_with('"') {
+                    true => toml_value(name).ok_or_else(|| {
+                        invalid(line_no, "invalid column name")
+                    })?,
+                    false => name.to_owned(),
+                };
+                column = Some(hints.column(name));
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid(line_no, "expected key = value"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let string = || {
+                toml_value(value)
+                    .ok_or_else(|| invalid(line_no, "expected a string"))
+            };
+
+            match (column, key) {
+                (None, "null_values") => {
+                    hints.null_values = toml_array(value).ok_or_else(|| {
+                        invalid(line_no, "expected an array of strings")
+                    })?
+                }
+                (Some(index), "type") => {
+                    hints.columns[index].dtype = Some(
+                        CellType::from_name(&string()?)
+                            .ok_or_else(|| invalid(line_no, "unknown type"))?,
+                    )
+                }
+                (Some(index), "format") => {
+                    hints.columns[index].format = Some(string()?)
+                }
+                _ => return Err(invalid(line_no, "unknown key")),
+            }
+        }
+
+        Ok(hints)
+    }
+
+    /// Hints read from the JSON `text`
+    #[cfg(feature = "serde")]
+    fn parse_json(text: &str) -> Result<Self> {
+        use serde_json::Value;
+
+        let root: Value = serde_json::from_str(text).map_err(|err| {
+            Error::InvalidTypeHints {
+                line: err.line(),
+                message: err.to_string(),
+            }
+        })?;
+        let strings = |value: &Value| -> Option<Vec<String>> {
+            value
+                .as_array()?
+                .iter()
+                .map(|value| value.as_str().map(str::to_owned))
+                .collect()
+        };
+
+        let mut hints = Self::default();
+        if let Some(markers) = root.get("null_values") {
+            hints.null_values = strings(markers)
+                .ok_or_else(|| invalid(0, "expected an array of strings"))?;
+        }
+        let columns = root.get("columns").and_then(Value::as_object);
+        for (name, column) in columns.into_iter().flatten() {
+            let index = hints.column(name.clone());
+            if let Some(dtype) = column.get("type") {
+                hints.columns[index].dtype = Some(
+                    dtype
+                        .as_str()
+                        .and_then(CellType::from_name)
+                        .ok_or_else(|| invalid(0, "unknown type"))?,
+                );
+            }
+            if let Some(format) = column.get("format") {
+                hints.columns[index].format = Some(
+                    format
+                        .as_str()
+                        .ok_or_else(|| invalid(0, "expected a string"))?
+                        .to_owned(),
+                );
+            }
+        }
+
+        Ok(hints)
+    }
+
+    /// JSON hints are not read without the `serde` feature
+    #[cfg(not(feature = "serde"))]
+    fn parse_json(_: &str) -> Result<Self> {
+        Err(invalid(1, "JSON hints require the serde feature"))
+    }
+
+    /// Hints read from the file at `path`, `None` if it does not exist
+    pub fn load(path: &str) -> Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map(Some),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Index of the hints of column `name`, added if missing
+    fn column(&mut self, name: String) -> usize {
+        match self.columns.iter().position(|column| column.name == name) {
+            Some(index) => index,
+            None => {
+                self.columns.push(ColumnHint {
+                    name,
+                    ..ColumnHint::default()
+                });
+                self.columns.len() - 1
+            }
+        }
+    }
+
+    /// Hints of the columns of `header` only, dropping the ones naming
+    /// other columns.
+    ///
+    /// ```
+    /// use nn::options::ParserOptions;
+    /// use nn::parser::CsvParser;
+    /// use nn::sidecar::TypeHints;
+    ///
+    /// let hints = TypeHints::parse("[columns.zip]\ntype = \"str\"\n").unwrap();
+    /// let header = ["qty".to_owned()];
+    /// let options = hints.for_header(&header).apply(ParserOptions::new());
+    /// assert!(CsvParser::parse_buffer_with_options(b"qty\n1\n", 1, &options).is_ok());
+    /// ```
+    pub fn for_header(mut self, header: &[String]) -> Self {
+        self.columns.retain(|column| header.contains(&column.name));
+        self
+    }
+
+    /// `options` with the hints added, keeping the types and date
+    /// formats already set on `options`
+    pub fn apply(self, mut options: ParserOptions) -> ParserOptions {
+        for marker in self.null_values {
+            if !options.null_values.contains(&marker) {
+                options.null_values.push(marker);
+            }
+        }
+
+        for column in self.columns {
+            let ColumnHint {
+                name,
+                dtype,
+                format,
+            } = column;
+            let has_format =
+                options.date_formats.iter().any(|(c, _)| *c == name);
+            if let (Some(format), false) = (format, has_format) {
+                options.date_formats.push((name.clone(), format));
+            }
+            let has_type = options.column_types.iter().any(|(c, _)| *c == name);
+            if let (Some(dtype), false) = (dtype, has_type) {
+                options.column_types.push((name, dtype));
+            }
+        }
+
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::CellType;
+    use crate::options::ParserOptions;
+    use crate::parser::CsvParser;
+
+    #[test]
+    fn unknown_columns_are_skipped() {
+        let path = std::env::temp_dir()
+            .join(format!("nn_sidecar_{}.csv", std::process::id()));
+        let file_name = path.to_str().unwrap();
+        let types = super::sidecar_path(file_name);
+        std::fs::write(&path, b"zip,qty\n75001,2\n").unwrap();
+        std::fs::write(
+            &types,
+            "[columns.zip]\ntype = \"str\"\n[columns.weight]\ntype = \"f64\"\n",
+        )
+        .unwrap();
+
+        let df =
+            CsvParser::parse_with_options(file_name, 1, &ParserOptions::new());
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&types).unwrap();
+
+        let df = df.unwrap();
+        assert_eq!(df.dtypes(), [CellType::String, CellType::I64]);
+    }
+}
@@ -0,0 +1,321 @@
+//! Threads running the jobs of the parser and of parallel `DataFrame`
+//! operations.
+//!
+//! Jobs run on fresh scoped threads, unless a [`ThreadPool`] is
+//! installed on the calling thread with [`ThreadPool::install`] (or
+//! given to the parser with [`ParserOptions::thread_pool`]): its
+//! threads are then reused by every call.
+//!
+//! [`ParserOptions::thread_pool`]: crate::options::ParserOptions::thread_pool
+
+#[cfg(feature = "threads")]
+use std::cell::RefCell;
+#[cfg(feature = "threads")]
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+#[cfg(feature = "threads")]
+use std::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(feature = "threads")]
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Job sent to the threads of a pool
+#[cfg(feature = "threads")]
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+#[cfg(feature = "threads")]
+thread_local! {
+    /// Pool installed on the current thread
+    static CURRENT: RefCell<Option<Arc<ThreadPool>>> = const { RefCell::new(None) };
+}
+
+/// Threads kept alive to run jobs, so that repeated parses (in a long
+/// running service, say) do not pay for spawning threads each time.
+///
+/// Jobs may borrow from the caller: running them blocks until every
+/// job is done, like scoped threads. A panic in a job is raised again
+/// on the caller once the other jobs are done. The threads stop when
+/// the pool is dropped.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use nn::dframe::SortOrder;
+/// use nn::options::ParserOptions;
+/// use nn::parser::CsvParser;
+/// use nn::threads::ThreadPool;
+///
+/// let pool = Arc::new(ThreadPool::new(2));
+/// let options = ParserOptions::new().thread_pool(Arc::clone(&pool));
+/// for _ in 0..3 {
+///     let df = CsvParser::parse_buffer_with_options(b"a,b\n1,2\n3,4\n", 2, &options)
+///         .unwrap();
+///     assert_eq!(df.len(), 2);
+/// }
+///
+/// // Sorting, like any parallel operation, runs on the installed pool
+/// let df = CsvParser::parse_buffer(b"a\n3\n1\n2\n", 1);
+/// let sorted = pool.install(|| df.sort_by(&[("a", SortOrder::Asc)]));
+/// assert_eq!(sorted.unwrap().column("a").unwrap().as_i64(), [Some(1), Some(2), Some(3)]);
+/// ```
+#[cfg(feature = "threads")]
+pub struct ThreadPool {
+    /// Queue of the jobs, closed on drop
+    sender: Mutex<Option<Sender<Job>>>,
+    /// Threads of the pool
+    workers: Vec<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "threads")]
+impl ThreadPool {
+    /// Pool of `threads` threads (at least one)
+    pub fn new(threads: usize) -> Self {
+        let (sender, receiver) = channel::<Job>();
+        let receiver: Arc<Mutex<Receiver<Job>>> =
+            Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                std::thread::spawn(move || loop {
+                    // The lock is released before running the job
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            sender: Mutex::new(Some(sender)),
+            workers,
+        }
+    }
+
+    /// Pool shared by the whole process, with a thread per available
+    /// core, created on first use
+    pub fn global() -> Arc<ThreadPool> {
+        static GLOBAL: OnceLock<Arc<ThreadPool>> = OnceLock::new();
+        let pool = GLOBAL.get_or_init(|| {
+            let threads =
+                std::thread::available_parallelism().map_or(1, usize::from);
+            Arc::new(ThreadPool::new(threads))
+        });
+        Arc::clone(pool)
+    }
+
+    /// Total threads of the pool
+    #[inline]
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Run `f` with the pool installed on the current thread: the jobs
+    /// of the parser and of parallel operations called by `f` run on
+    /// the threads of the pool.
+    pub fn install<R>(self: &Arc<Self>, f: impl FnOnce() -> R) -> R {
+        /// Puts back the pool installed before, even on panic
+        struct Restore(Option<Arc<ThreadPool>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let previous =
+            CURRENT.with(|current| current.replace(Some(Arc::clone(self))));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Run each job in `jobs` on the threads of the pool, and collect
+    /// their results in order
+    pub fn run<T, F, I>(&self, jobs: I) -> Vec<T>
+    where
+        T: Send,
+        F: FnOnce() -> T + Send,
+        I: IntoIterator<Item = F>,
+    {
+        // Collected first, so that a panic of the iterator happens
+        // before any job borrowing from the caller is sent
+        let jobs: Vec<F> = jobs.into_iter().collect();
+        let (results, received) = channel();
+        let mut wait = WaitJobs {
+            received: &received,
+            pending: 0,
+        };
+
+        {
+            let sender = self.sender.lock().unwrap();
+            // The queue is only closed on drop
+            let sender = sender.as_ref().unwrap();
+            for (index, job) in jobs.into_iter().enumerate() {
+                let results = results.clone();
+                let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || {
+                    let result = catch_unwind(AssertUnwindSafe(job));
+                    // The caller waits for every result
+                    let _ = results.send((index, result));
+                });
+                // SAFETY: the borrows of the job outlive it, as this
+                // function only returns, or unwinds, once every job sent
+                // is done: `wait` receives the result of each of them,
+                // even if sending the next one panics. Jobs never unwind
+                // out of the wrapper, and the threads outlive the
+                // `&self` borrow.
+                let job: Job = unsafe { std::mem::transmute(job) };
+                sender.send(job).unwrap();
+                wait.pending += 1;
+            }
+        }
+        drop(results);
+
+        let mut slots: Vec<Option<T>> =
+            (0..wait.pending).map(|_| None).collect();
+        let mut panic = None;
+        while wait.pending > 0 {
+            let (index, result) = received.recv().unwrap();
+            wait.pending -= 1;
+            match result {
+                Ok(value) => slots[index] = Some(value),
+                Err(payload) => panic = Some(payload),
+            }
+        }
+        if let Some(payload) = panic {
+            resume_unwind(payload);
+        }
+
+        slots.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// Result of a job run by [`ThreadPool::run`]
+#[cfg(feature = "threads")]
+type JobResult<T> = (usize, std::thread::Result<T>);
+
+/// Waits for the `pending` jobs sent by [`ThreadPool::run`] when
+/// dropped, so that the jobs borrowing from the caller are done before
+/// it unwinds
+#[cfg(feature = "threads")]
+struct WaitJobs<'r, T> {
+    /// Results of the jobs
+    received: &'r Receiver<JobResult<T>>,
+    /// Jobs sent whose result was not received yet
+    pending: usize,
+}
+
+#[cfg(feature = "threads")]
+impl<T> Drop for WaitJobs<'_, T> {
+    fn drop(&mut self) {
+        while self.pending > 0 && self.received.recv().is_ok() {
+            self.pending -= 1;
+        }
+    }
+}
+
+#[cfg(feature = "threads")]
+impl std::fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("threads", &self.threads())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "threads")]
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Closing the queue stops the threads once they are idle
+        self.sender.lock().unwrap().take();
+        self.workers.drain(..).for_each(|worker| {
+            let _ = worker.join();
+        });
+    }
+}
+
+/// Run each job in `jobs` and collect their results in order.
+///
+/// With the `threads` feature every job runs on the pool installed on
+/// the calling thread, or else on its own scoped thread. Otherwise
+/// jobs run one after another on the calling thread.
+#[cfg(feature = "threads")]
+pub(crate) fn run_jobs<T, F, I>(jobs: I) -> Vec<T>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+    I: IntoIterator<Item = F>,
+{
+    if let Some(pool) = CURRENT.with(|current| current.borrow().clone()) {
+        return pool.run(jobs);
+    }
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> =
+            jobs.into_iter().map(|job| scope.spawn(job)).collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Run each job in `jobs` and collect their results in order.
+///
+/// With the `threads` feature every job runs on the pool installed on
+/// the calling thread, or else on its own scoped thread. Otherwise
+/// jobs run one after another on the calling thread.
+#[cfg(not(feature = "threads"))]
+pub(crate) fn run_jobs<T, F, I>(jobs: I) -> Vec<T>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+    I: IntoIterator<Item = F>,
+{
+    jobs.into_iter().map(|job| job()).collect()
+}
+
+#[cfg(all(test, feature = "threads"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_collects_results_in_order() {
+        let pool = ThreadPool::new(3);
+        let values: Vec<usize> = (0..10).collect();
+        let doubled = pool.run(values.iter().map(|value| move || value * 2));
+        assert_eq!(doubled, (0..10).map(|value| value * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn panicking_iterator_sends_no_job() {
+        let pool = ThreadPool::new(2);
+        let values = vec![1, 2, 3];
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            pool.run(values.iter().map(|value| {
+                assert!(*value < 3, "iterator panics");
+                move || *value
+            }))
+        }));
+        assert!(result.is_err());
+
+        // The pool is still usable afterwards
+        assert_eq!(pool.run(values.iter().map(|value| move || *value)), values);
+    }
+
+    #[test]
+    fn panicking_job_is_raised_after_the_others() {
+        let pool = ThreadPool::new(2);
+        let done = std::sync::atomic::AtomicUsize::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            pool.run((0..4).map(|index| {
+                let done = &done;
+                move || {
+                    assert!(index != 1, "job panics");
+                    done.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                }
+            }))
+        }));
+        assert!(result.is_err());
+        assert_eq!(done.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,54 @@
+/// Kind of issue found in a cell while parsing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// Integer out of range: the cell was promoted to a decimal, and its
+    /// column to `F64`, if it fits in neither an `i64` nor a `u64`, or
+    /// turned to a null in a column read with
+    /// `ParserOptions::checked_integers` if it does not fit in an `i64`
+    IntegerOverflow,
+    /// Field of a column read with `ParserOptions::checked_integers`
+    /// that is not an integer: the cell was turned to a null
+    InvalidInteger,
+    /// Field of a column read with `ParserOptions::exact_decimals` that
+    /// is not a decimal: the cell was turned to a null
+    InvalidDecimal,
+    /// Field of a bytes column that is not valid in its encoding: the
+    /// cell was kept as a string, and its column turned to `String`
+    InvalidEncoding,
+    /// Field that is not valid UTF-8: invalid sequences were replaced
+    /// with `U+FFFD`
+    InvalidUtf8,
+    /// Record holding `fields` fields instead of one per column:
+    /// missing fields were read as nulls and extra fields dropped. The
+    /// column of the warning is always `0`.
+    RaggedRow {
+        /// Total fields of the record
+        fields: usize,
+    },
+}
+
+impl WarningKind {
+    /// Whether the warning is about a whole record rather than one of
+    /// its cells, so that its column does not point at a cell
+    ///
+    /// ```
+    /// use nn::warning::WarningKind;
+    ///
+    /// assert!(WarningKind::RaggedRow { fields: 3 }.is_row_scoped());
+    /// assert!(!WarningKind::InvalidUtf8.is_row_scoped());
+    /// ```
+    pub fn is_row_scoped(self) -> bool {
+        matches!(self, Self::RaggedRow { .. })
+    }
+}
+
+/// Issue found in a cell while parsing, which did not stop the parse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Row of the cell in the parsed `DataFrame`
+    pub row: usize,
+    /// Column of the cell in the parsed `DataFrame`
+    pub column: usize,
+    /// What happened to the cell
+    pub kind: WarningKind,
+}